@@ -0,0 +1,194 @@
+use nalgebra::{DMatrix, DVector};
+use std::f64::consts::PI;
+
+// the number of the thresholds tried by select_threshold, the same as in selectThreshold.m from ex8
+pub const NUMBER_OF_THRESHOLDS: usize = 1000;
+
+// Gaussian models every feature by its own normal distribution like estimateGaussian.m from ex8,
+// which is the same as the multivariate gaussian with a diagonal covariance matrix
+#[derive(Debug, Clone)]
+pub struct Gaussian {
+    pub mu: Vec<f64>,
+    // the variances of the features
+    pub sigma2: Vec<f64>,
+}
+
+impl Gaussian {
+    // fit returns None if some feature has zero variance, e.g. when it's the same in all the examples,
+    // because the density of the feature isn't defined then
+    pub fn fit<X: AsRef<[f64]>>(xs: &[X]) -> Option<Gaussian> {
+        let mu = mean(xs);
+        let n = mu.len();
+
+        let mut sigma2 = vec![0.0; n];
+        for x in xs {
+            for (j, value) in x.as_ref().iter().enumerate() {
+                sigma2[j] += (value - mu[j]).powi(2) / xs.len() as f64;
+            }
+        }
+
+        if sigma2.iter().any(|sigma2| sigma2.is_nan() || *sigma2 <= 0.0) {
+            return None;
+        }
+
+        Some(Gaussian { mu, sigma2 })
+    }
+
+    // probability returns the density in x, the product of the densities of the features
+    pub fn probability(&self, x: &[f64]) -> f64 {
+        x.iter()
+            .zip(&self.mu)
+            .zip(&self.sigma2)
+            .map(|((value, mu), sigma2)| (-(value - mu).powi(2) / (2.0 * sigma2)).exp() / (2.0 * PI * sigma2).sqrt())
+            .product()
+    }
+}
+
+// MultivariateGaussian models the features by one distribution with the full covariance matrix,
+// so it can capture the correlations between the features
+#[derive(Debug, Clone)]
+pub struct MultivariateGaussian {
+    pub mu: Vec<f64>,
+    pub sigma: DMatrix<f64>,
+    sigma_inverse: DMatrix<f64>,
+    // (2 * pi) ^ (-n / 2) * det(sigma) ^ (-1 / 2)
+    normalization: f64,
+}
+
+impl MultivariateGaussian {
+    // fit returns None if the covariance matrix is singular,
+    // e.g. when there are less examples than features or some features are redundant
+    pub fn fit<X: AsRef<[f64]>>(xs: &[X]) -> Option<MultivariateGaussian> {
+        let mu = mean(xs);
+        let n = mu.len();
+
+        let mut sigma = DMatrix::zeros(n, n);
+        for x in xs {
+            let difference = DVector::from_iterator(n, x.as_ref().iter().zip(&mu).map(|(value, mu)| value - mu));
+            sigma += &difference * difference.transpose() / xs.len() as f64;
+        }
+
+        let determinant = sigma.determinant();
+        if determinant.is_nan() || determinant <= 0.0 {
+            return None;
+        }
+        let sigma_inverse = sigma.clone().try_inverse()?;
+        let normalization = (2.0 * PI).powf(-(n as f64) / 2.0) / determinant.sqrt();
+
+        Some(MultivariateGaussian {
+            mu,
+            sigma,
+            sigma_inverse,
+            normalization,
+        })
+    }
+
+    pub fn probability(&self, x: &[f64]) -> f64 {
+        let difference =
+            DVector::from_iterator(self.mu.len(), x.iter().zip(&self.mu).map(|(value, mu)| value - mu));
+        let distance = (difference.transpose() * &self.sigma_inverse * &difference)[(0, 0)];
+
+        self.normalization * (-0.5 * distance).exp()
+    }
+}
+
+// select_threshold tries NUMBER_OF_THRESHOLDS evenly spaced thresholds epsilon between the lowest and
+// the highest probability and returns the one with the best F1 score on the labelled cross validation set
+// along with the score, an example is flagged as an anomaly when its probability is below epsilon
+pub fn select_threshold(cv_ys: &[bool], cv_probabilities: &[f64]) -> (f64, f64) {
+    let min = cv_probabilities.iter().cloned().fold(f64::INFINITY, f64::min);
+    let max = cv_probabilities.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+    let step = (max - min) / NUMBER_OF_THRESHOLDS as f64;
+
+    let mut best_epsilon = 0.0;
+    let mut best_f1 = 0.0;
+
+    for i in 1..NUMBER_OF_THRESHOLDS {
+        let epsilon = min + step * i as f64;
+        let predictions: Vec<bool> = cv_probabilities.iter().map(|p| *p < epsilon).collect();
+        let f1 = f1_score(&predictions, cv_ys);
+
+        if f1 > best_f1 {
+            best_f1 = f1;
+            best_epsilon = epsilon;
+        }
+    }
+
+    (best_epsilon, best_f1)
+}
+
+// f1_score is the harmonic mean of the precision and the recall, the anomalies are the positive class
+pub fn f1_score(predictions: &[bool], ys: &[bool]) -> f64 {
+    let count = |prediction: bool, y: bool| {
+        predictions.iter().zip(ys).filter(|(p, actual)| **p == prediction && **actual == y).count() as f64
+    };
+    let true_positives = count(true, true);
+    let false_positives = count(true, false);
+    let false_negatives = count(false, true);
+
+    if true_positives == 0.0 {
+        return 0.0;
+    }
+
+    let precision = true_positives / (true_positives + false_positives);
+    let recall = true_positives / (true_positives + false_negatives);
+
+    2.0 * precision * recall / (precision + recall)
+}
+
+fn mean<X: AsRef<[f64]>>(xs: &[X]) -> Vec<f64> {
+    let n = xs.first().map_or(0, |x| x.as_ref().len());
+
+    let mut mu = vec![0.0; n];
+    for x in xs {
+        for (sum, value) in mu.iter_mut().zip(x.as_ref()) {
+            *sum += value / xs.len() as f64;
+        }
+    }
+
+    mu
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::anomaly_detection::{f1_score, select_threshold, Gaussian, MultivariateGaussian};
+
+    #[test]
+    fn test_multivariate_gaussian_with_uncorrelated_features() {
+        let xs = [[1.0, 2.0], [3.0, 2.0], [1.0, 4.0], [3.0, 4.0]];
+
+        let gaussian = Gaussian::fit(&xs).unwrap();
+        let multivariate_gaussian = MultivariateGaussian::fit(&xs).unwrap();
+
+        assert_eq!(gaussian.mu, vec![2.0, 3.0]);
+        assert_eq!(gaussian.sigma2, vec![1.0, 1.0]);
+        // the features are uncorrelated, so both models are the same
+        for x in [[2.0, 3.0], [0.0, 1.0], [5.0, 3.5]].iter() {
+            assert!(relative_eq!(gaussian.probability(x), multivariate_gaussian.probability(x), epsilon = 1e-12));
+        }
+        assert!(relative_eq!(gaussian.probability(&[2.0, 3.0]), 1.0 / (2.0 * std::f64::consts::PI)));
+    }
+
+    #[test]
+    fn test_singular_covariance() {
+        assert!(MultivariateGaussian::fit(&[[1.0, 2.0], [2.0, 4.0], [3.0, 6.0]]).is_none());
+    }
+
+    #[test]
+    fn test_zero_variance() {
+        assert!(Gaussian::fit(&[[1.0, 2.0], [3.0, 2.0], [5.0, 2.0]]).is_none());
+        assert!(Gaussian::fit(&[[1.0, 2.0]]).is_none());
+    }
+
+    #[test]
+    fn test_select_threshold() {
+        let probabilities = [0.5, 0.4, 0.45, 0.01, 0.3, 0.02, 0.35];
+        let ys = [false, false, false, true, false, true, false];
+
+        let (epsilon, f1) = select_threshold(&ys, &probabilities);
+
+        assert!(epsilon > 0.02 && epsilon <= 0.3);
+        assert_eq!(f1, 1.0);
+        assert!(relative_eq!(f1_score(&[true, true, false], &[true, false, true]), 0.5));
+    }
+}
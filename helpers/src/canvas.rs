@@ -16,7 +16,9 @@ pub trait Drawer<T> {
         &self, buf: &mut [u8], width: usize, height: usize, app_data: &T,
     ) -> Result<(), Box<dyn Error>>;
     fn get_size(&self) -> Size;
-    fn handle_mouse_move(&mut self, _ctx: &EventCtx, _event: &MouseEvent, _diff: Vec2) {}
+    fn handle_mouse_move(&mut self, _ctx: &EventCtx, _event: &MouseEvent, _diff: Vec2, _app_data: &mut T) {}
+    fn handle_mouse_down(&mut self, _ctx: &EventCtx, _event: &MouseEvent, _app_data: &mut T) {}
+    fn handle_wheel(&mut self, _ctx: &EventCtx, _event: &MouseEvent, _app_data: &mut T) {}
 }
 
 impl<T> Canvas<T> {
@@ -34,7 +36,7 @@ impl<T> Canvas<T> {
 }
 
 impl<T> Widget<T> for Canvas<T> {
-    fn event(&mut self, ctx: &mut EventCtx, event: &Event, _data: &mut T, _env: &Env) {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, _env: &Env) {
         match event {
             Event::WindowConnected => {
                 self.timer_id = ctx.request_timer(self.refresh_period);
@@ -47,14 +49,18 @@ impl<T> Widget<T> for Canvas<T> {
             }
             Event::MouseDown(event) => {
                 self.last_mouse_position = event.pos;
+                self.drawer.handle_mouse_down(ctx, event, data);
             }
             Event::MouseMove(event) => {
                 let pos = event.pos;
                 let diff = self.last_mouse_position - pos;
 
-                self.drawer.handle_mouse_move(ctx, event, diff);
+                self.drawer.handle_mouse_move(ctx, event, diff, data);
                 self.last_mouse_position = event.pos;
             }
+            Event::Wheel(event) => {
+                self.drawer.handle_wheel(ctx, event, data);
+            }
             _ => (),
         }
     }
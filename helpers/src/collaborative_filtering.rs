@@ -0,0 +1,271 @@
+use crate::learning_rate::{LEARNING_RATE_DECREASE_FACTOR, LEARNING_RATE_INCREASE_FACTOR, MIN_LEARNING_RATE};
+use crate::math::standard_normal;
+use nalgebra::{DMatrix, DVector};
+use rand::Rng;
+
+pub const INITIAL_LEARNING_RATE: f64 = 0.001;
+// the step of the finite differences used by check_gradients
+pub const GRADIENT_CHECK_EPSILON: f64 = 1e-4;
+
+// Ratings are the ratings of the movies(rows) by the users(columns) like in ex8_movies.mat:
+// r[(i, j)] is 1 if user j rated movie i and y[(i, j)] is the rating
+#[derive(Debug, Clone)]
+pub struct Ratings {
+    pub y: DMatrix<f64>,
+    pub r: DMatrix<f64>,
+}
+
+impl Ratings {
+    pub fn number_of_movies(&self) -> usize {
+        self.y.nrows()
+    }
+
+    pub fn number_of_users(&self) -> usize {
+        self.y.ncols()
+    }
+
+    // add_user adds a user who rated some movies, ratings are (movie index, rating) pairs.
+    // It returns the index of the new user
+    pub fn add_user(&mut self, ratings: &[(usize, f64)]) -> usize {
+        let user = self.number_of_users();
+        self.y = self.y.clone().insert_column(user, 0.0);
+        self.r = self.r.clone().insert_column(user, 0.0);
+
+        for (movie, rating) in ratings {
+            self.y[(*movie, user)] = *rating;
+            self.r[(*movie, user)] = 1.0;
+        }
+
+        user
+    }
+
+    // normalize subtracts the mean rating of every movie from its ratings, so a user without ratings
+    // gets the mean ratings predicted instead of zeros. It returns the normalized ratings and the means
+    pub fn normalize(&self) -> (Ratings, DVector<f64>) {
+        let mut y = self.y.clone();
+        let mut means = DVector::zeros(self.number_of_movies());
+
+        for movie in 0..self.number_of_movies() {
+            let number_of_ratings = self.r.row(movie).sum();
+            // the movies nobody rated keep the mean of zero
+            if number_of_ratings > 0.0 {
+                means[movie] = self.y.row(movie).component_mul(&self.r.row(movie)).sum() / number_of_ratings;
+            }
+
+            for user in 0..self.number_of_users() {
+                if self.r[(movie, user)] > 0.0 {
+                    y[(movie, user)] -= means[movie];
+                }
+            }
+        }
+
+        (Ratings { y, r: self.r.clone() }, means)
+    }
+}
+
+// cost_and_gradient calculates the regularized collaborative filtering cost like cofiCostFunc.m from ex8
+// and its gradients over the movie features x(a row per movie) and the user parameters theta(a row per user).
+// Only the observed ratings contribute to the cost
+pub fn cost_and_gradient(
+    x: &DMatrix<f64>,
+    theta: &DMatrix<f64>,
+    ratings: &Ratings,
+    regularization_rate: f64,
+) -> (f64, DMatrix<f64>, DMatrix<f64>) {
+    let errors = (x * theta.transpose() - &ratings.y).component_mul(&ratings.r);
+
+    let cost = errors.norm_squared() / 2.0
+        + regularization_rate / 2.0 * (theta.norm_squared() + x.norm_squared());
+    let x_gradient = &errors * theta + regularization_rate * x;
+    let theta_gradient = errors.transpose() * x + regularization_rate * theta;
+
+    (cost, x_gradient, theta_gradient)
+}
+
+// Recommender is a trained collaborative filtering model
+#[derive(Debug, Clone)]
+pub struct Recommender {
+    pub x: DMatrix<f64>,
+    pub theta: DMatrix<f64>,
+    // the mean rating of every movie, it's added back to the predictions
+    pub means: DVector<f64>,
+    // the value of the cost function after every iteration
+    pub costs: Vec<f64>,
+}
+
+impl Recommender {
+    // train learns the movie features and the user parameters from the mean normalized ratings by gradient descent.
+    // The learning rate grows while the cost goes down, a step increasing the cost is undone and the rate is decreased
+    pub fn train<R: Rng>(
+        ratings: &Ratings,
+        number_of_features: usize,
+        regularization_rate: f64,
+        number_of_iterations: usize,
+        rng: &mut R,
+    ) -> Recommender {
+        let (normalized, means) = ratings.normalize();
+        let mut x = random_matrix(ratings.number_of_movies(), number_of_features, rng);
+        let mut theta = random_matrix(ratings.number_of_users(), number_of_features, rng);
+
+        let mut learning_rate = INITIAL_LEARNING_RATE;
+        let (mut cost, mut x_gradient, mut theta_gradient) =
+            cost_and_gradient(&x, &theta, &normalized, regularization_rate);
+        let mut costs = Vec::new();
+
+        for _ in 0..number_of_iterations {
+            if learning_rate < MIN_LEARNING_RATE {
+                break;
+            }
+
+            let new_x = &x - learning_rate * &x_gradient;
+            let new_theta = &theta - learning_rate * &theta_gradient;
+            let (new_cost, new_x_gradient, new_theta_gradient) =
+                cost_and_gradient(&new_x, &new_theta, &normalized, regularization_rate);
+
+            if new_cost.is_finite() && new_cost <= cost {
+                x = new_x;
+                theta = new_theta;
+                cost = new_cost;
+                x_gradient = new_x_gradient;
+                theta_gradient = new_theta_gradient;
+                learning_rate *= LEARNING_RATE_INCREASE_FACTOR;
+            } else {
+                learning_rate *= LEARNING_RATE_DECREASE_FACTOR;
+            }
+
+            costs.push(cost);
+        }
+
+        Recommender { x, theta, means, costs }
+    }
+
+    pub fn predict(&self, movie: usize, user: usize) -> f64 {
+        self.x.row(movie).dot(&self.theta.row(user)) + self.means[movie]
+    }
+
+    // recommend returns up to n movies the user hasn't rated yet with the highest predicted ratings
+    pub fn recommend(&self, ratings: &Ratings, user: usize, n: usize) -> Vec<(usize, f64)> {
+        let mut predictions: Vec<(usize, f64)> = (0..ratings.number_of_movies())
+            .filter(|movie| ratings.r[(*movie, user)] == 0.0)
+            .map(|movie| (movie, self.predict(movie, user)))
+            .collect();
+
+        predictions.sort_by(|(_, a), (_, b)| b.partial_cmp(a).unwrap());
+        predictions.truncate(n);
+
+        predictions
+    }
+}
+
+// check_gradients compares the gradients of cost_and_gradient with the finite differences on a small random
+// problem like checkCostFunction.m from ex8. It returns the relative difference which should be less than 1e-9
+pub fn check_gradients<R: Rng>(regularization_rate: f64, rng: &mut R) -> f64 {
+    let x_t = random_matrix(4, 3, rng);
+    let theta_t = random_matrix(5, 3, rng);
+
+    // about a half of the ratings is missing
+    let mut y = &x_t * theta_t.transpose();
+    let mut r = DMatrix::zeros(y.nrows(), y.ncols());
+    for (rating, is_rated) in y.iter_mut().zip(r.iter_mut()) {
+        if rng.gen_bool(0.5) {
+            *is_rated = 1.0;
+        } else {
+            *rating = 0.0;
+        }
+    }
+    let ratings = Ratings { y, r };
+
+    let x = random_matrix(x_t.nrows(), x_t.ncols(), rng);
+    let theta = random_matrix(theta_t.nrows(), theta_t.ncols(), rng);
+    let (_, x_gradient, theta_gradient) = cost_and_gradient(&x, &theta, &ratings, regularization_rate);
+
+    let mut analytical = Vec::new();
+    let mut numerical = Vec::new();
+
+    for i in 0..x.len() {
+        let cost = |shift: f64| {
+            let mut shifted = x.clone();
+            shifted[i] += shift;
+            cost_and_gradient(&shifted, &theta, &ratings, regularization_rate).0
+        };
+        analytical.push(x_gradient[i]);
+        numerical.push((cost(GRADIENT_CHECK_EPSILON) - cost(-GRADIENT_CHECK_EPSILON)) / (2.0 * GRADIENT_CHECK_EPSILON));
+    }
+
+    for i in 0..theta.len() {
+        let cost = |shift: f64| {
+            let mut shifted = theta.clone();
+            shifted[i] += shift;
+            cost_and_gradient(&x, &shifted, &ratings, regularization_rate).0
+        };
+        analytical.push(theta_gradient[i]);
+        numerical.push((cost(GRADIENT_CHECK_EPSILON) - cost(-GRADIENT_CHECK_EPSILON)) / (2.0 * GRADIENT_CHECK_EPSILON));
+    }
+
+    let analytical = DVector::from_vec(analytical);
+    let numerical = DVector::from_vec(numerical);
+
+    (&numerical - &analytical).norm() / (&numerical + &analytical).norm()
+}
+
+fn random_matrix<R: Rng>(n_rows: usize, n_columns: usize, rng: &mut R) -> DMatrix<f64> {
+    DMatrix::from_fn(n_rows, n_columns, |_, _| standard_normal(rng))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::collaborative_filtering::{check_gradients, cost_and_gradient, Ratings, Recommender};
+    use nalgebra::DMatrix;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_cost() {
+        let x = DMatrix::from_row_slice(2, 2, &[1.0, 2.0, 3.0, 4.0]);
+        let theta = DMatrix::from_row_slice(1, 2, &[1.0, 1.0]);
+        // the second movie isn't rated, so its prediction doesn't matter
+        let ratings = Ratings {
+            y: DMatrix::from_row_slice(2, 1, &[5.0, 0.0]),
+            r: DMatrix::from_row_slice(2, 1, &[1.0, 0.0]),
+        };
+
+        let (cost, x_gradient, theta_gradient) = cost_and_gradient(&x, &theta, &ratings, 0.0);
+
+        assert!(relative_eq!(cost, 2.0));
+        assert_eq!(x_gradient, DMatrix::from_row_slice(2, 2, &[-2.0, -2.0, 0.0, 0.0]));
+        assert_eq!(theta_gradient, DMatrix::from_row_slice(1, 2, &[-2.0, -4.0]));
+
+        let (cost, _, _) = cost_and_gradient(&x, &theta, &ratings, 1.0);
+        assert!(relative_eq!(cost, 2.0 + (2.0 + 30.0) / 2.0));
+    }
+
+    #[test]
+    fn test_gradients() {
+        let mut rng = StdRng::seed_from_u64(1);
+
+        assert!(check_gradients(0.0, &mut rng) < 1e-9);
+        assert!(check_gradients(1.5, &mut rng) < 1e-9);
+    }
+
+    #[test]
+    fn test_recommendations_for_new_user() {
+        // two groups of movies, the users like either the first or the second group
+        let mut ratings = Ratings {
+            y: DMatrix::from_row_slice(4, 4, &[
+                5.0, 5.0, 1.0, 1.0,
+                5.0, 4.0, 1.0, 2.0,
+                1.0, 1.0, 5.0, 4.0,
+                2.0, 1.0, 5.0, 5.0,
+            ]),
+            r: DMatrix::from_element(4, 4, 1.0),
+        };
+        let user = ratings.add_user(&[(0, 5.0)]);
+
+        let recommender = Recommender::train(&ratings, 2, 0.1, 1000, &mut StdRng::seed_from_u64(1));
+        let recommendations = recommender.recommend(&ratings, user, 3);
+
+        assert_eq!(recommendations.len(), 3);
+        assert_eq!(recommendations[0].0, 1);
+        assert!(recommender.costs.windows(2).all(|pair| pair[1] <= pair[0]));
+    }
+}
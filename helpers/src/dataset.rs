@@ -0,0 +1,262 @@
+use std::error::Error;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+pub const DELIMITER: char = ',';
+
+// Examples are the features and the labels of the examples
+pub type Examples<const N_FEATURES: usize> = (Vec<[f64; N_FEATURES]>, Vec<f64>);
+
+// LabelColumn chooses the column with the labels, the rest of the columns are the features
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum LabelColumn {
+    // like in ex1data1.txt and ex2data1.txt
+    #[default]
+    Last,
+    // 0-based
+    Index(usize),
+    // the name in the header
+    Name(String),
+}
+
+impl FromStr for LabelColumn {
+    type Err = Box<dyn Error>;
+
+    // from_str parses "last", a 1-based column number or a column name
+    fn from_str(value: &str) -> Result<LabelColumn, Self::Err> {
+        if value == "last" {
+            return Ok(LabelColumn::Last);
+        }
+
+        match value.parse::<usize>() {
+            Ok(0) => Err("the columns are numbered from 1".into()),
+            Ok(number) => Ok(LabelColumn::Index(number - 1)),
+            Err(_) => Ok(LabelColumn::Name(value.to_string())),
+        }
+    }
+}
+
+// Dataset is a table of numbers from a comma separated file like the ones from the course
+#[derive(Debug, Clone, PartialEq)]
+pub struct Dataset {
+    // the file name or whatever the data came from, it's used in the errors
+    pub source: String,
+    // the names of the feature columns if the file has a header
+    pub feature_names: Option<Vec<String>>,
+    pub xs: Vec<Vec<f64>>,
+    pub ys: Vec<f64>,
+    // the line number of every example for the errors
+    pub lines: Vec<usize>,
+}
+
+impl Dataset {
+    pub fn load(path: &Path, label_column: &LabelColumn) -> Result<Dataset, Box<dyn Error>> {
+        let content = fs::read_to_string(path).map_err(|error| format!("{}: {}", path.display(), error))?;
+
+        Dataset::parse(&path.display().to_string(), &content, label_column)
+    }
+
+    // parse reads the lines of comma separated numbers skipping the empty ones. The first line is the header
+    // if none of its fields are numbers. Every line should have the same number of columns
+    pub fn parse(source: &str, content: &str, label_column: &LabelColumn) -> Result<Dataset, Box<dyn Error>> {
+        let mut lines = content
+            .lines()
+            .enumerate()
+            .map(|(i, line)| (i + 1, line.trim()))
+            .filter(|(_, line)| !line.is_empty())
+            .peekable();
+        let error = |line: usize, message: String| -> Box<dyn Error> { format!("{}:{}: {}", source, line, message).into() };
+
+        let header = match lines.peek() {
+            Some((_, line)) if split(line).iter().all(|field| field.parse::<f64>().is_err()) => {
+                lines.next().map(|(number, line)| (number, split(line)))
+            }
+            _ => None,
+        };
+
+        let mut rows = Vec::new();
+        for (number, line) in lines {
+            let row = split(line)
+                .iter()
+                .enumerate()
+                .map(|(column, field)| {
+                    parse_number(field).map_err(|message| error(number, format!("column {}: {}", column + 1, message)))
+                })
+                .collect::<Result<Vec<f64>, Box<dyn Error>>>()?;
+            rows.push((number, row));
+        }
+
+        // a header without the examples isn't enough either
+        if rows.is_empty() {
+            return Err(format!("{}: there are no examples", source).into());
+        }
+        let number_of_columns = match &header {
+            Some((_, names)) => names.len(),
+            None => rows[0].1.len(),
+        };
+        if number_of_columns < 2 {
+            return Err(format!("{}: there should be at least 2 columns: features and a label", source).into());
+        }
+        for (number, row) in &rows {
+            if row.len() != number_of_columns {
+                return Err(error(*number, format!("expected {} columns, got {}", number_of_columns, row.len())));
+            }
+        }
+
+        let label_index = match label_column {
+            LabelColumn::Last => number_of_columns - 1,
+            LabelColumn::Index(index) if *index < number_of_columns => *index,
+            LabelColumn::Index(index) => {
+                return Err(format!("{}: there's no column {}, there are {}", source, index + 1, number_of_columns).into());
+            }
+            LabelColumn::Name(name) => match &header {
+                Some((number, names)) => names
+                    .iter()
+                    .position(|column_name| column_name == name)
+                    .ok_or_else(|| error(*number, format!("there's no column \"{}\" in the header", name)))?,
+                None => return Err(format!("{}: there's no header to find the column \"{}\" in", source, name).into()),
+            },
+        };
+
+        let mut dataset = Dataset {
+            source: source.to_string(),
+            feature_names: header.map(|(_, mut names)| {
+                names.remove(label_index);
+                names
+            }),
+            xs: Vec::new(),
+            ys: Vec::new(),
+            lines: Vec::new(),
+        };
+        for (number, mut row) in rows {
+            dataset.ys.push(row.remove(label_index));
+            dataset.xs.push(row);
+            dataset.lines.push(number);
+        }
+
+        Ok(dataset)
+    }
+
+    pub fn number_of_features(&self) -> usize {
+        match (&self.feature_names, self.xs.first()) {
+            (Some(names), _) => names.len(),
+            (None, Some(x)) => x.len(),
+            (None, None) => 0,
+        }
+    }
+
+    // examples converts the dataset to the fixed size examples used by the demos
+    pub fn examples<const N_FEATURES: usize>(&self) -> Result<Examples<N_FEATURES>, Box<dyn Error>> {
+        if self.number_of_features() != N_FEATURES {
+            return Err(format!(
+                "{}: expected {} features, got {}",
+                self.source,
+                N_FEATURES,
+                self.number_of_features()
+            ).into());
+        }
+
+        let mut xs = Vec::new();
+        for x in &self.xs {
+            let mut example = [0.0; N_FEATURES];
+            example.copy_from_slice(x);
+            xs.push(example);
+        }
+
+        Ok((xs, self.ys.clone()))
+    }
+
+    // check_labels returns an error pointing to the first example with an invalid label
+    pub fn check_labels<F: Fn(f64) -> bool>(&self, is_valid: F, expected: &str) -> Result<(), Box<dyn Error>> {
+        match self.ys.iter().zip(&self.lines).find(|(y, _)| !is_valid(**y)) {
+            Some((y, line)) => Err(format!("{}:{}: the label is {}, expected {}", self.source, line, y, expected).into()),
+            None => Ok(()),
+        }
+    }
+}
+
+// parse_number parses a number typed by the user or read from a file,
+// "inf" and "NaN" are valid f64 but they aren't valid examples
+pub fn parse_number(field: &str) -> Result<f64, Box<dyn Error>> {
+    field
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|value| value.is_finite())
+        .ok_or_else(|| format!("\"{}\" isn't a number", field.trim()).into())
+}
+
+fn split(line: &str) -> Vec<String> {
+    line.split(DELIMITER).map(|field| field.trim().to_string()).collect()
+}
+
+// format_examples writes the examples in the format of ex1data1.txt: the features and then the label,
+// the values are written with all the digits so they're read back exactly
+pub fn format_examples<X: AsRef<[f64]>>(xs: &[X], ys: &[f64]) -> String {
+    let mut content = String::new();
+
+    for (x, y) in xs.iter().zip(ys) {
+        for feature in x.as_ref() {
+            write!(content, "{}{}", feature, DELIMITER).unwrap();
+        }
+        writeln!(content, "{}", y).unwrap();
+    }
+
+    content
+}
+
+pub fn save_examples<X: AsRef<[f64]>>(path: &Path, xs: &[X], ys: &[f64]) -> Result<(), Box<dyn Error>> {
+    fs::write(path, format_examples(xs, ys)).map_err(|error| format!("{}: {}", path.display(), error))?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::dataset::{format_examples, parse_number, Dataset, LabelColumn};
+
+    #[test]
+    fn test_parse() {
+        let content = "size, bedrooms, price\n2104,3,399900\n\n1600,3,329900\n";
+
+        let dataset = Dataset::parse("ex1data2.txt", content, &LabelColumn::Last).unwrap();
+        assert_eq!(dataset.feature_names, Some(vec!["size".to_string(), "bedrooms".to_string()]));
+        assert_eq!(dataset.xs, vec![vec![2104.0, 3.0], vec![1600.0, 3.0]]);
+        assert_eq!(dataset.ys, vec![399900.0, 329900.0]);
+        assert_eq!(dataset.lines, vec![2, 4]);
+
+        let dataset = Dataset::parse("ex1data2.txt", content, &"bedrooms".parse().unwrap()).unwrap();
+        assert_eq!(dataset.xs, vec![vec![2104.0, 399900.0], vec![1600.0, 329900.0]]);
+        assert_eq!(dataset.examples::<1>().unwrap_err().to_string(), "ex1data2.txt: expected 1 features, got 2");
+
+        let error = Dataset::parse("data.txt", "1,2\n3,x\n", &LabelColumn::Last).unwrap_err();
+        assert_eq!(error.to_string(), "data.txt:2: column 2: \"x\" isn't a number");
+        let error = Dataset::parse("data.txt", "1,2\n3,4,5\n", &LabelColumn::Last).unwrap_err();
+        assert_eq!(error.to_string(), "data.txt:2: expected 2 columns, got 3");
+        let error = Dataset::parse("data.txt", "size,bedrooms,price\n\n", &LabelColumn::Last).unwrap_err();
+        assert_eq!(error.to_string(), "data.txt: there are no examples");
+        let error = Dataset::parse("data.txt", "\n", &LabelColumn::Last).unwrap_err();
+        assert_eq!(error.to_string(), "data.txt: there are no examples");
+    }
+
+    #[test]
+    fn test_parse_number() {
+        assert_eq!(parse_number(" 1.5 ").unwrap(), 1.5);
+        assert!(parse_number("x").is_err());
+        assert!(parse_number("inf").is_err());
+        assert!(parse_number("NaN").is_err());
+    }
+
+    #[test]
+    fn test_format_and_parse_back() {
+        let xs = [[0.1, 2.0], [-3.5, 1e-7]];
+        let ys = [1.0, 0.0];
+
+        let dataset = Dataset::parse("saved", &format_examples(&xs, &ys), &LabelColumn::Last).unwrap();
+
+        assert_eq!(dataset.examples::<2>().unwrap(), (xs.to_vec(), ys.to_vec()));
+        assert!(dataset.check_labels(|y| y == 0.0 || y == 1.0, "0 or 1").is_ok());
+    }
+}
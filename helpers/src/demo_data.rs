@@ -1,8 +1,28 @@
+use crate::dataset::{save_examples, Dataset};
+use crate::learning_settings::LearningSettings;
+use crate::online_learning::Example;
+use std::error::Error;
+use std::path::Path;
+use std::sync::mpsc::Sender;
+
+// the trajectory keeps at most this many values of theta, every second one is dropped when there're more
+pub const MAX_TRAJECTORY_LENGTH: usize = 1000;
+
 #[derive(Debug)]
 pub struct RegressionDemoDataNFeatures<const N_FEATURES: usize> where [(); N_FEATURES + 1]: {
     pub xs: Vec<[f64; N_FEATURES]>,
     pub ys: Vec<f64>,
     pub theta: [f64; N_FEATURES + 1],
+    // the learning rate currently in effect, it's adjusted by the learning thread
+    pub learning_rate: f64,
+    // the settings chosen in the UI, the learning thread reads them on every iteration
+    pub settings: LearningSettings,
+    // the number of iterations since the learning started
+    pub iteration: usize,
+    // the values theta took since the learning started, they're drawn on the cost surface
+    pub trajectory: Vec<[f64; N_FEATURES + 1]>,
+    // in the online learning mode the new points are sent to the learning thread instead of being stored
+    pub online_examples: Option<Sender<Example<N_FEATURES>>>,
 }
 
 pub type RegressionDemoData1Feature = RegressionDemoDataNFeatures<1>;
@@ -10,14 +30,101 @@ pub type RegressionDemoData2Features = RegressionDemoDataNFeatures<2>;
 
 impl RegressionDemoData1Feature {
     pub fn add_point(&mut self, point: [f64; 2]) {
-        self.xs.push([point[0]]);
-        self.ys.push(point[1]);
+        self.add_example([point[0]], point[1]);
     }
 }
 
 impl RegressionDemoData2Features {
     pub fn add_point(&mut self, point: [f64; 3]) {
-        self.xs.push([point[0], point[1]]);
-        self.ys.push(point[2]);
+        self.add_example([point[0], point[1]], point[2]);
+    }
+}
+
+impl<const N_FEATURES: usize> RegressionDemoDataNFeatures<N_FEATURES> where [(); N_FEATURES + 1]: {
+    pub fn add_example(&mut self, x: [f64; N_FEATURES], y: f64) {
+        match &self.online_examples {
+            // the learning thread is gone only when the app is closing, so the example can be dropped
+            Some(online_examples) => {
+                let _ = online_examples.send((x, y));
+            }
+            None => {
+                self.xs.push(x);
+                self.ys.push(y);
+            }
+        }
+    }
+
+    // move_example and remove_example edit the stored examples, the examples streamed in the online learning mode
+    // can't be edited. The index can be outdated if the examples were changed after it was found
+    pub fn move_example(&mut self, i: usize, x: [f64; N_FEATURES], y: f64) {
+        if self.online_examples.is_none() && i < self.xs.len() {
+            self.xs[i] = x;
+            self.ys[i] = y;
+        }
+    }
+
+    pub fn remove_example(&mut self, i: usize) {
+        if self.online_examples.is_none() && i < self.xs.len() {
+            self.xs.remove(i);
+            self.ys.remove(i);
+        }
+    }
+
+    // reset_theta starts the learning over
+    pub fn reset_theta(&mut self) {
+        self.theta = [self.settings.default_theta; N_FEATURES + 1];
+        self.iteration = 0;
+        self.trajectory = vec![self.theta];
+    }
+
+    pub fn record_theta(&mut self) {
+        let theta = self.theta;
+        push_to_trajectory(&mut self.trajectory, theta);
+    }
+
+    // set_examples replaces the examples with the ones from the dataset
+    pub fn set_examples(&mut self, dataset: &Dataset) -> Result<(), Box<dyn Error>> {
+        let (xs, ys) = dataset.examples::<N_FEATURES>()?;
+        self.xs = xs;
+        self.ys = ys;
+
+        Ok(())
+    }
+
+    pub fn save_examples(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        save_examples(path, &self.xs, &self.ys)
+    }
+}
+
+// push_to_trajectory adds theta to the trajectory, every second value is dropped when it gets too long,
+// so the trajectory keeps its shape from the start to the current theta
+pub fn push_to_trajectory<T>(trajectory: &mut Vec<T>, theta: T) {
+    trajectory.push(theta);
+
+    if trajectory.len() > MAX_TRAJECTORY_LENGTH {
+        let last = trajectory.len() - 1;
+        let mut i = 0;
+        trajectory.retain(|_| {
+            i += 1;
+            (i - 1) % 2 == 0 || i - 1 == last
+        });
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::demo_data::{push_to_trajectory, MAX_TRAJECTORY_LENGTH};
+
+    #[test]
+    fn test_push_to_trajectory() {
+        let mut trajectory = Vec::new();
+        for i in 0..=MAX_TRAJECTORY_LENGTH {
+            push_to_trajectory(&mut trajectory, i);
+        }
+
+        assert!(trajectory.len() <= MAX_TRAJECTORY_LENGTH);
+        assert_eq!(trajectory[0], 0);
+        assert_eq!(trajectory[1], 2);
+        assert_eq!(trajectory.last(), Some(&MAX_TRAJECTORY_LENGTH));
+    }
+}
@@ -0,0 +1,222 @@
+use crate::canvas::Drawer;
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Duration;
+
+pub const DEFAULT_NUMBER_OF_FRAMES: usize = 50;
+// GIF stores the delays in hundredths of a second, so the period is rounded to 10ms
+pub const DEFAULT_FRAME_PERIOD: u64 = 100;
+// 1 is the best quality and 30 is the fastest quantization of the colors
+pub const GIF_QUANTIZATION_SPEED: i32 = 10;
+
+pub const EXPORT_USAGE: &str = "export without opening a window: \
+--snapshot <image.png> | --record <animation.gif> [--size <width>x<height>] [--panel <number>] \
+[--wait <ms before the first frame>] [--frames <number>] [--frame-period <ms>]";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExportMode {
+    Snapshot(PathBuf),
+    Recording(PathBuf),
+}
+
+// ExportArguments tells the demo to render one of its drawers to a file instead of opening a window
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExportArguments {
+    pub mode: ExportMode,
+    // the size of the drawer in the window is used by default
+    pub size: Option<(usize, usize)>,
+    // the index of the drawer for the demos with several charts
+    pub panel: usize,
+    // the time the demo runs before the first frame, e.g. to let the learning thread converge
+    pub wait: Duration,
+    pub number_of_frames: usize,
+    pub frame_period: Duration,
+}
+
+impl ExportArguments {
+    // parse takes the export arguments out of the command line arguments and returns the rest of them
+    // for the demo. There are no export arguments if neither --snapshot nor --record is given
+    pub fn parse(args: Vec<String>) -> Result<(Option<ExportArguments>, Vec<String>), Box<dyn Error>> {
+        let mut mode = None;
+        let mut size = None;
+        let mut panel = 0;
+        let mut wait = Duration::from_millis(0);
+        let mut number_of_frames = DEFAULT_NUMBER_OF_FRAMES;
+        let mut frame_period = Duration::from_millis(DEFAULT_FRAME_PERIOD);
+        let mut is_export_option_given = false;
+        let mut rest = Vec::new();
+
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let mut value = || args.next().ok_or(format!("{} requires a value", arg));
+
+            match arg.as_str() {
+                "--snapshot" | "--record" if mode.is_some() => {
+                    return Err("only one of --snapshot and --record can be given".into());
+                }
+                "--snapshot" => mode = Some(ExportMode::Snapshot(PathBuf::from(value()?))),
+                "--record" => mode = Some(ExportMode::Recording(PathBuf::from(value()?))),
+                "--size" => size = Some(parse_size(&value()?)?),
+                "--panel" => panel = value()?.parse()?,
+                "--wait" => wait = Duration::from_millis(value()?.parse()?),
+                "--frames" => number_of_frames = value()?.parse()?,
+                "--frame-period" => frame_period = Duration::from_millis(value()?.parse()?),
+                _ => {
+                    rest.push(arg);
+                    continue;
+                }
+            }
+
+            is_export_option_given = true;
+        }
+
+        let mode = match mode {
+            Some(mode) => mode,
+            None if is_export_option_given => return Err("--snapshot or --record is required to export".into()),
+            None => return Ok((None, rest)),
+        };
+        if number_of_frames == 0 {
+            return Err("--frames should be positive".into());
+        }
+
+        Ok((
+            Some(ExportArguments {
+                mode,
+                size,
+                panel,
+                wait,
+                number_of_frames,
+                frame_period,
+            }),
+            rest,
+        ))
+    }
+
+    // run waits and renders the chosen drawer to the file
+    pub fn run<T>(&self, drawers: &[Box<dyn Drawer<T>>], app_data: &T) -> Result<(), Box<dyn Error>> {
+        let drawer = drawers
+            .get(self.panel)
+            .ok_or(format!("--panel should be less than {}", drawers.len()))?;
+        let (width, height) = match self.size {
+            Some(size) => size,
+            None => {
+                let size = drawer.get_size();
+                (size.width as usize, size.height as usize)
+            }
+        };
+
+        thread::sleep(self.wait);
+
+        match &self.mode {
+            ExportMode::Snapshot(path) => save_png(path, drawer.as_ref(), app_data, width, height),
+            ExportMode::Recording(path) => record_gif(
+                path,
+                drawer.as_ref(),
+                app_data,
+                width,
+                height,
+                self.number_of_frames,
+                self.frame_period,
+            ),
+        }
+    }
+}
+
+// parse_size parses sizes like 800x600
+fn parse_size(value: &str) -> Result<(usize, usize), Box<dyn Error>> {
+    let (width, height) = value
+        .split_once('x')
+        .ok_or(format!("the size should look like 800x600, got {}", value))?;
+    let size: (usize, usize) = (width.parse()?, height.parse()?);
+    if size.0 == 0 || size.1 == 0 {
+        return Err("the size should be positive".into());
+    }
+
+    Ok(size)
+}
+
+// render draws the current state of the demo to an RGB buffer like Canvas does
+pub fn render<T>(drawer: &dyn Drawer<T>, app_data: &T, width: usize, height: usize) -> Result<Vec<u8>, Box<dyn Error>> {
+    let mut buf = vec![0u8; width * height * 3];
+    drawer.draw_demo_data(&mut buf, width, height, app_data)?;
+
+    Ok(buf)
+}
+
+pub fn save_png<T>(
+    path: &Path,
+    drawer: &dyn Drawer<T>,
+    app_data: &T,
+    width: usize,
+    height: usize,
+) -> Result<(), Box<dyn Error>> {
+    let buf = render(drawer, app_data, width, height)?;
+    image::save_buffer(path, &buf, width as u32, height as u32, image::ColorType::Rgb8)
+        .map_err(|error| format!("{}: {}", path.display(), error))?;
+
+    Ok(())
+}
+
+// record_gif renders a frame every frame_period while the learning thread keeps running
+// and writes them to a looped animation
+pub fn record_gif<T>(
+    path: &Path,
+    drawer: &dyn Drawer<T>,
+    app_data: &T,
+    width: usize,
+    height: usize,
+    number_of_frames: usize,
+    frame_period: Duration,
+) -> Result<(), Box<dyn Error>> {
+    let (gif_width, gif_height) = (u16::try_from(width)?, u16::try_from(height)?);
+    let file = File::create(path).map_err(|error| format!("{}: {}", path.display(), error))?;
+    let mut encoder = gif::Encoder::new(file, gif_width, gif_height, &[])?;
+    encoder.set_repeat(gif::Repeat::Infinite)?;
+
+    for _ in 0..number_of_frames {
+        let buf = render(drawer, app_data, width, height)?;
+
+        let mut frame = gif::Frame::from_rgb_speed(gif_width, gif_height, &buf, GIF_QUANTIZATION_SPEED);
+        frame.delay = (frame_period.as_millis() / 10) as u16;
+        encoder.write_frame(&frame)?;
+
+        thread::sleep(frame_period);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::export::{ExportArguments, ExportMode, DEFAULT_NUMBER_OF_FRAMES};
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    fn to_args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse() {
+        let (export, rest) = ExportArguments::parse(to_args(&[
+            "--online", "--record", "demo.gif", "--size", "640x480", "--frame-period", "50",
+        ])).unwrap();
+
+        assert_eq!(rest, to_args(&["--online"]));
+        assert_eq!(export, Some(ExportArguments {
+            mode: ExportMode::Recording(PathBuf::from("demo.gif")),
+            size: Some((640, 480)),
+            panel: 0,
+            wait: Duration::from_millis(0),
+            number_of_frames: DEFAULT_NUMBER_OF_FRAMES,
+            frame_period: Duration::from_millis(50),
+        }));
+
+        assert_eq!(ExportArguments::parse(to_args(&["--seed", "1"])).unwrap(), (None, to_args(&["--seed", "1"])));
+        assert!(ExportArguments::parse(to_args(&["--size", "640x480"])).is_err());
+        assert!(ExportArguments::parse(to_args(&["--snapshot", "a.png", "--size", "640"])).is_err());
+    }
+}
@@ -0,0 +1,266 @@
+use rand::distributions::WeightedIndex;
+use rand::prelude::Distribution;
+use rand::seq::index::sample;
+use rand::Rng;
+
+// Initialization is the way to pick the initial centroids
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Initialization {
+    // K random examples
+    Random,
+    // k-means++: the examples far from the centroids picked so far are more likely to become the next centroid
+    KMeansPlusPlus,
+}
+
+impl Initialization {
+    pub fn init_centroids<X: AsRef<[f64]>, R: Rng>(&self, xs: &[X], k: usize, rng: &mut R) -> Vec<Vec<f64>> {
+        match self {
+            Initialization::Random => init_random_centroids(xs, k, rng),
+            Initialization::KMeansPlusPlus => init_k_means_plus_plus_centroids(xs, k, rng),
+        }
+    }
+}
+
+// KMeansResult is the result of running k-means until convergence
+#[derive(Debug, Clone)]
+pub struct KMeansResult {
+    pub initial_centroids: Vec<Vec<f64>>,
+    pub centroids: Vec<Vec<f64>>,
+    // assignments[i] is the index of the centroid closest to i-th example
+    pub assignments: Vec<usize>,
+    // the value of the cost function after every iteration
+    pub distortions: Vec<f64>,
+}
+
+// init_random_centroids picks k random examples as the initial centroids like kMeansInitCentroids.m from ex7
+pub fn init_random_centroids<X: AsRef<[f64]>, R: Rng>(xs: &[X], k: usize, rng: &mut R) -> Vec<Vec<f64>> {
+    assert!(k <= xs.len());
+
+    sample(rng, xs.len(), k)
+        .into_iter()
+        .map(|i| xs[i].as_ref().to_vec())
+        .collect()
+}
+
+// init_k_means_plus_plus_centroids picks the first centroid uniformly and every next one with the probability
+// proportional to the squared distance from an example to the closest centroid picked so far
+pub fn init_k_means_plus_plus_centroids<X: AsRef<[f64]>, R: Rng>(
+    xs: &[X],
+    k: usize,
+    rng: &mut R,
+) -> Vec<Vec<f64>> {
+    assert!(k <= xs.len());
+
+    let mut centroids = Vec::with_capacity(k);
+    if k == 0 {
+        return centroids;
+    }
+
+    centroids.push(xs[rng.gen_range(0..xs.len())].as_ref().to_vec());
+    let mut squared_distances: Vec<f64> = xs.iter().map(|x| squared_distance(x.as_ref(), &centroids[0])).collect();
+
+    while centroids.len() < k {
+        let next = match WeightedIndex::new(&squared_distances) {
+            Ok(distribution) => distribution.sample(rng),
+            // all the examples coincide with the centroids, so any of them will do
+            Err(_) => rng.gen_range(0..xs.len()),
+        };
+        let centroid = xs[next].as_ref().to_vec();
+
+        for (distance, x) in squared_distances.iter_mut().zip(xs) {
+            *distance = distance.min(squared_distance(x.as_ref(), &centroid));
+        }
+        centroids.push(centroid);
+    }
+
+    centroids
+}
+
+// find_closest_centroids is the cluster assignment step,
+// it returns the index of the closest centroid for every example
+pub fn find_closest_centroids<X: AsRef<[f64]>>(xs: &[X], centroids: &[Vec<f64>]) -> Vec<usize> {
+    xs.iter()
+        .map(|x| {
+            centroids
+                .iter()
+                .map(|centroid| squared_distance(x.as_ref(), centroid))
+                .enumerate()
+                .min_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap())
+                .map(|(i, _)| i)
+                .expect("there should be at least one centroid")
+        })
+        .collect()
+}
+
+// compute_centroids is the move centroid step, every centroid is moved to the mean of the examples
+// assigned to it. A centroid without examples stays where it was
+pub fn compute_centroids<X: AsRef<[f64]>>(
+    xs: &[X],
+    assignments: &[usize],
+    old_centroids: &[Vec<f64>],
+) -> Vec<Vec<f64>> {
+    let mut sums: Vec<Vec<f64>> = old_centroids.iter().map(|centroid| vec![0.0; centroid.len()]).collect();
+    let mut counts = vec![0; old_centroids.len()];
+
+    for (x, centroid_i) in xs.iter().zip(assignments) {
+        for (sum, value) in sums[*centroid_i].iter_mut().zip(x.as_ref()) {
+            *sum += value;
+        }
+        counts[*centroid_i] += 1;
+    }
+
+    sums.into_iter()
+        .zip(counts)
+        .zip(old_centroids)
+        .map(|((sum, count), old_centroid)| {
+            if count == 0 {
+                old_centroid.clone()
+            } else {
+                sum.into_iter().map(|value| value / count as f64).collect()
+            }
+        })
+        .collect()
+}
+
+// distortion is the cost function of k-means, the average squared distance
+// between the examples and the centroids they are assigned to
+pub fn distortion<X: AsRef<[f64]>>(xs: &[X], centroids: &[Vec<f64>], assignments: &[usize]) -> f64 {
+    if xs.is_empty() {
+        return 0.0;
+    }
+
+    let sum: f64 = xs
+        .iter()
+        .zip(assignments)
+        .map(|(x, centroid_i)| squared_distance(x.as_ref(), &centroids[*centroid_i]))
+        .sum();
+
+    sum / xs.len() as f64
+}
+
+// run_k_means alternates the assignment and the move steps until the assignments stop changing
+// or max_iterations is reached
+pub fn run_k_means<X: AsRef<[f64]>>(
+    xs: &[X],
+    initial_centroids: Vec<Vec<f64>>,
+    max_iterations: usize,
+) -> KMeansResult {
+    let mut centroids = initial_centroids.clone();
+    let mut assignments = find_closest_centroids(xs, &centroids);
+    let mut distortions = Vec::new();
+
+    for _ in 0..max_iterations {
+        centroids = compute_centroids(xs, &assignments, &centroids);
+        let new_assignments = find_closest_centroids(xs, &centroids);
+        distortions.push(distortion(xs, &centroids, &new_assignments));
+
+        if new_assignments == assignments {
+            break;
+        }
+        assignments = new_assignments;
+    }
+
+    KMeansResult {
+        initial_centroids,
+        centroids,
+        assignments,
+        distortions,
+    }
+}
+
+// run_k_means_with_restarts runs k-means number_of_restarts times from different initial centroids
+// and keeps the result with the lowest distortion, so a single bad local optimum doesn't matter much
+pub fn run_k_means_with_restarts<X: AsRef<[f64]>, R: Rng>(
+    xs: &[X],
+    k: usize,
+    initialization: Initialization,
+    number_of_restarts: usize,
+    max_iterations: usize,
+    rng: &mut R,
+) -> KMeansResult {
+    (0..number_of_restarts.max(1))
+        .map(|_| {
+            let initial_centroids = initialization.init_centroids(xs, k, rng);
+            let result = run_k_means(xs, initial_centroids, max_iterations);
+            let final_distortion = distortion(xs, &result.centroids, &result.assignments);
+
+            (result, final_distortion)
+        })
+        .min_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap())
+        .map(|(result, _)| result)
+        .unwrap()
+}
+
+// elbow_sweep returns the lowest distortion found for every K from 1 to max_k(but not more than the number
+// of examples), plotting it against K helps to choose K by the elbow method
+pub fn elbow_sweep<X: AsRef<[f64]>, R: Rng>(
+    xs: &[X],
+    max_k: usize,
+    initialization: Initialization,
+    number_of_restarts: usize,
+    max_iterations: usize,
+    rng: &mut R,
+) -> Vec<f64> {
+    (1..=max_k.min(xs.len()))
+        .map(|k| {
+            let result = run_k_means_with_restarts(xs, k, initialization, number_of_restarts, max_iterations, rng);
+
+            distortion(xs, &result.centroids, &result.assignments)
+        })
+        .collect()
+}
+
+pub fn squared_distance(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(a, b)| (a - b).powi(2)).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::k_means::{
+        elbow_sweep, find_closest_centroids, run_k_means, run_k_means_with_restarts, Initialization,
+    };
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_k_means_finds_clusters() {
+        let xs = [[0.0, 0.0], [0.0, 1.0], [1.0, 0.0], [10.0, 10.0], [10.0, 11.0], [11.0, 10.0]];
+
+        let result = run_k_means(&xs, vec![vec![0.0, 0.0], vec![1.0, 0.0]], 10);
+
+        assert_eq!(find_closest_centroids(&xs, &result.centroids), result.assignments);
+        assert_eq!(result.assignments, vec![0, 0, 0, 1, 1, 1]);
+        assert!(relative_eq!(result.centroids[1][0], 31.0 / 3.0));
+        // the distortion never increases
+        assert!(result.distortions.windows(2).all(|pair| pair[1] <= pair[0]));
+    }
+
+    #[test]
+    fn test_restarts_with_k_means_plus_plus() {
+        let xs = [[0.0, 0.0], [0.0, 1.0], [1.0, 0.0], [10.0, 10.0], [10.0, 11.0], [11.0, 10.0], [0.0, 10.0]];
+
+        let result =
+            run_k_means_with_restarts(&xs, 3, Initialization::KMeansPlusPlus, 10, 10, &mut StdRng::seed_from_u64(1));
+
+        let a = &result.assignments;
+        assert!(a[0] == a[1] && a[1] == a[2]);
+        assert!(a[3] == a[4] && a[4] == a[5]);
+        assert!(a[0] != a[3] && a[0] != a[6] && a[3] != a[6]);
+
+        // the same seed gives the same result
+        let same_result =
+            run_k_means_with_restarts(&xs, 3, Initialization::KMeansPlusPlus, 10, 10, &mut StdRng::seed_from_u64(1));
+        assert_eq!(result.initial_centroids, same_result.initial_centroids);
+    }
+
+    #[test]
+    fn test_elbow_sweep() {
+        let xs = [[0.0, 0.0], [0.0, 1.0], [1.0, 0.0], [10.0, 10.0], [10.0, 11.0], [11.0, 10.0]];
+
+        let distortions = elbow_sweep(&xs, 10, Initialization::KMeansPlusPlus, 5, 10, &mut StdRng::seed_from_u64(1));
+
+        assert_eq!(distortions.len(), xs.len());
+        assert!(distortions.windows(2).all(|pair| pair[1] <= pair[0]));
+        assert!(relative_eq!(distortions[xs.len() - 1], 0.0));
+    }
+}
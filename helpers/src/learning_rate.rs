@@ -0,0 +1,80 @@
+// when a step made the cost go down, the next one is tried with a slightly bigger learning rate
+pub const LEARNING_RATE_INCREASE_FACTOR: f64 = 1.05;
+// when a step made the cost go up(or theta blew up to NaN/inf), the step is retried with a smaller one
+pub const LEARNING_RATE_DECREASE_FACTOR: f64 = 0.5;
+// below this value we consider that we've converged and just keep the old parameters
+pub const MIN_LEARNING_RATE: f64 = 1e-12;
+
+// AdaptiveLearningRate adjusts the step size of gradient descent on the fly using "bold driver"
+// heuristic with backtracking: every step is checked against the cost function and rejected if it
+// diverges, so too large initial learning rate doesn't make the parameters blow up
+#[derive(Debug, Clone, Copy)]
+pub struct AdaptiveLearningRate {
+    value: f64,
+}
+
+impl AdaptiveLearningRate {
+    pub fn new(initial_value: f64) -> AdaptiveLearningRate {
+        AdaptiveLearningRate { value: initial_value }
+    }
+
+    // value returns the learning rate currently in effect
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    // step makes one step of gradient descent using `step_function(params, learning_rate)`.
+    // If the cost rises or the new parameters are not finite, the learning rate is decreased
+    // and the step is retried until the cost goes down.
+    pub fn step<P, C, S>(&mut self, params: P, cost_function: C, step_function: S) -> P
+        where P: Copy + AsRef<[f64]>,
+              C: Fn(P) -> f64,
+              S: Fn(P, f64) -> P,
+    {
+        let cost = cost_function(params);
+
+        while self.value >= MIN_LEARNING_RATE {
+            let new_params = step_function(params, self.value);
+            let new_cost = cost_function(new_params);
+
+            if is_finite(new_params.as_ref()) && new_cost.is_finite() && new_cost <= cost {
+                self.value *= LEARNING_RATE_INCREASE_FACTOR;
+                return new_params;
+            }
+
+            self.value *= LEARNING_RATE_DECREASE_FACTOR;
+        }
+
+        // we can't make any progress, so keep the step size from getting stuck at zero
+        self.value = MIN_LEARNING_RATE;
+
+        params
+    }
+}
+
+pub fn is_finite(params: &[f64]) -> bool {
+    params.iter().all(|x| x.is_finite())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::learning_rate::AdaptiveLearningRate;
+
+    #[test]
+    fn test_too_large_learning_rate_is_decreased() {
+        // minimizing `x ^ 2` with the learning rate of 10 would normally diverge
+        let mut learning_rate = AdaptiveLearningRate::new(10.0);
+        let mut params = [5.0];
+
+        for _ in 0..100 {
+            params = learning_rate.step(
+                params,
+                |p: [f64; 1]| p[0] * p[0],
+                |p: [f64; 1], rate| [p[0] - rate * 2.0 * p[0]],
+            );
+        }
+
+        assert!(params[0].abs() < 0.001);
+        assert!(learning_rate.value() < 10.0);
+    }
+}
@@ -0,0 +1,209 @@
+use druid::widget::{Flex, Label, Slider, Switch, TextBox};
+use druid::widget::prelude::*;
+use druid::{Data, Lens, WidgetExt};
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+
+// the learning rate slider is logarithmic
+pub const MIN_LEARNING_RATE_LOG10: f64 = -5.0;
+pub const MAX_LEARNING_RATE_LOG10: f64 = 0.0;
+pub const MIN_STEPS_PER_UPDATE: usize = 1;
+pub const MAX_STEPS_PER_UPDATE: usize = 100;
+// ms
+pub const MIN_UPDATE_PERIOD: u64 = 1;
+pub const MAX_UPDATE_PERIOD: u64 = 500;
+pub const SETTINGS_LABEL_WIDTH: f64 = 200.0;
+
+// LearningSettings are the settings of gradient descent in the demos, they're changed in the UI
+// and the learning threads read them on every iteration
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct LearningSettings {
+    // the adaptive learning rate starts over from this value when it's changed
+    pub learning_rate: f64,
+    pub steps_per_update: usize,
+    // the pause between the updates(ms)
+    pub update_period: u64,
+    // every element of theta starts from this value, the learning starts over when it's changed
+    pub default_theta: f64,
+    // linear regression with multiple features can be solved by the normal equation instead of gradient descent
+    pub use_normal_equation: bool,
+}
+
+impl LearningSettings {
+    // check makes sure the settings could be chosen in the UI, e.g. the ones loaded from a session
+    pub fn check(&self) -> Result<(), Box<dyn Error>> {
+        let learning_rate_log10 = self.learning_rate.log10();
+        if !(MIN_LEARNING_RATE_LOG10..=MAX_LEARNING_RATE_LOG10).contains(&learning_rate_log10) {
+            return Err(format!(
+                "the learning rate should be from {} to {}, got {}",
+                10f64.powf(MIN_LEARNING_RATE_LOG10), 10f64.powf(MAX_LEARNING_RATE_LOG10), self.learning_rate,
+            ).into());
+        }
+        if !(MIN_STEPS_PER_UPDATE..=MAX_STEPS_PER_UPDATE).contains(&self.steps_per_update) {
+            return Err(format!(
+                "the steps per update should be from {} to {}, got {}",
+                MIN_STEPS_PER_UPDATE, MAX_STEPS_PER_UPDATE, self.steps_per_update,
+            ).into());
+        }
+        if !(MIN_UPDATE_PERIOD..=MAX_UPDATE_PERIOD).contains(&self.update_period) {
+            return Err(format!(
+                "the update period should be from {}ms to {}ms, got {}ms",
+                MIN_UPDATE_PERIOD, MAX_UPDATE_PERIOD, self.update_period,
+            ).into());
+        }
+        if !self.default_theta.is_finite() {
+            return Err(format!("the default theta should be a number, got {}", self.default_theta).into());
+        }
+
+        Ok(())
+    }
+}
+
+// LearningSettingsData is the state of the settings widgets
+#[derive(Clone, Data, Lens, Debug)]
+pub struct LearningSettingsData {
+    pub learning_rate_log10: f64,
+    pub steps_per_update_slider: f64,
+    pub update_period_slider: f64,
+    pub default_theta: String,
+    pub use_normal_equation: bool,
+}
+
+impl LearningSettingsData {
+    pub fn new(settings: &LearningSettings) -> LearningSettingsData {
+        LearningSettingsData {
+            learning_rate_log10: settings.learning_rate.log10(),
+            steps_per_update_slider: settings.steps_per_update as f64,
+            update_period_slider: settings.update_period as f64,
+            default_theta: settings.default_theta.to_string(),
+            use_normal_equation: settings.use_normal_equation,
+        }
+    }
+
+    pub fn learning_rate(&self) -> f64 {
+        10f64.powf(self.learning_rate_log10)
+    }
+
+    pub fn steps_per_update(&self) -> usize {
+        (self.steps_per_update_slider.round() as usize).clamp(MIN_STEPS_PER_UPDATE, MAX_STEPS_PER_UPDATE)
+    }
+
+    pub fn update_period(&self) -> u64 {
+        (self.update_period_slider.round() as u64).clamp(MIN_UPDATE_PERIOD, MAX_UPDATE_PERIOD)
+    }
+
+    // settings returns the settings chosen in the UI, the default theta stays the same while it isn't a number.
+    // The learning rate stays exactly the same while the slider isn't moved, 10^log10 doesn't always give it back
+    pub fn settings(&self, previous: &LearningSettings) -> LearningSettings {
+        LearningSettings {
+            learning_rate: if self.learning_rate_log10 == previous.learning_rate.log10() {
+                previous.learning_rate
+            } else {
+                self.learning_rate()
+            },
+            steps_per_update: self.steps_per_update(),
+            update_period: self.update_period(),
+            default_theta: self.default_theta
+                .trim()
+                .parse::<f64>()
+                .ok()
+                .filter(|value| value.is_finite())
+                .unwrap_or(previous.default_theta),
+            use_normal_equation: self.use_normal_equation,
+        }
+    }
+}
+
+// build_learning_settings_widget builds the controls for the settings, the normal equation switch is only shown
+// if the demo supports it
+pub fn build_learning_settings_widget(with_normal_equation: bool) -> impl Widget<LearningSettingsData> {
+    let mut widget = Flex::column()
+        .with_child(
+            Flex::row()
+                .with_child(
+                    Label::new(|data: &LearningSettingsData, _env: &Env| {
+                        format!("learning rate: {:.5}", data.learning_rate())
+                    }).fix_width(SETTINGS_LABEL_WIDTH),
+                )
+                .with_child(
+                    Slider::new()
+                        .with_range(MIN_LEARNING_RATE_LOG10, MAX_LEARNING_RATE_LOG10)
+                        .lens(LearningSettingsData::learning_rate_log10),
+                ),
+        )
+        .with_child(
+            Flex::row()
+                .with_child(
+                    Label::new(|data: &LearningSettingsData, _env: &Env| {
+                        format!("steps per update: {}", data.steps_per_update())
+                    }).fix_width(SETTINGS_LABEL_WIDTH),
+                )
+                .with_child(
+                    Slider::new()
+                        .with_range(MIN_STEPS_PER_UPDATE as f64, MAX_STEPS_PER_UPDATE as f64)
+                        .lens(LearningSettingsData::steps_per_update_slider),
+                ),
+        )
+        .with_child(
+            Flex::row()
+                .with_child(
+                    Label::new(|data: &LearningSettingsData, _env: &Env| {
+                        format!("update period: {}ms", data.update_period())
+                    }).fix_width(SETTINGS_LABEL_WIDTH),
+                )
+                .with_child(
+                    Slider::new()
+                        .with_range(MIN_UPDATE_PERIOD as f64, MAX_UPDATE_PERIOD as f64)
+                        .lens(LearningSettingsData::update_period_slider),
+                ),
+        )
+        .with_child(
+            Flex::row()
+                .with_child(Label::new("default theta").fix_width(SETTINGS_LABEL_WIDTH))
+                .with_child(TextBox::new().lens(LearningSettingsData::default_theta)),
+        );
+
+    if with_normal_equation {
+        widget.add_child(
+            Flex::row()
+                .with_child(Label::new("use normal equation").fix_width(SETTINGS_LABEL_WIDTH))
+                .with_child(Switch::new().lens(LearningSettingsData::use_normal_equation)),
+        );
+    }
+
+    widget
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::learning_settings::{LearningSettings, LearningSettingsData};
+
+    #[test]
+    fn test_settings() {
+        let settings = LearningSettings {
+            learning_rate: 0.01,
+            steps_per_update: 10,
+            update_period: 10,
+            default_theta: 0.5,
+            use_normal_equation: false,
+        };
+        let mut data = LearningSettingsData::new(&settings);
+
+        let new_settings = data.settings(&settings);
+        assert_eq!(new_settings, settings);
+
+        // 10^log10(0.07) isn't 0.07
+        let settings = LearningSettings { learning_rate: 0.07, ..settings };
+        assert_eq!(LearningSettingsData::new(&settings).settings(&settings), settings);
+        assert!(settings.check().is_ok());
+        assert!(LearningSettings { learning_rate: 0.0, ..settings }.check().is_err());
+        assert!(LearningSettings { steps_per_update: 0, ..settings }.check().is_err());
+        assert!(LearningSettings { update_period: u64::MAX, ..settings }.check().is_err());
+
+        data.default_theta = "-".to_string();
+        data.steps_per_update_slider = 1000.0;
+        let new_settings = data.settings(&settings);
+        assert_eq!(new_settings.default_theta, 0.5);
+        assert_eq!(new_settings.steps_per_update, 100);
+    }
+}
@@ -1,10 +1,28 @@
 #![feature(adt_const_params, generic_const_exprs)]
 #![allow(incomplete_features)]
 
+#[cfg(test)]
+#[macro_use]
+extern crate approx;
+
 pub mod canvas;
 pub mod math;
 pub mod plotters;
 pub mod demo_data;
+pub mod learning_rate;
+pub mod svm;
+pub mod k_means;
+pub mod pca;
+pub mod anomaly_detection;
+pub mod mat_file;
+pub mod collaborative_filtering;
+pub mod online_learning;
+pub mod export;
+pub mod dataset;
+pub mod session;
+pub mod learning_settings;
+pub mod training_control;
+pub mod view;
 
 #[cfg(test)]
 mod tests {
@@ -0,0 +1,61 @@
+use matfile::{MatFile, NumericData};
+use nalgebra::DMatrix;
+use std::error::Error;
+use std::fs::File;
+use std::path::Path;
+
+// load_mat_file parses a .mat file from the course exercises
+pub fn load_mat_file(path: &Path) -> Result<MatFile, Box<dyn Error>> {
+    let file = File::open(path).map_err(|error| format!("{}: {}", path.display(), error))?;
+
+    MatFile::parse(file).map_err(|error| format!("{}: {}", path.display(), error).into())
+}
+
+// get_array_from_mat_file returns the rows of a double array with N_COLUMNS columns
+pub fn get_array_from_mat_file<const N_COLUMNS: usize>(
+    mat_file: &MatFile,
+    array_name: &str,
+) -> Vec<[f64; N_COLUMNS]> {
+    let array = mat_file.find_by_name(array_name).unwrap().data().clone();
+
+    if let NumericData::Double { real, .. } = array {
+        let mut res: Vec<[f64; N_COLUMNS]> = Vec::new();
+
+        let n_rows = real.len() / N_COLUMNS;
+        for r in 0..n_rows {
+            let mut row = [0.0; N_COLUMNS];
+            for c in 0..N_COLUMNS {
+                // the data is written column by column(for some reason)
+                row[c] = real[c * n_rows + r];
+            }
+            res.push(row);
+        }
+
+        return res;
+    }
+
+    panic!("array has different type: {:?}", array);
+}
+
+// get_matrix_from_mat_file returns a 2D array of any size, e.g. the ratings from ex8_movies.mat.
+// The logical arrays(like R) are stored as uint8, so they are converted to 0.0 and 1.0
+pub fn get_matrix_from_mat_file(mat_file: &MatFile, array_name: &str) -> Result<DMatrix<f64>, Box<dyn Error>> {
+    let array = mat_file
+        .find_by_name(array_name)
+        .ok_or(format!("there's no array {}", array_name))?;
+
+    let size = array.size();
+    if size.len() != 2 {
+        return Err(format!("{} should have 2 dimensions, but it has {}", array_name, size.len()).into());
+    }
+
+    let values: Vec<f64> = match array.data() {
+        NumericData::Double { real, .. } => real.clone(),
+        NumericData::Single { real, .. } => real.iter().map(|value| *value as f64).collect(),
+        NumericData::UInt8 { real, .. } => real.iter().map(|value| *value as f64).collect(),
+        data => return Err(format!("{} has unsupported type: {:?}", array_name, data).into()),
+    };
+
+    // the data is written column by column like nalgebra stores it
+    Ok(DMatrix::from_vec(size[0], size[1], values))
+}
@@ -1,7 +1,17 @@
+use rand::Rng;
+
 pub fn sigmoid(x: f64) -> f64 {
     1.0 / (1.0 + std::f64::consts::E.powf(-x))
 }
 
+// standard_normal samples the standard normal distribution by Box-Muller transform
+pub fn standard_normal<R: Rng>(rng: &mut R) -> f64 {
+    let u1: f64 = 1.0 - rng.gen::<f64>();
+    let u2: f64 = rng.gen();
+
+    (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+}
+
 pub fn logistic_regression_predict<const N_FEATURES: usize>(
     theta: [f64; N_FEATURES + 1],
     x: [f64; N_FEATURES],
@@ -49,3 +59,56 @@ pub fn gradient_descent_step<const N_FEATURES: usize, F: Fn([f64; N_FEATURES]) -
 
     result
 }
+
+pub fn linear_regression_predict<const N_FEATURES: usize>(
+    theta: [f64; N_FEATURES + 1],
+    x: [f64; N_FEATURES],
+) -> f64 {
+    let mut result = 0.0;
+
+    for (theta_i, theta_val) in theta.iter().enumerate() {
+        result += theta_val * if theta_i > 0 { x[theta_i - 1] } else { 1.0 };
+    }
+
+    result
+}
+
+// linear_regression_cost calculates the squared error cost `1 / 2m * sum((h(x) - y) ^ 2)`
+pub fn linear_regression_cost<const N_FEATURES: usize>(
+    theta: [f64; N_FEATURES + 1],
+    xs: &[[f64; N_FEATURES]],
+    ys: &[f64],
+) -> f64 {
+    assert_eq!(xs.len(), ys.len());
+
+    let mut cost = 0.0;
+
+    for (point_i, x) in xs.iter().enumerate() {
+        let error = linear_regression_predict(theta, *x) - ys[point_i];
+        cost += error * error;
+    }
+
+    cost / (2.0 * ys.len() as f64)
+}
+
+// logistic_regression_cost calculates the cross entropy cost
+// `-1 / m * sum(y * log(h(x)) + (1 - y) * log(1 - h(x)))`
+pub fn logistic_regression_cost<const N_FEATURES: usize>(
+    theta: [f64; N_FEATURES + 1],
+    xs: &[[f64; N_FEATURES]],
+    ys: &[f64],
+) -> f64 {
+    assert_eq!(xs.len(), ys.len());
+
+    let mut cost = 0.0;
+
+    for (point_i, x) in xs.iter().enumerate() {
+        let y = ys[point_i];
+        // sigmoid saturates to exactly 0 or 1 quite fast, so we don't let log go to infinity
+        let prediction = logistic_regression_predict(theta, *x).clamp(f64::EPSILON, 1.0 - f64::EPSILON);
+
+        cost -= y * prediction.ln() + (1.0 - y) * (1.0 - prediction).ln();
+    }
+
+    cost / ys.len() as f64
+}
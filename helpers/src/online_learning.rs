@@ -0,0 +1,49 @@
+use crate::demo_data::RegressionDemoDataNFeatures;
+use crate::math::gradient_descent_step;
+use std::sync::mpsc::{Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+// Example is one labelled example sent to the online learning thread
+pub type Example<const N_FEATURES: usize> = ([f64; N_FEATURES], f64);
+
+// online_learning_thread takes one step of stochastic gradient descent per example received from the channel
+// and discards it like in the online learning lecture. Nothing is stored for training, the last shown_examples
+// examples are kept in data.xs and data.ys only to be drawn. The thread ends when all the senders are dropped
+pub fn online_learning_thread<const N_FEATURES: usize, P: Fn([f64; N_FEATURES + 1], [f64; N_FEATURES]) -> f64>(
+    data: Arc<Mutex<RegressionDemoDataNFeatures<N_FEATURES>>>,
+    examples: Receiver<Example<N_FEATURES>>,
+    learning_rate: f64,
+    shown_examples: usize,
+    predict: P,
+) {
+    for (x, y) in examples {
+        let mut data = data.lock().unwrap();
+
+        let theta = data.theta;
+        data.theta = gradient_descent_step(theta, learning_rate, &[x], &[y], |x| predict(theta, x));
+        data.learning_rate = learning_rate;
+        data.record_theta();
+
+        data.xs.push(x);
+        data.ys.push(y);
+        if data.xs.len() > shown_examples {
+            data.xs.remove(0);
+            data.ys.remove(0);
+        }
+    }
+}
+
+// example_stream_thread sends a generated example every period, like the users coming to a website one by one.
+// generate gets the number of the example, so the distribution can change over time
+pub fn example_stream_thread<T, F: FnMut(usize) -> T>(examples: Sender<T>, period: Duration, mut generate: F) {
+    for i in 0.. {
+        // the learning thread is gone, so there's nobody to send to
+        if examples.send(generate(i)).is_err() {
+            return;
+        }
+
+        thread::sleep(period);
+    }
+}
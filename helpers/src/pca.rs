@@ -0,0 +1,136 @@
+use nalgebra::DMatrix;
+
+// Pca is principal component analysis fitted on some data set. The features are normalized first,
+// then the principal components are found by SVD of the covariance matrix like in pca.m from ex7
+#[derive(Debug, Clone)]
+pub struct Pca {
+    pub mu: Vec<f64>,
+    pub sigma: Vec<f64>,
+    // the columns are the principal components sorted by the variance along them
+    pub u: DMatrix<f64>,
+    // the variance along every principal component in the descending order
+    pub s: Vec<f64>,
+}
+
+impl Pca {
+    pub fn fit<X: AsRef<[f64]>>(xs: &[X]) -> Pca {
+        assert!(!xs.is_empty());
+
+        let m = xs.len();
+        let n = xs[0].as_ref().len();
+
+        let mut mu = vec![0.0; n];
+        for x in xs {
+            for (mu, value) in mu.iter_mut().zip(x.as_ref()) {
+                *mu += value / m as f64;
+            }
+        }
+
+        // the standard deviation is calculated like std in octave
+        let mut sigma = vec![0.0; n];
+        for x in xs {
+            for ((sigma, mu), value) in sigma.iter_mut().zip(&mu).zip(x.as_ref()) {
+                *sigma += (value - mu).powi(2);
+            }
+        }
+        for sigma in sigma.iter_mut() {
+            *sigma = (*sigma / (m.max(2) - 1) as f64).sqrt();
+            // constant features are only centered
+            if *sigma == 0.0 {
+                *sigma = 1.0;
+            }
+        }
+
+        let mut pca = Pca { mu, sigma, u: DMatrix::zeros(n, n), s: Vec::new() };
+
+        let normalized = DMatrix::from_fn(m, n, |i, j| (xs[i].as_ref()[j] - pca.mu[j]) / pca.sigma[j]);
+        let covariance = normalized.transpose() * &normalized / m as f64;
+        let svd = covariance.svd(true, false);
+        let u = svd.u.expect("u is requested");
+        let singular_values = svd.singular_values;
+
+        // nalgebra doesn't sort singular values
+        let mut order: Vec<usize> = (0..n).collect();
+        order.sort_by(|a, b| singular_values[*b].partial_cmp(&singular_values[*a]).unwrap());
+
+        pca.u = DMatrix::from_fn(n, n, |i, j| u[(i, order[j])]);
+        pca.s = order.iter().map(|i| singular_values[*i]).collect();
+
+        pca
+    }
+
+    // dimension returns the number of the original features
+    pub fn dimension(&self) -> usize {
+        self.mu.len()
+    }
+
+    pub fn normalize(&self, x: &[f64]) -> Vec<f64> {
+        x.iter().zip(&self.mu).zip(&self.sigma).map(|((x, mu), sigma)| (x - mu) / sigma).collect()
+    }
+
+    pub fn denormalize(&self, x: &[f64]) -> Vec<f64> {
+        x.iter().zip(&self.mu).zip(&self.sigma).map(|((x, mu), sigma)| x * sigma + mu).collect()
+    }
+
+    // project maps x onto the first k principal components, `z = U_reduce_transpose * x_normalized`
+    pub fn project(&self, x: &[f64], k: usize) -> Vec<f64> {
+        let x = self.normalize(x);
+
+        (0..k)
+            .map(|component| x.iter().enumerate().map(|(i, value)| self.u[(i, component)] * value).sum())
+            .collect()
+    }
+
+    // recover approximates the original x from its projection, `x_approx = U_reduce * z`
+    pub fn recover(&self, z: &[f64]) -> Vec<f64> {
+        let x: Vec<f64> = (0..self.dimension())
+            .map(|i| z.iter().enumerate().map(|(component, value)| self.u[(i, component)] * value).sum())
+            .collect();
+
+        self.denormalize(&x)
+    }
+
+    // retained_variance returns the share of the variance retained by the first k components
+    pub fn retained_variance(&self, k: usize) -> f64 {
+        let total: f64 = self.s.iter().sum();
+        if total == 0.0 {
+            return 1.0;
+        }
+
+        self.s.iter().take(k).sum::<f64>() / total
+    }
+
+    // smallest_k returns the smallest number of components retaining at least the share of the variance,
+    // e.g. 0.99 for 99%
+    pub fn smallest_k(&self, share: f64) -> usize {
+        (1..=self.dimension())
+            .find(|k| self.retained_variance(*k) >= share)
+            .unwrap_or_else(|| self.dimension())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pca::Pca;
+
+    #[test]
+    fn test_pca() {
+        // the points lie almost on the line `y = 2 * x`
+        let xs = [[1.0, 2.1], [2.0, 3.9], [3.0, 6.0], [4.0, 8.1], [5.0, 9.9]];
+        let pca = Pca::fit(&xs);
+
+        assert!(pca.retained_variance(1) > 0.99);
+        assert_eq!(pca.smallest_k(0.99), 1);
+        assert_eq!(pca.smallest_k(1.0), 2);
+
+        // projecting onto all the components doesn't lose anything
+        for x in xs.iter() {
+            let recovered = pca.recover(&pca.project(x, 2));
+            assert!(relative_eq!(recovered[0], x[0], epsilon = 1e-9));
+            assert!(relative_eq!(recovered[1], x[1], epsilon = 1e-9));
+        }
+
+        // the first component goes along the line in the normalized space
+        assert!(relative_eq!(pca.u[(0, 0)].abs(), pca.u[(1, 0)].abs(), epsilon = 1e-2));
+    }
+}
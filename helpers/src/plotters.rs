@@ -2,7 +2,11 @@ pub mod three_d {
     use plotters::prelude::*;
     use plotters::coord::cartesian::Cartesian3d;
     use plotters::coord::types::RangedCoordf64;
+    use plotters::coord::CoordTranslate;
     use std::error::Error;
+    use std::ops::Range;
+    use crate::plotters::two_d::Grid;
+    use crate::view::View;
 
     pub fn plot_surface<F: Fn(f64, f64) -> f64>(
         chart: &mut ChartContext<
@@ -11,26 +15,28 @@ pub mod three_d {
         >,
         surface_function: F,
         number_of_points: usize,
-        max_value: f64,
+        x_range: (f64, f64),
+        z_range: (f64, f64),
         color: RGBColor,
     ) -> Result<(), Box<dyn Error>> {
-        let scale_factor = max_value as f64 / number_of_points as f64;
+        let x_step = (x_range.1 - x_range.0) / number_of_points as f64;
+        let z_step = (z_range.1 - z_range.0) / number_of_points as f64;
 
         chart.draw_series(
-            (0..=number_of_points)
-                .map(|x| std::iter::repeat(x).zip(0..=number_of_points))
+            (0..number_of_points)
+                .map(|x| std::iter::repeat(x).zip(0..number_of_points))
                 .flatten()
                 .map(|(x, z)| {
-                    let x = x as f64 * scale_factor;
-                    let z = z as f64 * scale_factor;
+                    let x = x_range.0 + x as f64 * x_step;
+                    let z = z_range.0 + z as f64 * z_step;
 
                     let to_polygon_point = |x, z| (x, surface_function(x, z), z);
 
                     Polygon::new(vec![
                         to_polygon_point(x, z),
-                        to_polygon_point(x + scale_factor, z),
-                        to_polygon_point(x + scale_factor, z + scale_factor),
-                        to_polygon_point(x, z + scale_factor),
+                        to_polygon_point(x + x_step, z),
+                        to_polygon_point(x + x_step, z + z_step),
+                        to_polygon_point(x, z + z_step),
                     ], &color.mix(0.3))
                 }),
         )?;
@@ -38,6 +44,121 @@ pub mod three_d {
         Ok(())
     }
 
+    // CostSurface is the cost J(theta0, theta1) sampled once in the view with the path gradient descent took on it,
+    // the same samples give the range of the cost axis and the surface
+    pub struct CostSurface {
+        grid: Grid,
+        // the visible parts of the trajectory as (theta0, cost, theta1)
+        paths: Vec<Vec<(f64, f64, f64)>>,
+        current_theta: Option<(f64, f64, f64)>,
+    }
+
+    impl CostSurface {
+        // sample fits the view to the trajectory mirrored around the center, so the center(e.g. the minimum)
+        // stays in the middle of the chart, and samples the cost on a grid of resolution x resolution cells
+        pub fn sample<F: Fn(f64, f64) -> f64>(
+            cost_function: F,
+            trajectory: &[[f64; 2]],
+            center: Option<[f64; 2]>,
+            view: &mut View<2>,
+            resolution: usize,
+        ) -> CostSurface {
+            view.fit(trajectory.iter().flat_map(|theta| {
+                let mirrored = center.map(|center| [2.0 * center[0] - theta[0], 2.0 * center[1] - theta[1]]);
+
+                std::iter::once(*theta).chain(mirrored)
+            }));
+
+            let with_cost = |[theta0, theta1]: [f64; 2]| (theta0, cost_function(theta0, theta1), theta1);
+            let paths = view
+                .visible_segments(trajectory.iter().copied())
+                .into_iter()
+                .map(|segment| segment.into_iter().map(with_cost).collect())
+                .collect();
+            let current_theta = trajectory.last().copied().filter(|theta| view.contains(*theta)).map(with_cost);
+
+            CostSurface {
+                grid: Grid::sample(cost_function, view.ranges[0], view.ranges[1], resolution),
+                paths,
+                current_theta,
+            }
+        }
+
+        // cost_range returns the range of the cost axis, the path can go lower than the nodes of the grid
+        // near the minimum
+        pub fn cost_range(&self) -> Range<f64> {
+            let (min_cost, max_cost) = self.paths
+                .iter()
+                .flatten()
+                .fold(self.grid.value_range(), |(min, max), (_, cost, _)| (min.min(*cost), max.max(*cost)));
+
+            if max_cost > min_cost { min_cost..max_cost } else { min_cost..min_cost + 1.0 }
+        }
+
+        pub fn draw(
+            &self,
+            chart: &mut ChartContext<
+                BitMapBackend,
+                Cartesian3d<RangedCoordf64, RangedCoordf64, RangedCoordf64>,
+            >,
+            surface_color: RGBColor,
+            path_color: RGBColor,
+        ) -> Result<(), Box<dyn Error>> {
+            let grid = &self.grid;
+            let node = |i: usize, j: usize| {
+                let (theta0, theta1) = grid.point(i, j);
+                (theta0, grid.value(i, j), theta1)
+            };
+
+            chart.draw_series(
+                (0..grid.resolution())
+                    .flat_map(|j| (0..grid.resolution()).map(move |i| (i, j)))
+                    .map(|(i, j)| vec![node(i, j), node(i + 1, j), node(i + 1, j + 1), node(i, j + 1)])
+                    .filter(|polygon| polygon.iter().all(|(_, cost, _)| cost.is_finite()))
+                    .map(|polygon| Polygon::new(polygon, surface_color.mix(0.3))),
+            )?;
+
+            for path in &self.paths {
+                chart.draw_series(LineSeries::new(path.iter().copied(), path_color.stroke_width(2)))?;
+            }
+            if let Some(current_theta) = self.current_theta {
+                chart.draw_series([Circle::new(current_theta, 4, path_color.filled())])?;
+            }
+
+            Ok(())
+        }
+    }
+
+    // pick_surface_point returns the point of the surface which is drawn closest to the pixel, the surface is sampled
+    // like in plot_surface. The point is returned as [x, z, y] like the new points
+    pub fn pick_surface_point<F: Fn(f64, f64) -> f64>(
+        coord_spec: &Cartesian3d<RangedCoordf64, RangedCoordf64, RangedCoordf64>,
+        surface_function: F,
+        number_of_points: usize,
+        x_range: (f64, f64),
+        z_range: (f64, f64),
+        pixel: (i32, i32),
+    ) -> Option<[f64; 3]> {
+        let x_step = (x_range.1 - x_range.0) / number_of_points as f64;
+        let z_step = (z_range.1 - z_range.0) / number_of_points as f64;
+        let distance = |point: &(f64, f64, f64)| {
+            let (x, y) = coord_spec.translate(point);
+            (x - pixel.0).pow(2) + (y - pixel.1).pow(2)
+        };
+
+        (0..=number_of_points)
+            .flat_map(|x| (0..=number_of_points).map(move |z| (x, z)))
+            .map(|(x, z)| {
+                let x = x_range.0 + x as f64 * x_step;
+                let z = z_range.0 + z as f64 * z_step;
+
+                (x, surface_function(x, z), z)
+            })
+            .filter(|(_, y, _)| y.is_finite())
+            .min_by_key(distance)
+            .map(|(x, y, z)| [x, z, y])
+    }
+
     pub fn plot_points<F: Fn([f64; 3], [f64; 2]) -> RGBColor>(
         chart: &mut ChartContext<
             BitMapBackend,
@@ -93,3 +214,236 @@ pub mod three_d {
         Ok(())
     }
 }
+
+pub mod two_d {
+    use plotters::coord::types::RangedCoordf64;
+    use plotters::coord::CoordTranslate;
+    use plotters::prelude::Cartesian2d;
+
+    // split_into_visible_segments splits a polyline into the parts lying between min_y and max_y
+    // because plotting library doesn't support values outside of drawing region
+    pub fn split_into_visible_segments<I: IntoIterator<Item=(f64, f64)>>(
+        points: I,
+        min_y: f64,
+        max_y: f64,
+    ) -> Vec<Vec<(f64, f64)>> {
+        let mut segments = Vec::new();
+        let mut current_segment = Vec::new();
+
+        for (x, y) in points {
+            if y.is_finite() && y >= min_y && y <= max_y {
+                current_segment.push((x, y));
+            } else if !current_segment.is_empty() {
+                segments.push(current_segment);
+                current_segment = Vec::new();
+            }
+        }
+
+        if !current_segment.is_empty() {
+            segments.push(current_segment);
+        }
+
+        segments
+    }
+
+    // line_inside_rect returns the part of the line `theta[0] + theta[1] * x + theta[2] * y = level`
+    // lying inside the rectangle or None if the line doesn't cross it
+    pub fn line_inside_rect(
+        theta: [f64; 3],
+        level: f64,
+        x_range: (f64, f64),
+        y_range: (f64, f64),
+    ) -> Option<[(f64, f64); 2]> {
+        let (min_x, max_x) = x_range;
+        let (min_y, max_y) = y_range;
+        let mut points = Vec::new();
+
+        if theta[2] != 0.0 {
+            for x in [min_x, max_x].iter() {
+                let y = (level - theta[0] - theta[1] * x) / theta[2];
+                if y >= min_y && y <= max_y {
+                    points.push((*x, y));
+                }
+            }
+        }
+        if theta[1] != 0.0 {
+            for y in [min_y, max_y].iter() {
+                let x = (level - theta[0] - theta[2] * y) / theta[1];
+                if x >= min_x && x <= max_x {
+                    points.push((x, *y));
+                }
+            }
+        }
+
+        // the line can cross the same corner twice, so we take the points furthest from each other
+        let mut result = None;
+        let mut max_distance = -1.0;
+        for (i, a) in points.iter().enumerate() {
+            for b in points.iter().skip(i + 1) {
+                let distance = (a.0 - b.0).powi(2) + (a.1 - b.1).powi(2);
+                if distance > max_distance {
+                    max_distance = distance;
+                    result = Some([*a, *b]);
+                }
+            }
+        }
+
+        result
+    }
+
+    // closest_point returns the index of the point drawn closest to the pixel if it's not further than max_distance
+    // pixels away, it's used to find the point under the mouse
+    pub fn closest_point<I: IntoIterator<Item=(f64, f64)>>(
+        coord_spec: &Cartesian2d<RangedCoordf64, RangedCoordf64>,
+        points: I,
+        pixel: (i32, i32),
+        max_distance: i32,
+    ) -> Option<usize> {
+        points
+            .into_iter()
+            .map(|point| {
+                let (x, y) = coord_spec.translate(&point);
+                (x - pixel.0).pow(2) + (y - pixel.1).pow(2)
+            })
+            .enumerate()
+            .filter(|(_, distance)| *distance <= max_distance.pow(2))
+            .min_by_key(|(_, distance)| *distance)
+            .map(|(i, _)| i)
+    }
+
+    // Grid is a function of 2 variables sampled on a regular grid of resolution x resolution cells,
+    // it's used to draw contours without recalculating the function for every level
+    #[derive(Debug, Clone)]
+    pub struct Grid {
+        x_range: (f64, f64),
+        y_range: (f64, f64),
+        resolution: usize,
+        values: Vec<f64>,
+    }
+
+    impl Grid {
+        pub fn sample<F: Fn(f64, f64) -> f64>(
+            function: F,
+            x_range: (f64, f64),
+            y_range: (f64, f64),
+            resolution: usize,
+        ) -> Grid {
+            let mut grid = Grid { x_range, y_range, resolution, values: Vec::new() };
+
+            grid.values = (0..=resolution)
+                .flat_map(|j| (0..=resolution).map(move |i| (i, j)))
+                .map(|(i, j)| {
+                    let (x, y) = grid.point(i, j);
+                    function(x, y)
+                })
+                .collect();
+
+            grid
+        }
+
+        pub fn resolution(&self) -> usize {
+            self.resolution
+        }
+
+        // point returns the coordinates of the node in i-th column and j-th row
+        pub fn point(&self, i: usize, j: usize) -> (f64, f64) {
+            let (min_x, max_x) = self.x_range;
+            let (min_y, max_y) = self.y_range;
+
+            (
+                min_x + (max_x - min_x) * i as f64 / self.resolution as f64,
+                min_y + (max_y - min_y) * j as f64 / self.resolution as f64,
+            )
+        }
+
+        pub fn value(&self, i: usize, j: usize) -> f64 {
+            self.values[j * (self.resolution + 1) + i]
+        }
+
+        // value_range returns the lowest and the highest finite value, min is greater than max if there're none
+        pub fn value_range(&self) -> (f64, f64) {
+            self.values
+                .iter()
+                .filter(|value| value.is_finite())
+                .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), value| (min.min(*value), max.max(*value)))
+        }
+
+        // contour approximates the curve `function(x, y) = level` by marching squares
+        // and returns it as a set of line segments
+        pub fn contour(&self, level: f64) -> Vec<[(f64, f64); 2]> {
+            let mut segments = Vec::new();
+
+            for j in 0..self.resolution {
+                for i in 0..self.resolution {
+                    // the corners go counterclockwise starting from the bottom left one,
+                    // k-th edge connects k-th and (k + 1)-th corners
+                    let corners = [(i, j), (i + 1, j), (i + 1, j + 1), (i, j + 1)];
+                    let values = corners.map(|(i, j)| self.value(i, j));
+                    let is_above = values.map(|value| value >= level);
+
+                    let crossing = |edge: usize| {
+                        let (a, b) = (edge, (edge + 1) % 4);
+                        let (x_a, y_a) = self.point(corners[a].0, corners[a].1);
+                        let (x_b, y_b) = self.point(corners[b].0, corners[b].1);
+                        let t = (level - values[a]) / (values[b] - values[a]);
+
+                        (x_a + (x_b - x_a) * t, y_a + (y_b - y_a) * t)
+                    };
+
+                    let crossed_edges: Vec<usize> = (0..4)
+                        .filter(|edge| is_above[*edge] != is_above[(edge + 1) % 4])
+                        .collect();
+
+                    match crossed_edges.len() {
+                        2 => segments.push([crossing(crossed_edges[0]), crossing(crossed_edges[1])]),
+                        4 => {
+                            // a saddle, the value in the center decides which corners are cut off
+                            let is_center_above = values.iter().sum::<f64>() / 4.0 >= level;
+                            for (corner, is_corner_above) in is_above.iter().enumerate() {
+                                if *is_corner_above != is_center_above {
+                                    segments.push([crossing((corner + 3) % 4), crossing(corner)]);
+                                }
+                            }
+                        }
+                        _ => (),
+                    }
+                }
+            }
+
+            segments
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use crate::plotters::two_d::{closest_point, Grid};
+        use plotters::prelude::Cartesian2d;
+
+        #[test]
+        // the pixel range of the y axis is reversed like in the charts
+        #[allow(clippy::reversed_empty_ranges)]
+        fn test_closest_point() {
+            // 10 pixels per unit, the y axis goes up
+            let coord_spec = Cartesian2d::new(0.0..10.0, 0.0..10.0, (0..100, 100..0));
+            let points = vec![(1.0, 1.0), (5.0, 5.0), (5.3, 5.0)];
+
+            assert_eq!(closest_point(&coord_spec, points.iter().copied(), (52, 50), 5), Some(2));
+            assert_eq!(closest_point(&coord_spec, points.iter().copied(), (10, 90), 5), Some(0));
+            assert_eq!(closest_point(&coord_spec, points.iter().copied(), (90, 10), 5), None);
+        }
+
+        #[test]
+        fn test_contour_of_circle() {
+            let grid = Grid::sample(|x, y| x * x + y * y, (-2.0, 2.0), (-2.0, 2.0), 40);
+            assert_eq!(grid.value_range(), (0.0, 8.0));
+            let segments = grid.contour(1.0);
+
+            assert!(!segments.is_empty());
+            for segment in segments {
+                for (x, y) in segment.iter() {
+                    assert!(relative_eq!((x * x + y * y).sqrt(), 1.0, epsilon = 0.01));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,179 @@
+use crate::demo_data::RegressionDemoDataNFeatures;
+use crate::learning_settings::LearningSettings;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+// Camera is the rotation of a 3D chart, it's shared by the visualizer that rotates it and the session
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Camera {
+    pub pitch: f64,
+    pub yaw: f64,
+}
+
+// ChartSession is everything needed to reopen a chart where it was left: the examples, the model
+// and the learning settings. The features are stored as vectors to keep the format the same for all the charts
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChartSession {
+    pub xs: Vec<Vec<f64>>,
+    pub ys: Vec<f64>,
+    pub theta: Vec<f64>,
+    pub learning_rate: f64,
+    pub settings: LearningSettings,
+    // only the 3D charts have it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub camera: Option<Camera>,
+}
+
+impl ChartSession {
+    pub fn new<const N_FEATURES: usize>(
+        data: &RegressionDemoDataNFeatures<N_FEATURES>,
+        camera: Option<Camera>,
+    ) -> ChartSession where [(); N_FEATURES + 1]: {
+        ChartSession {
+            xs: data.xs.iter().map(|x| x.to_vec()).collect(),
+            ys: data.ys.clone(),
+            theta: data.theta.to_vec(),
+            learning_rate: data.learning_rate,
+            settings: data.settings,
+            camera,
+        }
+    }
+
+    // check makes sure the session fits a chart with the given number of features,
+    // the files can be edited by hand or come from a different chart
+    pub fn check(&self, number_of_features: usize) -> Result<(), Box<dyn Error>> {
+        if let Some(i) = self.xs.iter().position(|x| x.len() != number_of_features) {
+            return Err(format!(
+                "example {}: expected {} features, got {}", i + 1, number_of_features, self.xs[i].len(),
+            ).into());
+        }
+        if self.xs.is_empty() {
+            return Err("there are no examples".into());
+        }
+        if self.xs.len() != self.ys.len() {
+            return Err(format!("there are {} examples but {} labels", self.xs.len(), self.ys.len()).into());
+        }
+        if self.theta.len() != number_of_features + 1 {
+            return Err(format!("expected {} theta values, got {}", number_of_features + 1, self.theta.len()).into());
+        }
+        if !(self.learning_rate.is_finite() && self.learning_rate > 0.0) {
+            return Err(format!("the learning rate should be positive, got {}", self.learning_rate).into());
+        }
+        self.settings.check()?;
+
+        Ok(())
+    }
+
+    // check_labels returns an error pointing to the first example with an invalid label like Dataset::check_labels,
+    // so the sessions follow the same rules as the data files
+    pub fn check_labels<F: Fn(f64) -> bool>(&self, is_valid: F, expected: &str) -> Result<(), Box<dyn Error>> {
+        match self.ys.iter().position(|y| !is_valid(*y)) {
+            Some(i) => Err(format!("example {}: the label is {}, expected {}", i + 1, self.ys[i], expected).into()),
+            None => Ok(()),
+        }
+    }
+
+    pub fn restore<const N_FEATURES: usize>(
+        &self,
+        data: &mut RegressionDemoDataNFeatures<N_FEATURES>,
+    ) -> Result<(), Box<dyn Error>> where [(); N_FEATURES + 1]: {
+        self.check(N_FEATURES)?;
+
+        data.xs = self.xs.iter().map(|x| {
+            let mut example = [0.0; N_FEATURES];
+            example.copy_from_slice(x);
+            example
+        }).collect();
+        data.ys = self.ys.clone();
+        data.theta.copy_from_slice(&self.theta);
+        data.trajectory = vec![data.theta];
+        data.learning_rate = self.learning_rate;
+        data.settings = self.settings;
+
+        Ok(())
+    }
+}
+
+pub fn save_session<S: Serialize>(path: &Path, session: &S) -> Result<(), Box<dyn Error>> {
+    let content = serde_json::to_string_pretty(session)?;
+    fs::write(path, content).map_err(|error| format!("{}: {}", path.display(), error))?;
+
+    Ok(())
+}
+
+pub fn load_session<S: DeserializeOwned>(path: &Path) -> Result<S, Box<dyn Error>> {
+    let content = fs::read_to_string(path).map_err(|error| format!("{}: {}", path.display(), error))?;
+
+    Ok(serde_json::from_str(&content).map_err(|error| format!("{}: {}", path.display(), error))?)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::demo_data::RegressionDemoData2Features;
+    use crate::learning_settings::LearningSettings;
+    use crate::session::{Camera, ChartSession};
+
+    #[test]
+    fn test_save_and_restore() {
+        let data = RegressionDemoData2Features {
+            xs: vec![[1.0, 2.0], [3.0, 0.1]],
+            ys: vec![1.0, 0.0],
+            theta: [0.5, -1.0, 1e-9],
+            learning_rate: 0.03,
+            settings: LearningSettings {
+                learning_rate: 0.01,
+                steps_per_update: 5,
+                update_period: 20,
+                default_theta: 1.0,
+                use_normal_equation: true,
+            },
+            iteration: 0,
+            trajectory: Vec::new(),
+            online_examples: None,
+        };
+        let session = ChartSession::new(&data, Some(Camera { pitch: 0.1, yaw: -0.2 }));
+
+        let json = serde_json::to_string(&session).unwrap();
+        let session: ChartSession = serde_json::from_str(&json).unwrap();
+
+        let mut restored = RegressionDemoData2Features {
+            xs: Vec::new(),
+            ys: Vec::new(),
+            theta: [0.0; 3],
+            learning_rate: 1.0,
+            settings: LearningSettings {
+                learning_rate: 1.0,
+                steps_per_update: 1,
+                update_period: 1,
+                default_theta: 0.0,
+                use_normal_equation: false,
+            },
+            iteration: 0,
+            trajectory: Vec::new(),
+            online_examples: None,
+        };
+        session.restore(&mut restored).unwrap();
+        assert_eq!(restored.xs, data.xs);
+        assert_eq!(restored.ys, data.ys);
+        assert_eq!(restored.theta, data.theta);
+        assert_eq!(restored.learning_rate, data.learning_rate);
+        assert_eq!(restored.settings, data.settings);
+        assert_eq!(session.camera, Some(Camera { pitch: 0.1, yaw: -0.2 }));
+
+        let error = session.check(1).unwrap_err();
+        assert_eq!(error.to_string(), "example 1: expected 1 features, got 2");
+
+        let error = session.check_labels(|y| y == 0.0 || y == 1.0, "0 or 1");
+        assert!(error.is_ok());
+        let session = ChartSession { ys: vec![0.3, 7.0], ..session };
+        let error = session.check_labels(|y| y == 0.0 || y == 1.0, "0 or 1").unwrap_err();
+        assert_eq!(error.to_string(), "example 1: the label is 0.3, expected 0 or 1");
+
+        let session = ChartSession { xs: Vec::new(), ys: Vec::new(), ..session };
+        assert_eq!(session.check(2).unwrap_err().to_string(), "there are no examples");
+        assert!(session.restore(&mut restored).is_err());
+    }
+}
@@ -0,0 +1,502 @@
+// the tolerance of the KKT conditions check in SMO
+pub const SMO_TOLERANCE: f64 = 1e-3;
+// alphas which changed less than this are considered unchanged
+pub const SMO_ALPHA_TOLERANCE: f64 = 1e-5;
+// alphas closer than `C * SMO_BOUND_TOLERANCE` to 0 or C are moved to the bound,
+// otherwise rounding errors leave them slightly inside the box and SMO gets stuck on them
+pub const SMO_BOUND_TOLERANCE: f64 = 1e-8;
+// the points with alpha bigger than this are support vectors
+pub const SUPPORT_VECTOR_ALPHA_THRESHOLD: f64 = 1e-8;
+
+// LinearSvm is a linear support vector machine minimizing
+// `C * sum(max(0, 1 - y * theta_transpose * x)) + 1 / 2 * |w| ^ 2`.
+// It's trained by SMO(sequential minimal optimization) on the dual problem,
+// one SMO pass per step, so the learning thread can show how it converges.
+// theta[0] is the bias and the rest is w, just like in logistic regression
+#[derive(Debug, Clone)]
+pub struct LinearSvm<const N_FEATURES: usize> where [(); N_FEATURES + 1]: {
+    pub theta: [f64; N_FEATURES + 1],
+    pub c: f64,
+    // the labels the alphas were found for, converted to -1 and 1
+    ys: Vec<f64>,
+    state: SmoState,
+}
+
+impl<const N_FEATURES: usize> LinearSvm<N_FEATURES> where [(); N_FEATURES + 1]: {
+    pub fn new(c: f64) -> LinearSvm<N_FEATURES> {
+        LinearSvm {
+            theta: [0.0; N_FEATURES + 1],
+            c,
+            ys: Vec::new(),
+            state: SmoState::default(),
+        }
+    }
+
+    // train_step makes one SMO pass(up to m steps on the max violating pairs) and returns the number
+    // of the made steps(0 means that we've converged), ys are 0 or 1 like in logistic regression
+    pub fn train_step(&mut self, xs: &[[f64; N_FEATURES]], ys: &[f64]) -> usize {
+        assert_eq!(xs.len(), ys.len());
+
+        let ys: Vec<f64> = ys.iter().map(|y| to_svm_label(*y)).collect();
+        // the alphas keep `sum(alpha * y) = 0` and `0 <= alpha <= C` only while every alpha belongs
+        // to an example with the same label and C isn't decreased, otherwise SMO starts over.
+        // New points start outside of the margin which keeps the constraints
+        let is_feasible = self.ys.len() <= ys.len()
+            && self.ys.iter().zip(&ys).all(|(old, new)| old == new)
+            && self.state.alphas.iter().all(|alpha| *alpha <= self.c);
+        if is_feasible {
+            self.state.alphas.resize(ys.len(), 0.0);
+        } else {
+            self.state = SmoState { alphas: vec![0.0; ys.len()], ..SmoState::default() };
+        }
+        self.ys = ys.clone();
+        let kernel = |i: usize, j: usize| dot(&xs[i], &xs[j]);
+
+        // the training set could have changed since the last step
+        self.state.update_errors(&ys, &kernel);
+        let number_of_changed_alphas = smo_pass(&mut self.state, &ys, self.c, &kernel);
+
+        self.theta = [0.0; N_FEATURES + 1];
+        self.theta[0] = self.state.b;
+        for (i, x) in xs.iter().enumerate() {
+            for (w, x_value) in self.theta[1..].iter_mut().zip(x) {
+                *w += self.state.alphas[i] * ys[i] * x_value;
+            }
+        }
+
+        number_of_changed_alphas
+    }
+
+    // decision_function returns `theta_transpose * x`, the sign of it is the predicted class
+    // and the value of 1 or -1 means that x lies on the margin
+    pub fn decision_function(&self, x: [f64; N_FEATURES]) -> f64 {
+        self.theta[0] + dot(&self.theta[1..], &x)
+    }
+
+    pub fn predict(&self, x: [f64; N_FEATURES]) -> bool {
+        self.decision_function(x) >= 0.0
+    }
+
+    // support_vectors returns indices of the training examples with non zero alphas
+    pub fn support_vectors(&self) -> Vec<usize> {
+        self.state.support_vectors()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Kernel {
+    // `a_transpose * b`
+    Linear,
+    // `(a_transpose * b + coef0) ^ degree`
+    Polynomial { degree: i32, coef0: f64 },
+    // `exp(-|a - b| ^ 2 / (2 * sigma ^ 2))`, sigma controls how fast the similarity falls off
+    Gaussian { sigma: f64 },
+}
+
+impl Kernel {
+    pub fn compute(&self, a: &[f64], b: &[f64]) -> f64 {
+        match self {
+            Kernel::Linear => dot(a, b),
+            Kernel::Polynomial { degree, coef0 } => (dot(a, b) + coef0).powi(*degree),
+            Kernel::Gaussian { sigma } => {
+                let squared_distance: f64 = a.iter().zip(b).map(|(a, b)| (a - b).powi(2)).sum();
+
+                (-squared_distance / (2.0 * sigma * sigma)).exp()
+            }
+        }
+    }
+}
+
+// KernelSvm is a support vector machine with any kernel, so it can learn non-linear boundaries.
+// Unlike LinearSvm it doesn't have theta, the prediction is made from the support vectors:
+// `f(x) = sum(alpha_i * y_i * K(x_i, x)) + b`. The training set is fixed and the kernel matrix
+// is calculated once, so it's meant for the sets of up to a few thousands examples
+#[derive(Debug, Clone)]
+pub struct KernelSvm {
+    pub kernel: Kernel,
+    pub c: f64,
+    xs: Vec<Vec<f64>>,
+    // labels converted to -1 and 1
+    ys: Vec<f64>,
+    kernel_matrix: Vec<f64>,
+    state: SmoState,
+}
+
+impl KernelSvm {
+    // new prepares the SVM for training, ys are 0 or 1 like in logistic regression
+    pub fn new<X: AsRef<[f64]>>(kernel: Kernel, c: f64, xs: &[X], ys: &[f64]) -> KernelSvm {
+        assert_eq!(xs.len(), ys.len());
+
+        let m = xs.len();
+        let xs: Vec<Vec<f64>> = xs.iter().map(|x| x.as_ref().to_vec()).collect();
+        let ys: Vec<f64> = ys.iter().map(|y| to_svm_label(*y)).collect();
+
+        let mut kernel_matrix = vec![0.0; m * m];
+        for i in 0..m {
+            for j in i..m {
+                let value = kernel.compute(&xs[i], &xs[j]);
+                kernel_matrix[i * m + j] = value;
+                kernel_matrix[j * m + i] = value;
+            }
+        }
+
+        let state = SmoState {
+            alphas: vec![0.0; m],
+            b: 0.0,
+            // all alphas are 0, so f(x) = 0 everywhere
+            errors: ys.iter().map(|y| -y).collect(),
+        };
+
+        KernelSvm { kernel, c, xs, ys, kernel_matrix, state }
+    }
+
+    // train_step makes one SMO pass(up to m steps on the max violating pairs) and returns the number
+    // of the made steps, 0 means that we've converged
+    pub fn train_step(&mut self) -> usize {
+        let m = self.xs.len();
+        let kernel_matrix = &self.kernel_matrix;
+        let kernel = |i: usize, j: usize| kernel_matrix[i * m + j];
+
+        smo_pass(&mut self.state, &self.ys, self.c, &kernel)
+    }
+
+    // train makes the passes until convergence or until max_passes is reached
+    // and returns the number of the made passes
+    pub fn train(&mut self, max_passes: usize) -> usize {
+        for pass in 1..=max_passes {
+            if self.train_step() == 0 {
+                return pass;
+            }
+        }
+
+        max_passes
+    }
+
+    // decision_function returns `f(x)`, the sign of it is the predicted class
+    // and the value of 1 or -1 means that x lies on the margin
+    pub fn decision_function(&self, x: &[f64]) -> f64 {
+        self.state
+            .alphas
+            .iter()
+            .enumerate()
+            .filter(|(_, alpha)| **alpha > SUPPORT_VECTOR_ALPHA_THRESHOLD)
+            .map(|(i, alpha)| alpha * self.ys[i] * self.kernel.compute(&self.xs[i], x))
+            .sum::<f64>()
+            + self.state.b
+    }
+
+    pub fn predict(&self, x: &[f64]) -> bool {
+        self.decision_function(x) >= 0.0
+    }
+
+    // error returns the fraction of the misclassified examples, ys are 0 or 1
+    pub fn error<X: AsRef<[f64]>>(&self, xs: &[X], ys: &[f64]) -> f64 {
+        if xs.is_empty() {
+            return 0.0;
+        }
+
+        let number_of_errors = xs
+            .iter()
+            .zip(ys)
+            .filter(|(x, y)| self.predict(x.as_ref()) != (**y > 0.5))
+            .count();
+
+        number_of_errors as f64 / xs.len() as f64
+    }
+
+    // support_vectors returns indices of the training examples with non zero alphas
+    pub fn support_vectors(&self) -> Vec<usize> {
+        self.state.support_vectors()
+    }
+
+    // linear_theta returns `[b, w]` such that `f(x) = b + w_transpose * x`,
+    // it's possible only for the linear kernel
+    pub fn linear_theta(&self) -> Option<Vec<f64>> {
+        if self.kernel != Kernel::Linear {
+            return None;
+        }
+
+        let n = self.xs.first().map_or(0, |x| x.len());
+        let mut theta = vec![0.0; n + 1];
+        theta[0] = self.state.b;
+        for (i, x) in self.xs.iter().enumerate() {
+            for (w, x_value) in theta[1..].iter_mut().zip(x) {
+                *w += self.state.alphas[i] * self.ys[i] * x_value;
+            }
+        }
+
+        Some(theta)
+    }
+
+    pub fn training_set(&self) -> &[Vec<f64>] {
+        &self.xs
+    }
+}
+
+// select_parameters trains an SVM for every pair of kernel and C on the training set
+// and returns the one with the smallest error on the cross validation set with that error
+pub fn select_parameters<X, I>(
+    train_xs: &[X],
+    train_ys: &[f64],
+    cv_xs: &[X],
+    cv_ys: &[f64],
+    candidates: I,
+    max_passes: usize,
+) -> Option<(KernelSvm, f64)>
+    where X: AsRef<[f64]>,
+          I: IntoIterator<Item=(Kernel, f64)>,
+{
+    let mut best: Option<(KernelSvm, f64)> = None;
+
+    for (kernel, c) in candidates {
+        let mut svm = KernelSvm::new(kernel, c, train_xs, train_ys);
+        svm.train(max_passes);
+        let cv_error = svm.error(cv_xs, cv_ys);
+
+        let is_better = match &best {
+            Some((_, best_cv_error)) => cv_error < *best_cv_error,
+            None => true,
+        };
+        if is_better {
+            best = Some((svm, cv_error));
+        }
+    }
+
+    best
+}
+
+// SmoState is the state of SMO between the changes of alphas
+#[derive(Debug, Clone, Default)]
+struct SmoState {
+    // Lagrange multipliers, one per training example
+    alphas: Vec<f64>,
+    b: f64,
+    // errors[k] = f(x_k) - y_k, they are updated after every change of alphas
+    errors: Vec<f64>,
+}
+
+impl SmoState {
+    // update_errors calculates the errors from scratch
+    fn update_errors<K: Fn(usize, usize) -> f64>(&mut self, ys: &[f64], kernel: &K) {
+        let m = ys.len();
+        let alphas = &self.alphas;
+
+        self.errors = (0..m)
+            .map(|k| {
+                (0..m).map(|l| alphas[l] * ys[l] * kernel(l, k)).sum::<f64>() + self.b - ys[k]
+            })
+            .collect();
+    }
+
+    fn support_vectors(&self) -> Vec<usize> {
+        self.alphas
+            .iter()
+            .enumerate()
+            .filter(|(_, alpha)| **alpha > SUPPORT_VECTOR_ALPHA_THRESHOLD)
+            .map(|(i, _)| i)
+            .collect()
+    }
+}
+
+// smo_pass makes up to m steps of SMO. Instead of the random pairs of the simplified SMO from ex6
+// every step optimizes the pair violating KKT conditions the most(like LIBSVM does), which converges
+// much faster. Returns the number of the made steps, 0 means that KKT conditions are satisfied.
+// ys are -1 or 1, kernel(i, j) calculates the kernel between i-th and j-th examples
+fn smo_pass<K: Fn(usize, usize) -> f64>(state: &mut SmoState, ys: &[f64], c: f64, kernel: &K) -> usize {
+    let m = ys.len();
+    if m < 2 {
+        return 0;
+    }
+
+    for step in 0..m {
+        // alpha_i can be moved in the direction increasing y_i * f(x_i) and alpha_j decreasing it,
+        // the pair is optimal when no such alphas have errors differing more than the tolerance
+        let can_increase = |k: usize| {
+            (ys[k] > 0.0 && state.alphas[k] < c) || (ys[k] < 0.0 && state.alphas[k] > 0.0)
+        };
+        let can_decrease = |k: usize| {
+            (ys[k] < 0.0 && state.alphas[k] < c) || (ys[k] > 0.0 && state.alphas[k] > 0.0)
+        };
+        let errors = &state.errors;
+        let i = (0..m)
+            .filter(|k| can_increase(*k))
+            .min_by(|k1, k2| errors[*k1].total_cmp(&errors[*k2]));
+        let j = (0..m)
+            .filter(|k| can_decrease(*k))
+            .max_by(|k1, k2| errors[*k1].total_cmp(&errors[*k2]));
+
+        let (i, j) = match (i, j) {
+            (Some(i), Some(j)) if errors[j] - errors[i] > 2.0 * SMO_TOLERANCE => (i, j),
+            _ => return step,
+        };
+
+        if !optimize_pair(i, j, state, ys, c, kernel) {
+            return step;
+        }
+    }
+
+    m
+}
+
+// optimize_pair jointly optimizes alphas i and j, returns false if no progress can be made
+fn optimize_pair<K: Fn(usize, usize) -> f64>(
+    i: usize, j: usize, state: &mut SmoState, ys: &[f64], c: f64, kernel: &K,
+) -> bool {
+    let alpha_i_old = state.alphas[i];
+    let alpha_j_old = state.alphas[j];
+
+    let (low, high) = if ys[i] == ys[j] {
+        ((alpha_i_old + alpha_j_old - c).max(0.0), (alpha_i_old + alpha_j_old).min(c))
+    } else {
+        ((alpha_j_old - alpha_i_old).max(0.0), (c + alpha_j_old - alpha_i_old).min(c))
+    };
+    if low >= high {
+        return false;
+    }
+
+    let k_ij = kernel(i, j);
+    let k_ii = kernel(i, i);
+    let k_jj = kernel(j, j);
+    let eta = 2.0 * k_ij - k_ii - k_jj;
+    if eta >= 0.0 {
+        return false;
+    }
+
+    let error_i = state.errors[i];
+    let error_j = state.errors[j];
+
+    let alpha_j = (alpha_j_old - ys[j] * (error_i - error_j) / eta).clamp(low, high);
+    if (alpha_j - alpha_j_old).abs() < SMO_ALPHA_TOLERANCE {
+        return false;
+    }
+    let alpha_j = snap_to_bounds(alpha_j, c);
+    let alpha_i = snap_to_bounds(alpha_i_old + ys[i] * ys[j] * (alpha_j_old - alpha_j), c);
+
+    let delta_i = ys[i] * (alpha_i - alpha_i_old);
+    let delta_j = ys[j] * (alpha_j - alpha_j_old);
+
+    let b1 = state.b - error_i - delta_i * k_ii - delta_j * k_ij;
+    let b2 = state.b - error_j - delta_i * k_ij - delta_j * k_jj;
+    let new_b = if alpha_i > 0.0 && alpha_i < c {
+        b1
+    } else if alpha_j > 0.0 && alpha_j < c {
+        b2
+    } else {
+        (b1 + b2) / 2.0
+    };
+
+    for (k, error) in state.errors.iter_mut().enumerate() {
+        *error += delta_i * kernel(i, k) + delta_j * kernel(j, k) + new_b - state.b;
+    }
+
+    state.alphas[i] = alpha_i;
+    state.alphas[j] = alpha_j;
+    state.b = new_b;
+
+    true
+}
+
+fn snap_to_bounds(alpha: f64, c: f64) -> f64 {
+    if alpha < c * SMO_BOUND_TOLERANCE {
+        0.0
+    } else if alpha > c * (1.0 - SMO_BOUND_TOLERANCE) {
+        c
+    } else {
+        alpha
+    }
+}
+
+fn dot(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(a, b)| a * b).sum()
+}
+
+// to_svm_label converts 0/1 labels into -1/1
+pub fn to_svm_label(y: f64) -> f64 {
+    if y < 0.5 { -1.0 } else { 1.0 }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::svm::{Kernel, KernelSvm, LinearSvm};
+
+    #[test]
+    fn test_linear_svm_separates_points() {
+        let xs = [[1.0, 1.0], [2.0, 1.0], [3.0, 1.0], [7.0, 7.0], [8.0, 8.0], [9.0, 9.0]];
+        let ys = [0.0, 0.0, 0.0, 1.0, 1.0, 1.0];
+
+        let mut svm = LinearSvm::<2>::new(100.0);
+        for _ in 0..100 {
+            if svm.train_step(&xs, &ys) == 0 {
+                break;
+            }
+        }
+
+        for (x, y) in xs.iter().zip(&ys) {
+            assert_eq!(svm.predict(*x), *y > 0.5);
+        }
+
+        // the closest points of the different classes lie on the margin
+        assert_eq!(svm.support_vectors(), vec![2, 3]);
+        assert!(relative_eq!(svm.decision_function(xs[2]), -1.0, epsilon = 1e-3));
+        assert!(relative_eq!(svm.decision_function(xs[3]), 1.0, epsilon = 1e-3));
+    }
+
+    #[test]
+    fn test_linear_svm_relabelled_point() {
+        let xs = [[1.0, 1.0], [2.0, 1.0], [3.0, 1.0], [7.0, 7.0], [8.0, 8.0], [9.0, 9.0]];
+        let mut ys = [0.0, 0.0, 0.0, 1.0, 1.0, 1.0];
+        let train = |svm: &mut LinearSvm<2>, ys: &[f64]| {
+            for _ in 0..100 {
+                if svm.train_step(&xs, ys) == 0 {
+                    break;
+                }
+            }
+        };
+
+        let mut svm = LinearSvm::<2>::new(100.0);
+        train(&mut svm, &ys);
+        // the support vector changes its class, the result is the same as training from scratch
+        ys[2] = 1.0;
+        train(&mut svm, &ys);
+        let mut new_svm = LinearSvm::<2>::new(100.0);
+        train(&mut new_svm, &ys);
+
+        for (theta, new_theta) in svm.theta.iter().zip(&new_svm.theta) {
+            assert!(relative_eq!(theta, new_theta, epsilon = 1e-3));
+        }
+        for (x, y) in xs.iter().zip(&ys) {
+            assert_eq!(svm.predict(*x), *y > 0.5);
+        }
+    }
+
+    #[test]
+    fn test_linear_svm_with_nan() {
+        // a non-finite point makes the errors NaN, the training stops instead of panicking
+        let xs = [[1.0, 1.0], [f64::NAN, 1.0], [8.0, 8.0]];
+        let ys = [0.0, 0.0, 1.0];
+
+        let mut svm = LinearSvm::<2>::new(100.0);
+        svm.train_step(&xs, &ys);
+    }
+
+    #[test]
+    fn test_gaussian_kernel() {
+        // the values from ex6
+        let kernel = Kernel::Gaussian { sigma: 2.0 };
+
+        assert!(relative_eq!(kernel.compute(&[1.0, 2.0, 1.0], &[0.0, 4.0, -1.0]), 0.324652, epsilon = 1e-6));
+    }
+
+    #[test]
+    fn test_kernel_svm_learns_xor() {
+        // the points of xor can't be separated by a line
+        let xs = [[0.0, 0.0], [1.0, 1.0], [0.0, 1.0], [1.0, 0.0]];
+        let ys = [0.0, 0.0, 1.0, 1.0];
+
+        let mut svm = KernelSvm::new(Kernel::Gaussian { sigma: 0.5 }, 100.0, &xs, &ys);
+        svm.train(100);
+
+        assert_eq!(svm.error(&xs, &ys), 0.0);
+        assert_eq!(svm.support_vectors().len(), 4);
+    }
+}
@@ -0,0 +1,224 @@
+use druid::widget::{Button, Controller, Flex, Label, Slider};
+use druid::widget::prelude::*;
+use druid::{Data, Lens, WidgetExt};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::time::Duration;
+
+pub const MIN_STEPS: usize = 1;
+pub const MAX_STEPS: usize = 100;
+// the speed slider is logarithmic: from 10 times slower to 10 times faster
+pub const MIN_SPEED_LOG10: f64 = -1.0;
+pub const MAX_SPEED_LOG10: f64 = 1.0;
+pub const CONTROL_LABEL_WIDTH: f64 = 200.0;
+
+// ControlCommand is sent by the UI to the learning thread
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ControlCommand {
+    Start,
+    Pause,
+    // makes the given number of iterations and stays paused
+    Step(usize),
+    // the learning starts over
+    Reset,
+    // the updates happen this many times as often
+    SetSpeed(f64),
+}
+
+// TrainingControl is the end of the control channel in the learning thread
+pub struct TrainingControl {
+    commands: Receiver<ControlCommand>,
+    is_running: bool,
+    steps_left: usize,
+    speed: f64,
+}
+
+pub fn control_channel() -> (Sender<ControlCommand>, TrainingControl) {
+    let (sender, receiver) = mpsc::channel();
+
+    (sender, TrainingControl {
+        commands: receiver,
+        is_running: true,
+        steps_left: 0,
+        speed: 1.0,
+    })
+}
+
+impl TrainingControl {
+    // receive handles the commands sent since the last update, it returns true if the learning should start over
+    pub fn receive(&mut self) -> bool {
+        let mut reset_requested = false;
+
+        for command in self.commands.try_iter() {
+            match command {
+                ControlCommand::Start => self.is_running = true,
+                ControlCommand::Pause => {
+                    self.is_running = false;
+                    self.steps_left = 0;
+                }
+                ControlCommand::Step(number_of_steps) => {
+                    self.is_running = false;
+                    self.steps_left += number_of_steps;
+                }
+                ControlCommand::Reset => reset_requested = true,
+                ControlCommand::SetSpeed(speed) => self.speed = speed,
+            }
+        }
+
+        reset_requested
+    }
+
+    // take_iterations returns how many iterations to make in this update: all of them while running,
+    // otherwise the ones left from the step commands
+    pub fn take_iterations(&mut self, iterations_per_update: usize) -> usize {
+        if self.is_running {
+            return iterations_per_update;
+        }
+
+        let iterations = self.steps_left.min(iterations_per_update);
+        self.steps_left -= iterations;
+
+        iterations
+    }
+
+    // period returns the pause between the updates at the current speed
+    pub fn period(&self, period: Duration) -> Duration {
+        period.div_f64(self.speed)
+    }
+}
+
+// TrainingControlData is the state of the control widgets
+#[derive(Clone, Data, Lens, Debug)]
+pub struct TrainingControlData {
+    pub is_running: bool,
+    pub steps_slider: f64,
+    pub speed_log10: f64,
+}
+
+impl Default for TrainingControlData {
+    fn default() -> TrainingControlData {
+        TrainingControlData {
+            is_running: true,
+            steps_slider: MIN_STEPS as f64,
+            speed_log10: 0.0,
+        }
+    }
+}
+
+impl TrainingControlData {
+    pub fn steps(&self) -> usize {
+        (self.steps_slider.round() as usize).clamp(MIN_STEPS, MAX_STEPS)
+    }
+
+    pub fn speed(&self) -> f64 {
+        10f64.powf(self.speed_log10)
+    }
+}
+
+// ControlSenders sends the commands to the learning threads of all the charts of a demo
+#[derive(Clone)]
+struct ControlSenders(Vec<Sender<ControlCommand>>);
+
+impl ControlSenders {
+    fn send(&self, command: ControlCommand) {
+        for sender in &self.0 {
+            // the learning thread is gone only when the app is closing
+            let _ = sender.send(command);
+        }
+    }
+}
+
+// SpeedController sends the speed to the learning threads when it's changed by the slider
+struct SpeedController {
+    senders: ControlSenders,
+}
+
+impl<W: Widget<TrainingControlData>> Controller<TrainingControlData, W> for SpeedController {
+    fn update(
+        &mut self, child: &mut W, ctx: &mut UpdateCtx, old_data: &TrainingControlData, data: &TrainingControlData,
+        env: &Env,
+    ) {
+        if !old_data.speed_log10.same(&data.speed_log10) {
+            self.senders.send(ControlCommand::SetSpeed(data.speed()));
+        }
+
+        child.update(ctx, old_data, data, env)
+    }
+}
+
+// build_training_control_widget builds the start/pause, step, reset buttons and the speed slider,
+// the commands are sent to all the senders
+pub fn build_training_control_widget(senders: Vec<Sender<ControlCommand>>) -> impl Widget<TrainingControlData> {
+    let senders = ControlSenders(senders);
+    let start_senders = senders.clone();
+    let step_senders = senders.clone();
+    let reset_senders = senders.clone();
+
+    Flex::column()
+        .with_child(
+            Flex::row()
+                .with_child(
+                    Button::new(|data: &TrainingControlData, _env: &Env| {
+                        if data.is_running { "pause".to_string() } else { "start".to_string() }
+                    }).on_click(move |_ctx, data: &mut TrainingControlData, _env| {
+                        data.is_running = !data.is_running;
+                        start_senders.send(if data.is_running { ControlCommand::Start } else { ControlCommand::Pause });
+                    }),
+                )
+                .with_child(
+                    Button::new(|data: &TrainingControlData, _env: &Env| format!("step {}", data.steps()))
+                        .on_click(move |_ctx, data: &mut TrainingControlData, _env| {
+                            data.is_running = false;
+                            step_senders.send(ControlCommand::Step(data.steps()));
+                        }),
+                )
+                .with_child(
+                    Slider::new()
+                        .with_range(MIN_STEPS as f64, MAX_STEPS as f64)
+                        .lens(TrainingControlData::steps_slider),
+                )
+                .with_child(
+                    Button::new("reset").on_click(move |_ctx, _data: &mut TrainingControlData, _env| {
+                        reset_senders.send(ControlCommand::Reset);
+                    }),
+                ),
+        )
+        .with_child(
+            Flex::row()
+                .with_child(
+                    Label::new(|data: &TrainingControlData, _env: &Env| format!("speed: {:.2}x", data.speed()))
+                        .fix_width(CONTROL_LABEL_WIDTH),
+                )
+                .with_child(
+                    Slider::new()
+                        .with_range(MIN_SPEED_LOG10, MAX_SPEED_LOG10)
+                        .lens(TrainingControlData::speed_log10),
+                ),
+        )
+        .controller(SpeedController { senders })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::training_control::{control_channel, ControlCommand};
+    use std::time::Duration;
+
+    #[test]
+    fn test_training_control() {
+        let (sender, mut control) = control_channel();
+        assert!(!control.receive());
+        assert_eq!(control.take_iterations(10), 10);
+
+        sender.send(ControlCommand::Step(15)).unwrap();
+        sender.send(ControlCommand::SetSpeed(2.0)).unwrap();
+        assert!(!control.receive());
+        assert_eq!(control.take_iterations(10), 10);
+        assert_eq!(control.take_iterations(10), 5);
+        assert_eq!(control.take_iterations(10), 0);
+        assert_eq!(control.period(Duration::from_millis(10)), Duration::from_millis(5));
+
+        sender.send(ControlCommand::Reset).unwrap();
+        sender.send(ControlCommand::Start).unwrap();
+        assert!(control.receive());
+        assert_eq!(control.take_iterations(10), 10);
+    }
+}
@@ -0,0 +1,177 @@
+use druid::{MouseButton, MouseEvent, Point};
+use plotters::coord::types::RangedCoordf64;
+use plotters::coord::ReverseCoordTranslate;
+use plotters::prelude::Cartesian2d;
+use std::ops::Range;
+
+// the axes fit the data with this much space around it(relative to the size of the data)
+pub const VIEW_PADDING: f64 = 0.1;
+// one step of the mouse wheel zooms the chart in or out this many times
+pub const ZOOM_STEP: f64 = 1.2;
+
+// View is the visible area of a chart with N axes, the ranges fit the data until the chart is zoomed or panned.
+// It's shared by the visualizer and the reset button
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct View<const N: usize> {
+    pub ranges: [(f64, f64); N],
+    // the ranges shown while there's no data
+    default_ranges: [(f64, f64); N],
+    // the ranges stop following the data once the user changes them
+    is_fitting: bool,
+}
+
+impl<const N: usize> View<N> {
+    pub fn new(default_ranges: [(f64, f64); N]) -> View<N> {
+        View {
+            ranges: default_ranges,
+            default_ranges,
+            is_fitting: true,
+        }
+    }
+
+    // fit makes the ranges fit the points unless the chart was zoomed or panned
+    pub fn fit<I: IntoIterator<Item=[f64; N]>>(&mut self, points: I) {
+        if !self.is_fitting {
+            return;
+        }
+
+        let mut bounds = [(f64::INFINITY, f64::NEG_INFINITY); N];
+        for point in points {
+            for (bound, value) in bounds.iter_mut().zip(point) {
+                if value.is_finite() {
+                    *bound = (bound.0.min(value), bound.1.max(value));
+                }
+            }
+        }
+
+        for ((range, (min, max)), (default_min, default_max)) in self.ranges.iter_mut()
+            .zip(bounds)
+            .zip(self.default_ranges) {
+            *range = if min > max {
+                (default_min, default_max)
+            } else if min == max {
+                // all the values are the same, so they're put in the middle of a range as wide as the default one
+                let half_width = (default_max - default_min) / 2.0;
+                (min - half_width, max + half_width)
+            } else {
+                let padding = (max - min) * VIEW_PADDING;
+                (min - padding, max + padding)
+            };
+        }
+    }
+
+    pub fn range(&self, axis: usize) -> Range<f64> {
+        self.ranges[axis].0..self.ranges[axis].1
+    }
+
+    pub fn contains(&self, point: [f64; N]) -> bool {
+        self.ranges.iter().zip(point).all(|((min, max), value)| value >= *min && value <= *max)
+    }
+
+    // visible_segments splits the path into the parts inside of the view,
+    // plotting library doesn't support values outside of drawing region
+    pub fn visible_segments<I: IntoIterator<Item=[f64; N]>>(&self, path: I) -> Vec<Vec<[f64; N]>> {
+        let mut segments = vec![Vec::new()];
+        for point in path {
+            if self.contains(point) {
+                segments.last_mut().unwrap().push(point);
+            } else if !segments.last().unwrap().is_empty() {
+                segments.push(Vec::new());
+            }
+        }
+        segments.retain(|segment| !segment.is_empty());
+
+        segments
+    }
+
+    pub fn center(&self) -> [f64; N] {
+        self.ranges.map(|(min, max)| (min + max) / 2.0)
+    }
+
+    // zoom scales the ranges around the center, the chart is zoomed in if the factor is greater than 1
+    pub fn zoom(&mut self, center: [f64; N], factor: f64) {
+        self.is_fitting = false;
+        for (range, center) in self.ranges.iter_mut().zip(center) {
+            *range = (center - (center - range.0) / factor, center + (range.1 - center) / factor);
+        }
+    }
+
+    pub fn pan(&mut self, shift: [f64; N]) {
+        self.is_fitting = false;
+        for (range, shift) in self.ranges.iter_mut().zip(shift) {
+            *range = (range.0 + shift, range.1 + shift);
+        }
+    }
+
+    // reset makes the ranges fit the data again
+    pub fn reset(&mut self) {
+        self.ranges = self.default_ranges;
+        self.is_fitting = true;
+    }
+}
+
+// zoom_factor returns how much a step of the mouse wheel zooms the chart in,
+// the horizontal scrolling doesn't zoom it
+pub fn zoom_factor(event: &MouseEvent) -> f64 {
+    if event.wheel_delta.y < 0.0 {
+        ZOOM_STEP
+    } else if event.wheel_delta.y > 0.0 {
+        1.0 / ZOOM_STEP
+    } else {
+        1.0
+    }
+}
+
+// is_pan tells if the chart is panned by dragging after this mouse down:
+// by the middle button or by the left one with alt
+pub fn is_pan(event: &MouseEvent) -> bool {
+    event.button == MouseButton::Middle || (event.button == MouseButton::Left && event.mods.alt())
+}
+
+// pan_shift returns how much the view of a 2D chart should move so the data under the mouse follows it,
+// the pixels outside of the chart are ignored
+pub fn pan_shift(
+    coord_spec: &Cartesian2d<RangedCoordf64, RangedCoordf64>,
+    from: Point,
+    to: Point,
+) -> Option<[f64; 2]> {
+    let from = coord_spec.reverse_translate((from.x as i32, from.y as i32))?;
+    let to = coord_spec.reverse_translate((to.x as i32, to.y as i32))?;
+
+    Some([from.0 - to.0, from.1 - to.1])
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::view::View;
+
+    #[test]
+    fn test_view() {
+        let mut view = View::new([(0.0, 10.0), (0.0, 1.0)]);
+
+        view.fit(Vec::new());
+        assert_eq!(view.ranges, [(0.0, 10.0), (0.0, 1.0)]);
+
+        view.fit(vec![[20.0, 5.0], [30.0, 5.0]]);
+        assert_eq!(view.ranges, [(19.0, 31.0), (4.5, 5.5)]);
+
+        view.zoom([25.0, 5.0], 2.0);
+        assert_eq!(view.ranges, [(22.0, 28.0), (4.75, 5.25)]);
+        view.pan([1.0, -1.0]);
+        assert_eq!(view.ranges, [(23.0, 29.0), (3.75, 4.25)]);
+        assert!(view.contains([25.0, 4.0]));
+        assert!(!view.contains([25.0, 5.0]));
+        assert_eq!(
+            view.visible_segments(vec![[24.0, 4.0], [25.0, 4.0], [25.0, 5.0], [26.0, 4.0]]),
+            vec![vec![[24.0, 4.0], [25.0, 4.0]], vec![[26.0, 4.0]]],
+        );
+
+        // the view doesn't follow the data after it was changed by the user
+        view.fit(vec![[0.0, 0.0], [1.0, 1.0]]);
+        assert_eq!(view.ranges, [(23.0, 29.0), (3.75, 4.25)]);
+        view.reset();
+        view.fit(vec![[0.0, 0.0], [1.0, 1.0]]);
+        assert_relative_eq!(view.ranges[0].0, -0.1);
+        assert_relative_eq!(view.ranges[1].1, 1.1);
+    }
+}
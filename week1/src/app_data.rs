@@ -0,0 +1,9 @@
+use druid::*;
+use helpers::learning_settings::LearningSettingsData;
+use helpers::training_control::TrainingControlData;
+
+#[derive(Clone, Data, Lens, Debug)]
+pub struct AppData {
+    pub learning_settings: LearningSettingsData,
+    pub training_control: TrainingControlData,
+}
@@ -1,11 +1,53 @@
+use helpers::learning_settings::LearningSettings;
+
 pub const MAX_VALUE: f64 = 10.0;
 
-pub const LEARNING_RATE: f64 = 0.01;
-pub const GRADIENT_UPDATE_PERIOD: usize = 10;
-pub const GRADIENT_STEPS_PER_UPDATE: usize = 10;
-pub const DEFAULT_THETA0: f64 = 0.0;
-pub const DEFAULT_THETA1: f64 = 0.0;
+// the settings the learning starts with, they're changed in the UI
+pub const DEFAULT_LEARNING_SETTINGS: LearningSettings = LearningSettings {
+    learning_rate: 0.01,
+    steps_per_update: 10,
+    update_period: 10,
+    default_theta: 0.0,
+    use_normal_equation: false,
+};
+
+// online learning mode(`--online`): every example is used for one step of stochastic gradient descent and discarded
+pub const ONLINE_LEARNING_RATE: f64 = 0.01;
+// a new example comes from the stream every period(ms)
+pub const ONLINE_STREAM_PERIOD: u64 = 50;
+// the slope of the streamed examples goes back and forth with this period(in examples), so the model has to adapt
+pub const ONLINE_STREAM_DRIFT_PERIOD: f64 = 400.0;
+pub const ONLINE_STREAM_NOISE: f64 = 0.5;
+// the last examples are drawn, but they aren't used for training anymore
+pub const ONLINE_SHOWN_EXAMPLES: usize = 30;
 
 pub const REFRESH_PERIOD: usize = 10;
+// the window can be resized, so the size of the exported images is set separately
+pub const DEFAULT_EXPORT_WIDTH: f64 = 800.0;
+pub const DEFAULT_EXPORT_HEIGHT: f64 = 600.0;
 pub const UI_PLOT_TOP_RIGHT_MARGIN: i32 = 40;
-pub const UI_LEGEND_SIZE: i32 = 40;
\ No newline at end of file
+pub const UI_LEGEND_SIZE: i32 = 40;
+// the points closer to the mouse than this(px) can be dragged or deleted
+pub const POINT_PICK_DISTANCE: i32 = 6;
+
+// the cost panel next to the chart shows J(theta0, theta1) and the path of gradient descent
+pub const COST_PLOT_WIDTH: f64 = 350.0;
+pub const COST_PLOT_HEIGHT: f64 = 350.0;
+// the cost is sampled on a grid of COST_RESOLUTION x COST_RESOLUTION cells
+pub const COST_RESOLUTION: usize = 50;
+pub const COST_LEVELS: usize = 15;
+// the range of theta0 and theta1 shown while there's no trajectory
+pub const COST_DEFAULT_RANGE: (f64, f64) = (-10.0, 10.0);
+// the arrows on the trajectory are at least this far from each other(px)
+pub const TRAJECTORY_ARROW_SPACING: f64 = 40.0;
+pub const TRAJECTORY_ARROW_SIZE: f64 = 8.0;
+
+// the cost surface below the cost panel, it's rotated by dragging
+pub const COST_SURFACE_RESOLUTION: usize = 20;
+pub const COST_SURFACE_DEFAULT_PITCH: f64 = 0.45;
+pub const COST_SURFACE_DEFAULT_YAW: f64 = -0.90;
+pub const COST_SURFACE_SCALE: f64 = 0.7;
+
+// the chart takes the width left by the cost panels, the settings go below them
+pub const WINDOW_WIDTH: f64 = 1000.0;
+pub const WINDOW_HEIGHT: f64 = 2.0 * COST_PLOT_HEIGHT + 250.0;
\ No newline at end of file
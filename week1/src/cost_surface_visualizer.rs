@@ -0,0 +1,104 @@
+use druid::widget::prelude::*;
+use druid::{MouseEvent, Vec2};
+use std::error::Error;
+use std::sync::{Arc, Mutex};
+use plotters::prelude::*;
+use helpers::canvas::Drawer;
+use helpers::plotters::three_d::CostSurface;
+use helpers::session::Camera;
+use helpers::view::{zoom_factor, View};
+use crate::constants::*;
+use crate::demo_data::DemoData;
+use crate::gradient_descent::{cost, examples, optimal_theta};
+
+// CostSurfaceVisualizer shows the cost J(theta0, theta1) as a bowl shaped surface with the path gradient descent
+// took on it, dragging the chart rotates it and the wheel zooms it
+pub struct CostSurfaceVisualizer {
+    data: Arc<Mutex<DemoData>>,
+    // it's shared to be saved in the session
+    camera: Arc<Mutex<Camera>>,
+    // the ranges of theta0 and theta1, the cost axis fits the surface
+    view: Arc<Mutex<View<2>>>,
+}
+
+impl CostSurfaceVisualizer {
+    pub fn new(data: Arc<Mutex<DemoData>>, camera: Arc<Mutex<Camera>>) -> CostSurfaceVisualizer {
+        CostSurfaceVisualizer {
+            data,
+            camera,
+            view: Arc::new(Mutex::new(View::new([COST_DEFAULT_RANGE; 2]))),
+        }
+    }
+
+    // view is shared with the button resetting it
+    pub fn view(&self) -> Arc<Mutex<View<2>>> {
+        self.view.clone()
+    }
+}
+
+impl Drawer<()> for CostSurfaceVisualizer {
+    fn draw_demo_data(&self, buf: &mut [u8], width: usize, height: usize, _app_data: &()) -> Result<(), Box<dyn Error>> {
+        let root = BitMapBackend::with_buffer(
+            buf, (width as u32, height as u32),
+        ).into_drawing_area();
+
+        root.fill(&WHITE)?;
+
+        // the cost is sampled without holding the lock, so the learning thread doesn't wait for the drawing
+        let (points, trajectory) = {
+            let data = self.data.lock().unwrap();
+            (data.points.clone(), data.trajectory.clone())
+        };
+        if points.is_empty() {
+            return Ok(());
+        }
+        let trajectory: Vec<[f64; 2]> = trajectory.iter().map(|(theta0, theta1)| [*theta0, *theta1]).collect();
+        // the minimum stays in the middle of the chart
+        let optimum = optimal_theta(&points).map(|(best0, best1)| [best0, best1]);
+        let (xs, ys) = examples(&points);
+
+        let mut view = self.view.lock().unwrap();
+        let surface = CostSurface::sample(
+            |theta0, theta1| cost(theta0, theta1, &xs, &ys), &trajectory, optimum, &mut view, COST_SURFACE_RESOLUTION,
+        );
+
+        let mut chart = ChartBuilder::on(&root)
+            .caption("cost surface, drag to rotate", ("sans-serif", UI_PLOT_TOP_RIGHT_MARGIN / 2))
+            .margin(UI_PLOT_TOP_RIGHT_MARGIN / 4)
+            .build_cartesian_3d(view.range(0), surface.cost_range(), view.range(1))?;
+        chart.with_projection(|mut p| {
+            let camera = self.camera.lock().unwrap();
+            p.pitch = camera.pitch;
+            p.yaw = camera.yaw;
+            p.scale = COST_SURFACE_SCALE;
+            p.into_matrix()
+        });
+        chart.configure_axes().draw()?;
+
+        surface.draw(&mut chart, BLUE, RED)?;
+
+        Ok(())
+    }
+
+    fn get_size(&self) -> Size {
+        Size::new(COST_PLOT_WIDTH, COST_PLOT_HEIGHT)
+    }
+
+    fn handle_mouse_move(&mut self, ctx: &EventCtx, event: &MouseEvent, diff: Vec2, _app_data: &mut ()) {
+        if !event.buttons.has_left() {
+            return;
+        }
+
+        let size = ctx.size();
+        let mut camera = self.camera.lock().unwrap();
+        camera.yaw += diff.x / size.width;
+        camera.pitch -= diff.y / size.height;
+    }
+
+    fn handle_wheel(&mut self, _ctx: &EventCtx, event: &MouseEvent, _app_data: &mut ()) {
+        let mut view = self.view.lock().unwrap();
+        let center = view.center();
+
+        view.zoom(center, zoom_factor(event));
+    }
+}
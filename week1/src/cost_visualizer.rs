@@ -0,0 +1,240 @@
+use druid::widget::prelude::*;
+use druid::{MouseButton, MouseEvent, Vec2};
+use std::cell::RefCell;
+use std::error::Error;
+use std::sync::{Arc, Mutex};
+use plotters::coord::types::RangedCoordf64;
+use plotters::coord::{CoordTranslate, ReverseCoordTranslate};
+use plotters::prelude::*;
+use helpers::canvas::Drawer;
+use helpers::plotters::two_d::Grid;
+use helpers::view::{is_pan, pan_shift, zoom_factor, View};
+use crate::constants::*;
+use crate::demo_data::DemoData;
+use crate::gradient_descent::{cost, examples, optimal_theta};
+
+// CostVisualizer shows the cost J(theta0, theta1) as filled contours like in ex1 and the path gradient descent
+// took over them, click the chart to start gradient descent from that theta. The wheel zooms the chart
+// and dragging it with the middle button or with alt pans it
+pub struct CostVisualizer {
+    data: Arc<Mutex<DemoData>>,
+    // the coordinate system of the last drawn chart to convert clicks into theta
+    coord_spec: RefCell<Option<Cartesian2d<RangedCoordf64, RangedCoordf64>>>,
+    view: Arc<Mutex<View<2>>>,
+    is_panning: bool,
+}
+
+impl CostVisualizer {
+    pub fn new(data: Arc<Mutex<DemoData>>) -> CostVisualizer {
+        CostVisualizer {
+            data,
+            coord_spec: RefCell::new(None),
+            view: Arc::new(Mutex::new(View::new([COST_DEFAULT_RANGE; 2]))),
+            is_panning: false,
+        }
+    }
+
+    // view is shared with the button resetting it
+    pub fn view(&self) -> Arc<Mutex<View<2>>> {
+        self.view.clone()
+    }
+}
+
+impl Drawer<()> for CostVisualizer {
+    fn draw_demo_data(&self, buf: &mut [u8], width: usize, height: usize, _app_data: &()) -> Result<(), Box<dyn Error>> {
+        let root = BitMapBackend::with_buffer(
+            buf, (width as u32, height as u32),
+        ).into_drawing_area();
+
+        root.fill(&WHITE)?;
+
+        // the cost is sampled without holding the lock, so the learning thread doesn't wait for the drawing
+        let (points, trajectory) = {
+            let data = self.data.lock().unwrap();
+            (data.points.clone(), data.trajectory.clone())
+        };
+        let optimum = optimal_theta(&points);
+
+        let mut view = self.view.lock().unwrap();
+        // the trajectory is mirrored around the minimum to keep the minimum in the middle of the chart
+        view.fit(trajectory.iter().flat_map(|(theta0, theta1)| {
+            let mirrored = optimum.map(|(best0, best1)| [2.0 * best0 - theta0, 2.0 * best1 - theta1]);
+
+            std::iter::once([*theta0, *theta1]).chain(mirrored)
+        }));
+
+        let mut chart = ChartBuilder::on(&root)
+            .caption("cost, click to start from there", ("sans-serif", UI_PLOT_TOP_RIGHT_MARGIN / 2))
+            .margin(UI_PLOT_TOP_RIGHT_MARGIN / 4)
+            .x_label_area_size(UI_LEGEND_SIZE)
+            .y_label_area_size(UI_LEGEND_SIZE)
+            .build_cartesian_2d(view.range(0), view.range(1))?;
+
+        if !points.is_empty() {
+            let (xs, ys) = examples(&points);
+            let grid = Grid::sample(
+                |theta0, theta1| cost(theta0, theta1, &xs, &ys),
+                view.ranges[0],
+                view.ranges[1],
+                COST_RESOLUTION,
+            );
+            let levels = cost_levels(&grid);
+
+            // every cell is filled with the color of the band its cost is in
+            chart.draw_series(
+                (0..grid.resolution())
+                    .flat_map(|j| (0..grid.resolution()).map(move |i| (i, j)))
+                    .map(|(i, j)| {
+                        let value = (grid.value(i, j) + grid.value(i + 1, j)
+                            + grid.value(i, j + 1) + grid.value(i + 1, j + 1)) / 4.0;
+                        let band = levels.iter().filter(|level| value >= **level).count();
+
+                        Rectangle::new([grid.point(i, j), grid.point(i + 1, j + 1)], band_color(band).filled())
+                    }),
+            )?;
+
+            chart.draw_series(
+                levels
+                    .iter()
+                    .flat_map(|level| grid.contour(*level))
+                    .map(|segment| PathElement::new(segment.to_vec(), BLACK.mix(0.3))),
+            )?;
+        }
+
+        // the mesh is drawn over the contours
+        chart
+            .configure_mesh()
+            .disable_mesh()
+            .x_desc("theta0")
+            .y_desc("theta1")
+            .draw()?;
+
+        if let Some((best0, best1)) = optimum.filter(|(best0, best1)| view.contains([*best0, *best1])) {
+            chart.draw_series([Cross::new((best0, best1), 6, BLACK.stroke_width(2))])?;
+        }
+
+        for segment in view.visible_segments(trajectory.iter().map(|(theta0, theta1)| [*theta0, *theta1])) {
+            let segment: Vec<(f64, f64)> = segment.iter().map(|[theta0, theta1]| (*theta0, *theta1)).collect();
+            let arrows = trajectory_arrows(chart.as_coord_spec(), &segment);
+
+            chart.draw_series(LineSeries::new(segment.iter().copied(), RED.stroke_width(2)))?;
+            chart.draw_series(
+                arrows
+                    .into_iter()
+                    .map(|(theta, triangle)| EmptyElement::at(theta) + Polygon::new(triangle, RED.filled())),
+            )?;
+        }
+
+        // the current theta
+        if let Some((theta0, theta1)) = trajectory.last().copied().filter(|(x, y)| view.contains([*x, *y])) {
+            chart.draw_series([Circle::new((theta0, theta1), 4, RED.filled())])?;
+        }
+
+        *self.coord_spec.borrow_mut() = Some(chart.as_coord_spec().clone());
+
+        Ok(())
+    }
+
+    fn get_size(&self) -> Size {
+        Size::new(COST_PLOT_WIDTH, COST_PLOT_HEIGHT)
+    }
+
+    fn handle_mouse_move(&mut self, _ctx: &EventCtx, event: &MouseEvent, diff: Vec2, _app_data: &mut ()) {
+        // the canvas doesn't get the mouse up, so the panning stops when the buttons are released
+        if !self.is_panning || !(event.buttons.has_middle() || event.buttons.has_left()) {
+            return;
+        }
+
+        let shift = match self.coord_spec.borrow().as_ref() {
+            Some(coord_spec) => pan_shift(coord_spec, event.pos + diff, event.pos),
+            None => None,
+        };
+        if let Some(shift) = shift {
+            self.view.lock().unwrap().pan(shift);
+        }
+    }
+
+    fn handle_mouse_down(&mut self, _ctx: &EventCtx, event: &MouseEvent, _app_data: &mut ()) {
+        self.is_panning = is_pan(event);
+        if self.is_panning || event.button != MouseButton::Left {
+            return;
+        }
+
+        let theta = match self.coord_spec.borrow().as_ref() {
+            Some(coord_spec) => coord_spec.reverse_translate((event.pos.x as i32, event.pos.y as i32)),
+            None => None,
+        };
+        // the clicks outside of the chart are ignored
+        if let Some((theta0, theta1)) = theta {
+            self.data.lock().unwrap().restart_from(theta0, theta1);
+        }
+    }
+
+    fn handle_wheel(&mut self, _ctx: &EventCtx, event: &MouseEvent, _app_data: &mut ()) {
+        let mut view = self.view.lock().unwrap();
+        // the point under the mouse stays in place
+        let center = match self.coord_spec.borrow().as_ref() {
+            Some(coord_spec) => coord_spec.reverse_translate((event.pos.x as i32, event.pos.y as i32)),
+            None => None,
+        };
+        let center = center.map(|(x, y)| [x, y]).unwrap_or_else(|| view.center());
+
+        view.zoom(center, zoom_factor(event));
+    }
+}
+
+// cost_levels returns the levels of the contours between the lowest and the highest cost on the grid.
+// The cost is quadratic in theta, so the levels grow quadratically to make the contours evenly spaced
+fn cost_levels(grid: &Grid) -> Vec<f64> {
+    let (min, max) = grid.value_range();
+
+    (1..COST_LEVELS)
+        .map(|k| {
+            let t = k as f64 / COST_LEVELS as f64;
+            min + (max - min) * t * t
+        })
+        .collect()
+}
+
+// band_color goes from blue for the lowest cost to red for the highest one like the colormap in ex1
+fn band_color(band: usize) -> HSLColor {
+    let t = band as f64 / COST_LEVELS as f64;
+
+    HSLColor((1.0 - t) * 2.0 / 3.0, 0.7, 0.75)
+}
+
+// Arrow is the theta an arrow points at and its triangle in pixels relative to it
+type Arrow = ((f64, f64), Vec<(i32, i32)>);
+
+// trajectory_arrows returns the arrows along the trajectory,
+// every arrow points in the direction gradient descent went since the previous one
+fn trajectory_arrows(
+    coord_spec: &Cartesian2d<RangedCoordf64, RangedCoordf64>,
+    trajectory: &[(f64, f64)],
+) -> Vec<Arrow> {
+    let mut arrows = Vec::new();
+    let mut start = match trajectory.first() {
+        Some(theta) => coord_spec.translate(theta),
+        None => return arrows,
+    };
+
+    for theta in trajectory.iter().skip(1) {
+        let end = coord_spec.translate(theta);
+        let (dx, dy) = ((end.0 - start.0) as f64, (end.1 - start.1) as f64);
+        let length = dx.hypot(dy);
+        if length < TRAJECTORY_ARROW_SPACING {
+            continue;
+        }
+
+        // the direction scaled to the size of the arrow, the base of the arrow goes along the normal (-dy, dx)
+        let (dx, dy) = (dx / length * TRAJECTORY_ARROW_SIZE, dy / length * TRAJECTORY_ARROW_SIZE);
+        arrows.push((*theta, vec![
+            (0, 0),
+            ((-dx - dy / 2.0) as i32, (-dy + dx / 2.0) as i32),
+            ((-dx + dy / 2.0) as i32, (-dy - dx / 2.0) as i32),
+        ]));
+        start = end;
+    }
+
+    arrows
+}
@@ -1,16 +1,112 @@
-use crate::constants::*;
+use helpers::dataset::{save_examples, Dataset};
+use helpers::learning_settings::LearningSettings;
+use helpers::session::{Camera, ChartSession};
+use helpers::demo_data::push_to_trajectory;
+use std::error::Error;
+use std::path::Path;
+use std::sync::mpsc::Sender;
 
 #[derive(Debug)]
 pub struct DemoData {
     pub points: Vec<(f64, f64)>,
     pub theta0: f64,
     pub theta1: f64,
+    // the learning rate currently in effect, it's adjusted by the learning thread
+    pub learning_rate: f64,
+    // the settings chosen in the UI, the learning thread reads them on every iteration
+    pub settings: LearningSettings,
+    // the number of iterations since the learning started
+    pub iteration: usize,
+    // the values theta took since the learning started, they're drawn on the cost panel
+    pub trajectory: Vec<(f64, f64)>,
+    // in the online learning mode the new points are sent to the learning thread instead of being stored
+    pub online_examples: Option<Sender<(f64, f64)>>,
 }
 
 impl DemoData {
     pub fn add_point(&mut self, point: (f64, f64)) {
+        if let Some(online_examples) = &self.online_examples {
+            // the learning thread is gone only when the app is closing, so the point can be dropped
+            let _ = online_examples.send(point);
+            return;
+        }
+
         self.points.push(point);
-        self.theta0 = DEFAULT_THETA0;
-        self.theta1 = DEFAULT_THETA1;
+        self.reset_theta();
+    }
+
+    // move_point doesn't start the learning over, so the line follows the point while it's dragged.
+    // The points streamed in the online learning mode can't be edited
+    pub fn move_point(&mut self, i: usize, point: (f64, f64)) {
+        if self.online_examples.is_none() && i < self.points.len() {
+            self.points[i] = point;
+        }
+    }
+
+    pub fn remove_point(&mut self, i: usize) {
+        if self.online_examples.is_none() && i < self.points.len() {
+            self.points.remove(i);
+            self.reset_theta();
+        }
+    }
+
+    // reset_theta starts the learning over
+    pub fn reset_theta(&mut self) {
+        self.theta0 = self.settings.default_theta;
+        self.theta1 = self.settings.default_theta;
+        self.iteration = 0;
+        self.trajectory = vec![(self.theta0, self.theta1)];
+    }
+
+    // restart_from starts the learning over from the given theta instead of the default one
+    pub fn restart_from(&mut self, theta0: f64, theta1: f64) {
+        self.theta0 = theta0;
+        self.theta1 = theta1;
+        self.iteration = 0;
+        self.trajectory = vec![(theta0, theta1)];
+    }
+
+    pub fn record_theta(&mut self) {
+        push_to_trajectory(&mut self.trajectory, (self.theta0, self.theta1));
+    }
+
+    // set_points replaces the points with the examples of the dataset with 1 feature
+    pub fn set_points(&mut self, dataset: &Dataset) -> Result<(), Box<dyn Error>> {
+        let (xs, ys) = dataset.examples::<1>()?;
+        self.points = xs.iter().zip(ys).map(|(x, y)| (x[0], y)).collect();
+        self.reset_theta();
+
+        Ok(())
+    }
+
+    pub fn save_points(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        let xs: Vec<[f64; 1]> = self.points.iter().map(|(x, _)| [*x]).collect();
+        let ys: Vec<f64> = self.points.iter().map(|(_, y)| *y).collect();
+
+        save_examples(path, &xs, &ys)
+    }
+
+    pub fn to_session(&self, camera: Camera) -> ChartSession {
+        ChartSession {
+            xs: self.points.iter().map(|(x, _)| vec![*x]).collect(),
+            ys: self.points.iter().map(|(_, y)| *y).collect(),
+            theta: vec![self.theta0, self.theta1],
+            learning_rate: self.learning_rate,
+            settings: self.settings,
+            camera: Some(camera),
+        }
+    }
+
+    pub fn restore_session(&mut self, session: &ChartSession) -> Result<(), Box<dyn Error>> {
+        session.check(1)?;
+
+        self.points = session.xs.iter().zip(&session.ys).map(|(x, y)| (x[0], *y)).collect();
+        self.theta0 = session.theta[0];
+        self.theta1 = session.theta[1];
+        self.trajectory = vec![(self.theta0, self.theta1)];
+        self.learning_rate = session.learning_rate;
+        self.settings = session.settings;
+
+        Ok(())
     }
-}
\ No newline at end of file
+}
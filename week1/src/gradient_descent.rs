@@ -1,21 +1,75 @@
+use std::sync::mpsc::Receiver;
 use std::sync::{Arc, Mutex};
 use crate::demo_data::DemoData;
 use std::thread;
 use std::time::Duration;
 use crate::constants::*;
+use helpers::dataset::Examples;
+use helpers::learning_rate::AdaptiveLearningRate;
+use helpers::training_control::TrainingControl;
+
+pub fn learning_thread(data: Arc<Mutex<DemoData>>, mut control: TrainingControl) {
+    // a restored session continues with the learning rate it was saved with
+    let (mut learning_rate, mut settings) = {
+        let data = data.lock().unwrap();
+        (AdaptiveLearningRate::new(data.learning_rate), data.settings)
+    };
 
-pub fn learning_thread(data: Arc<Mutex<DemoData>>) {
     loop {
-        for _ in 0..GRADIENT_STEPS_PER_UPDATE {
+        let reset_requested = control.receive();
+        {
             let mut data = data.lock().unwrap();
-            let (new_theta0, new_theta1) = gradient_descent_step(
-                data.theta0, data.theta1, LEARNING_RATE, &data.points,
+            // the learning rate and theta start over when their initial values are changed in the UI
+            if reset_requested || data.settings.learning_rate != settings.learning_rate {
+                learning_rate = AdaptiveLearningRate::new(data.settings.learning_rate);
+                data.learning_rate = learning_rate.value();
+            }
+            if reset_requested || data.settings.default_theta != settings.default_theta {
+                data.reset_theta();
+            }
+            settings = data.settings;
+        }
+
+        for _ in 0..control.take_iterations(settings.steps_per_update) {
+            let mut data = data.lock().unwrap();
+            let points = &data.points;
+            // the cost is calculated several times per step, so the examples are converted once
+            let (xs, ys) = examples(points);
+            let [new_theta0, new_theta1] = learning_rate.step(
+                [data.theta0, data.theta1],
+                |[theta0, theta1]: [f64; 2]| cost(theta0, theta1, &xs, &ys),
+                |[theta0, theta1]: [f64; 2], rate| {
+                    let (theta0, theta1) = gradient_descent_step(theta0, theta1, rate, points);
+                    [theta0, theta1]
+                },
             );
             data.theta0 = new_theta0;
             data.theta1 = new_theta1;
+            data.learning_rate = learning_rate.value();
+            data.iteration += 1;
+            data.record_theta();
         }
 
-        thread::sleep(Duration::from_millis(GRADIENT_UPDATE_PERIOD as u64));
+        thread::sleep(control.period(Duration::from_millis(settings.update_period)));
+    }
+}
+
+// online_learning_thread takes one step of stochastic gradient descent per example received from the channel
+// and discards it, the last ONLINE_SHOWN_EXAMPLES examples are kept in data.points only to be drawn
+pub fn online_learning_thread(data: Arc<Mutex<DemoData>>, examples: Receiver<(f64, f64)>) {
+    for point in examples {
+        let mut data = data.lock().unwrap();
+
+        let (theta0, theta1) = gradient_descent_step(data.theta0, data.theta1, ONLINE_LEARNING_RATE, &[point]);
+        data.theta0 = theta0;
+        data.theta1 = theta1;
+        data.learning_rate = ONLINE_LEARNING_RATE;
+        data.record_theta();
+
+        data.points.push(point);
+        if data.points.len() > ONLINE_SHOWN_EXAMPLES {
+            data.points.remove(0);
+        }
     }
 }
 
@@ -38,3 +92,31 @@ fn gradient_descent_step(theta0: f64, theta1: f64, learning_rate: f64, points: &
 
     (theta0 - diff0, theta1 - diff1)
 }
+
+// examples converts the points to the examples used by the cost
+pub fn examples(points: &[(f64, f64)]) -> Examples<1> {
+    points.iter().map(|(x, y)| ([*x], *y)).unzip()
+}
+
+// cost calculates the squared error cost `1 / 2m * sum((h(x) - y) ^ 2)`
+pub fn cost(theta0: f64, theta1: f64, xs: &[[f64; 1]], ys: &[f64]) -> f64 {
+    helpers::math::linear_regression_cost::<1>([theta0, theta1], xs, ys)
+}
+
+// optimal_theta returns theta with the lowest cost by least squares,
+// there's no single one if there're less than 2 different x
+pub fn optimal_theta(points: &[(f64, f64)]) -> Option<(f64, f64)> {
+    let m = points.len() as f64;
+    let mean_x = points.iter().map(|(x, _)| x).sum::<f64>() / m;
+    let mean_y = points.iter().map(|(_, y)| y).sum::<f64>() / m;
+
+    let covariance: f64 = points.iter().map(|(x, y)| (x - mean_x) * (y - mean_y)).sum();
+    let variance: f64 = points.iter().map(|(x, _)| (x - mean_x) * (x - mean_x)).sum();
+    if variance <= 0.0 {
+        return None;
+    }
+
+    let theta1 = covariance / variance;
+
+    Some((mean_y - theta1 * mean_x, theta1))
+}
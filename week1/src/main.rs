@@ -1,37 +1,258 @@
 mod visualizer;
+mod cost_visualizer;
+mod cost_surface_visualizer;
+mod app_data;
 mod constants;
 mod demo_data;
 mod gradient_descent;
+mod settings_controller;
 
-use druid::{AppLauncher, LocalizedString, WindowDesc};
+use druid::widget::{Button, ControllerHost, Flex};
+use druid::{lens, AppLauncher, LocalizedString, Size, WidgetExt, WindowDesc};
+use helpers::learning_settings::{build_learning_settings_widget, LearningSettingsData};
+use helpers::canvas::{Canvas, Drawer};
+use helpers::dataset::{Dataset, LabelColumn};
+use helpers::export::{ExportArguments, EXPORT_USAGE};
+use helpers::session::{load_session, save_session, Camera, ChartSession};
+use helpers::training_control::{build_training_control_widget, control_channel, ControlCommand, TrainingControlData};
+use std::error::Error;
+use std::path::PathBuf;
+use std::process;
+use std::sync::mpsc::{self, Sender};
 use std::sync::{Arc, Mutex};
 use std::thread;
+use std::time::Duration;
 use visualizer::Visualizer;
+use cost_visualizer::CostVisualizer;
+use cost_surface_visualizer::CostSurfaceVisualizer;
+use crate::app_data::AppData;
 use crate::demo_data::DemoData;
-use crate::gradient_descent::learning_thread;
+use crate::settings_controller::SettingsController;
+use crate::gradient_descent::{learning_thread, online_learning_thread};
 use crate::constants::*;
+use helpers::online_learning::example_stream_thread;
+use rand::Rng;
 
+const USAGE: &str = "usage: linear-regression-demo [--online] [--data <ex1data1.txt>] \
+[--label-column <last|number|name>] [--save <points.txt>] [--session <session.json>] \
+[--save-session <session.json>]";
+
+struct Arguments {
+    online: bool,
+    // the comma separated file with the initial points like ex1data1.txt
+    data: Option<PathBuf>,
+    label_column: LabelColumn,
+    // the points are saved to the file when the window is closed
+    save: Option<PathBuf>,
+    // the session with the points, theta and the learning rate to start from
+    session: Option<PathBuf>,
+    // the session is saved to the file when the window is closed
+    save_session: Option<PathBuf>,
+}
 
 fn main() {
-    let data = Arc::new(Mutex::new(DemoData {
-        points: vec![(1.0, 1.0), (2.0, 2.0)],
-        theta0: DEFAULT_THETA0,
-        theta1: DEFAULT_THETA1,
-    }));
+    let (export, args) = match ExportArguments::parse(std::env::args().skip(1).collect()) {
+        Ok(arguments) => arguments,
+        Err(error) => {
+            eprintln!("{}\n{}\n{}", error, USAGE, EXPORT_USAGE);
+            process::exit(2);
+        }
+    };
+    let arguments = match parse_arguments(args) {
+        Ok(arguments) => arguments,
+        Err(error) => {
+            eprintln!("{}\n{}\n{}", error, USAGE, EXPORT_USAGE);
+            process::exit(2);
+        }
+    };
+
+    let mut demo_data = DemoData {
+        points: if arguments.online { Vec::new() } else { vec![(1.0, 1.0), (2.0, 2.0)] },
+        theta0: DEFAULT_LEARNING_SETTINGS.default_theta,
+        theta1: DEFAULT_LEARNING_SETTINGS.default_theta,
+        learning_rate: DEFAULT_LEARNING_SETTINGS.learning_rate,
+        settings: DEFAULT_LEARNING_SETTINGS,
+        iteration: 0,
+        trajectory: vec![(DEFAULT_LEARNING_SETTINGS.default_theta, DEFAULT_LEARNING_SETTINGS.default_theta)],
+        online_examples: None,
+    };
+    let mut camera = Camera {
+        pitch: COST_SURFACE_DEFAULT_PITCH,
+        yaw: COST_SURFACE_DEFAULT_YAW,
+    };
+    if let Some(path) = &arguments.session {
+        let result = load_session::<ChartSession>(path).and_then(|session| {
+            demo_data.restore_session(&session).map_err(|error| format!("{}: {}", path.display(), error))?;
+            camera = session.camera.unwrap_or(camera);
+            Ok(())
+        });
+        if let Err(error) = result {
+            eprintln!("error: {}", error);
+            process::exit(1);
+        }
+    }
+    if let Some(path) = &arguments.data {
+        let result = Dataset::load(path, &arguments.label_column).and_then(|dataset| demo_data.set_points(&dataset));
+        if let Err(error) = result {
+            eprintln!("error: {}", error);
+            process::exit(1);
+        }
+    }
+    let app_data = AppData {
+        learning_settings: LearningSettingsData::new(&demo_data.settings),
+        training_control: TrainingControlData::default(),
+    };
+    let data = Arc::new(Mutex::new(demo_data));
+    // the camera of the cost surface is saved in the session
+    let camera = Arc::new(Mutex::new(camera));
 
     let thread_data = data.clone();
-    thread::spawn(move || learning_thread(thread_data));
+    // the training is only controlled from the UI when it isn't online
+    let mut control_senders = Vec::new();
+    if arguments.online {
+        let (sender, receiver) = mpsc::channel();
+        data.lock().unwrap().online_examples = Some(sender.clone());
+
+        thread::spawn(move || online_learning_thread(thread_data, receiver));
+        thread::spawn(move || {
+            example_stream_thread(sender, Duration::from_millis(ONLINE_STREAM_PERIOD), generate_example)
+        });
+    } else {
+        let (sender, control) = control_channel();
+        control_senders.push(sender);
+
+        thread::spawn(move || learning_thread(thread_data, control));
+    }
 
-    let window = WindowDesc::new(get_ui_builder(data))
+    if let Some(export) = export {
+        let drawers: Vec<Box<dyn Drawer<()>>> = vec![
+            Box::new(Visualizer::new(data.clone())),
+            Box::new(CostVisualizer::new(data.clone())),
+            Box::new(CostSurfaceVisualizer::new(data, camera)),
+        ];
+        if let Err(error) = export.run(&drawers, &()) {
+            eprintln!("error: {}", error);
+            process::exit(1);
+        }
+        return;
+    }
+
+    let window = WindowDesc::new(get_ui_builder(data.clone(), camera.clone(), control_senders))
+        .window_size(Size::new(WINDOW_WIDTH, WINDOW_HEIGHT))
         .title(LocalizedString::new("Linear Regression Demo")
             .with_placeholder("linear-regression-demo"));
 
     AppLauncher::with_window(window)
         .use_simple_logger()
-        .launch(())
+        .launch(app_data)
         .expect("launch failed");
+
+    if let Some(path) = &arguments.save {
+        if let Err(error) = data.lock().unwrap().save_points(path) {
+            eprintln!("error: {}", error);
+            process::exit(1);
+        }
+    }
+    if let Some(path) = &arguments.save_session {
+        let camera = *camera.lock().unwrap();
+        if let Err(error) = save_session(path, &data.lock().unwrap().to_session(camera)) {
+            eprintln!("error: {}", error);
+            process::exit(1);
+        }
+    }
+}
+
+fn parse_arguments(args: Vec<String>) -> Result<Arguments, Box<dyn Error>> {
+    let mut online = false;
+    let mut data = None;
+    let mut label_column = LabelColumn::default();
+    let mut save = None;
+    let mut session = None;
+    let mut save_session = None;
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("{} requires a value", arg));
+
+        match arg.as_str() {
+            // in the online learning mode the examples are streamed to the learning thread and the clicks are added
+            // to the stream instead of restarting the training
+            "--online" => online = true,
+            "--data" => data = Some(PathBuf::from(value()?)),
+            "--label-column" => label_column = value()?.parse()?,
+            "--save" => save = Some(PathBuf::from(value()?)),
+            "--session" => session = Some(PathBuf::from(value()?)),
+            "--save-session" => save_session = Some(PathBuf::from(value()?)),
+            _ => return Err(format!("unexpected argument {}", arg).into()),
+        }
+    }
+
+    Ok(Arguments {
+        online,
+        data,
+        label_column,
+        save,
+        session,
+        save_session,
+    })
 }
 
-fn get_ui_builder(data: Arc<Mutex<DemoData>>) -> impl Fn() -> Visualizer {
-    move || Visualizer::new(data.clone())
+// generate_example generates a point near the line going through the center of the chart,
+// the slope of the line slowly goes back and forth
+fn generate_example(i: usize) -> (f64, f64) {
+    let mut rng = rand::thread_rng();
+    let slope = (2.0 * std::f64::consts::PI * i as f64 / ONLINE_STREAM_DRIFT_PERIOD).sin();
+    let center = MAX_VALUE / 2.0;
+
+    let x = rng.gen_range(0.0..MAX_VALUE);
+    let y = center + slope * (x - center) + rng.gen_range(-ONLINE_STREAM_NOISE..ONLINE_STREAM_NOISE);
+
+    (x, y.clamp(0.0, MAX_VALUE))
+}
+
+fn get_ui_builder(
+    data: Arc<Mutex<DemoData>>,
+    camera: Arc<Mutex<Camera>>,
+    control_senders: Vec<Sender<ControlCommand>>,
+) -> impl Fn() -> ControllerHost<Flex<AppData>, SettingsController> {
+    move || {
+        let visualizer = Visualizer::new(data.clone());
+        let cost_visualizer = CostVisualizer::new(data.clone());
+        let cost_surface_visualizer = CostSurfaceVisualizer::new(data.clone(), camera.clone());
+        let (view, cost_view, surface_view) = (
+            visualizer.view(), cost_visualizer.view(), cost_surface_visualizer.view(),
+        );
+        let surface_camera = camera.clone();
+        let refresh_period = Duration::from_millis(REFRESH_PERIOD as u64);
+
+        let mut column = Flex::<AppData>::column()
+            .with_flex_child(
+                Flex::row()
+                    .with_flex_child(visualizer, 1.0)
+                    .with_child(
+                        Flex::column()
+                            .with_child(Canvas::new(refresh_period, Box::new(cost_visualizer)))
+                            .with_child(Canvas::new(refresh_period, Box::new(cost_surface_visualizer))),
+                    )
+                    .lens(lens::Unit),
+                1.0,
+            )
+            .with_child(Button::new("Reset View").on_click(
+                move |_ctx, _app_data: &mut AppData, _env| {
+                    view.lock().unwrap().reset();
+                    cost_view.lock().unwrap().reset();
+                    surface_view.lock().unwrap().reset();
+                    *surface_camera.lock().unwrap() = Camera {
+                        pitch: COST_SURFACE_DEFAULT_PITCH,
+                        yaw: COST_SURFACE_DEFAULT_YAW,
+                    };
+                },
+            ))
+            .with_child(build_learning_settings_widget(false).lens(AppData::learning_settings));
+        if !control_senders.is_empty() {
+            column.add_child(build_training_control_widget(control_senders.clone()).lens(AppData::training_control));
+        }
+
+        column.controller(SettingsController::new(data.clone()))
+    }
 }
\ No newline at end of file
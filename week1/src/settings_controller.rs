@@ -0,0 +1,28 @@
+use druid::widget::prelude::*;
+use druid::widget::Controller;
+use druid::Data;
+use std::sync::{Arc, Mutex};
+use crate::app_data::AppData;
+use crate::demo_data::DemoData;
+
+// SettingsController passes the settings chosen in the UI to the learning thread
+pub struct SettingsController {
+    data: Arc<Mutex<DemoData>>,
+}
+
+impl SettingsController {
+    pub fn new(data: Arc<Mutex<DemoData>>) -> SettingsController {
+        SettingsController { data }
+    }
+}
+
+impl<W: Widget<AppData>> Controller<AppData, W> for SettingsController {
+    fn update(&mut self, child: &mut W, ctx: &mut UpdateCtx, old_data: &AppData, data: &AppData, env: &Env) {
+        if !old_data.same(data) {
+            let mut demo_data = self.data.lock().unwrap();
+            demo_data.settings = data.learning_settings.settings(&demo_data.settings);
+        }
+
+        child.update(ctx, old_data, data, env)
+    }
+}
@@ -1,16 +1,35 @@
 use druid::piet::{ImageFormat, InterpolationMode};
 use druid::widget::prelude::*;
 use druid::{Point, Rect, TimerToken};
+use std::cell::RefCell;
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
+use plotters::coord::types::RangedCoordf64;
+use plotters::coord::ReverseCoordTranslate;
 use plotters::prelude::*;
 use crate::demo_data::DemoData;
-use crate::constants::{REFRESH_PERIOD, MAX_VALUE, UI_PLOT_TOP_RIGHT_MARGIN, UI_LEGEND_SIZE};
+use crate::constants::{
+    REFRESH_PERIOD, MAX_VALUE, UI_PLOT_TOP_RIGHT_MARGIN, UI_LEGEND_SIZE, DEFAULT_EXPORT_WIDTH, DEFAULT_EXPORT_HEIGHT,
+    POINT_PICK_DISTANCE,
+};
+use helpers::canvas::Drawer;
+use helpers::plotters::two_d::{closest_point, line_inside_rect};
+use helpers::view::{is_pan, pan_shift, zoom_factor, View};
 use std::error::Error;
 
+// Visualizer draws the points and the line, click to add a point, drag a point to move it
+// and ctrl-click it to delete it. The wheel zooms the chart and dragging it with the middle button
+// or with alt pans it
 pub struct Visualizer {
     timer_id: TimerToken,
     data: Arc<Mutex<DemoData>>,
+    // the coordinate system of the last drawn chart to convert the mouse position into points
+    coord_spec: RefCell<Option<Cartesian2d<RangedCoordf64, RangedCoordf64>>>,
+    // the index of the point being dragged
+    dragged_point: Option<usize>,
+    view: Arc<Mutex<View<2>>>,
+    is_panning: bool,
+    last_mouse_position: Point,
 }
 
 impl Visualizer {
@@ -18,8 +37,18 @@ impl Visualizer {
         Visualizer {
             timer_id: TimerToken::INVALID,
             data,
+            coord_spec: RefCell::new(None),
+            dragged_point: None,
+            view: Arc::new(Mutex::new(View::new([(0.0, MAX_VALUE), (0.0, MAX_VALUE)]))),
+            is_panning: false,
+            last_mouse_position: Point::ORIGIN,
         }
     }
+
+    // view is shared with the button resetting it
+    pub fn view(&self) -> Arc<Mutex<View<2>>> {
+        self.view.clone()
+    }
 }
 
 impl Widget<()> for Visualizer {
@@ -35,24 +64,69 @@ impl Widget<()> for Visualizer {
                 }
             }
             Event::MouseDown(event) => {
-                // calculate the point inside the plot
-
-                let size = ctx.size();
-                let plot_size = Size::new(
-                    size.width - UI_LEGEND_SIZE as f64 - UI_PLOT_TOP_RIGHT_MARGIN as f64,
-                    size.height - UI_LEGEND_SIZE as f64 - UI_PLOT_TOP_RIGHT_MARGIN as f64,
-                );
-                let pos = Point::new(
-                    (event.pos.x - UI_LEGEND_SIZE as f64).clamp(0.0, plot_size.width),
-                    (event.pos.y - UI_LEGEND_SIZE as f64).clamp(0.0, plot_size.height),
-                );
-
-                let point = (
-                    (pos.x / plot_size.width * MAX_VALUE).clamp(0.0, MAX_VALUE),
-                    ((plot_size.height - pos.y) / plot_size.height * MAX_VALUE).clamp(0.0, MAX_VALUE),
-                );
-
-                self.data.lock().unwrap().add_point(point);
+                self.last_mouse_position = event.pos;
+                self.is_panning = is_pan(event);
+                if self.is_panning {
+                    ctx.set_active(true);
+                    return;
+                }
+
+                let coord_spec = self.coord_spec.borrow();
+                let coord_spec = match coord_spec.as_ref() {
+                    Some(coord_spec) => coord_spec,
+                    None => return,
+                };
+                let pixel = (event.pos.x as i32, event.pos.y as i32);
+                let mut data = self.data.lock().unwrap();
+
+                self.dragged_point = None;
+                match closest_point(coord_spec, data.points.iter().copied(), pixel, POINT_PICK_DISTANCE) {
+                    Some(i) if event.mods.ctrl() => data.remove_point(i),
+                    Some(i) => {
+                        self.dragged_point = Some(i);
+                        ctx.set_active(true);
+                    }
+                    // the clicks outside of the chart are ignored
+                    None => {
+                        if let Some(point) = coord_spec.reverse_translate(pixel) {
+                            data.add_point(point);
+                        }
+                    }
+                }
+            }
+            Event::MouseMove(event) => {
+                let coord_spec = self.coord_spec.borrow();
+                let coord_spec = match coord_spec.as_ref() {
+                    Some(coord_spec) => coord_spec,
+                    None => return,
+                };
+
+                if self.is_panning {
+                    if let Some(shift) = pan_shift(coord_spec, self.last_mouse_position, event.pos) {
+                        self.view.lock().unwrap().pan(shift);
+                    }
+                } else if let Some(i) = self.dragged_point {
+                    if let Some(point) = coord_spec.reverse_translate((event.pos.x as i32, event.pos.y as i32)) {
+                        self.data.lock().unwrap().move_point(i, point);
+                    }
+                }
+                self.last_mouse_position = event.pos;
+            }
+            Event::MouseUp(_) => {
+                self.dragged_point = None;
+                self.is_panning = false;
+                ctx.set_active(false);
+            }
+            Event::Wheel(event) => {
+                let mut view = self.view.lock().unwrap();
+                // the point under the mouse stays in place
+                let center = match self.coord_spec.borrow().as_ref() {
+                    Some(coord_spec) => coord_spec.reverse_translate((event.pos.x as i32, event.pos.y as i32)),
+                    None => None,
+                };
+                let center = center.map(|(x, y)| [x, y]).unwrap_or_else(|| view.center());
+
+                view.zoom(center, zoom_factor(event));
             }
             _ => (),
         }
@@ -88,7 +162,7 @@ impl Widget<()> for Visualizer {
         let mut buf = vec![0u8; width * height * 3];
 
         {
-            self.draw_demo_data(&mut buf, width, height).unwrap();
+            self.draw_demo_data(&mut buf, width, height, &()).unwrap();
         }
 
         let image = ctx
@@ -102,61 +176,63 @@ impl Widget<()> for Visualizer {
     }
 }
 
-impl Visualizer {
-    fn draw_demo_data(&self, buf: &mut [u8], width: usize, height: usize) -> Result<(), Box<dyn Error>> {
+// the window paints the visualizer itself, Drawer is implemented to export the chart by helpers::export
+impl Drawer<()> for Visualizer {
+    fn get_size(&self) -> Size {
+        Size::new(DEFAULT_EXPORT_WIDTH, DEFAULT_EXPORT_HEIGHT)
+    }
+
+    fn draw_demo_data(&self, buf: &mut [u8], width: usize, height: usize, _app_data: &()) -> Result<(), Box<dyn Error>> {
         let root = BitMapBackend::with_buffer(
             buf, (width as u32, height as u32),
         ).into_drawing_area();
 
         root.fill(&WHITE)?;
 
+        let data = self.data.lock().unwrap();
+
+        // show the learning rate currently in effect and the progress above the plot
+        root.draw(&Text::new(
+            format!("learning rate: {:.5}, iteration: {}", data.learning_rate, data.iteration),
+            (UI_LEGEND_SIZE, UI_PLOT_TOP_RIGHT_MARGIN / 2),
+            ("sans-serif", UI_PLOT_TOP_RIGHT_MARGIN / 2).into_font(),
+        ))?;
+
         let root = root.margin(
             UI_PLOT_TOP_RIGHT_MARGIN, 0, 0, UI_PLOT_TOP_RIGHT_MARGIN,
         );
+        let mut view = self.view.lock().unwrap();
+        view.fit(data.points.iter().map(|(x, y)| [*x, *y]));
+
         let mut plot_ctx = ChartBuilder::on(&root)
             .x_label_area_size(UI_LEGEND_SIZE)
             .y_label_area_size(UI_LEGEND_SIZE)
-            .build_cartesian_2d(0f64..MAX_VALUE, 0f64..MAX_VALUE)?;
+            .build_cartesian_2d(view.range(0), view.range(1))?;
 
         plot_ctx
             .configure_mesh()
             .draw()?;
 
-        let data = self.data.lock().unwrap();
-
+        // plotting library doesn't support values outside of drawing region
         plot_ctx.draw_series(
             data.points
                 .iter()
+                .filter(|(x, y)| view.contains([*x, *y]))
                 .map(|(x, y)| Circle::new((*x, *y), 2, GREEN.filled()))
         )?;
 
-        let theta0 = data.theta0;
-        let theta1 = data.theta1;
-
-        if theta1 != 0.0 {
-            // plotting library doesn't support values outside of drawing region
-            // so we need to do these tricks
-            let clamp_point = |point: (f64, f64)| -> (f64, f64){
-                if point.1 < 0.0 {
-                    return (-theta0 / theta1, 0.0);
-                } else if point.1 > MAX_VALUE {
-                    return ((MAX_VALUE - theta0) / theta1, MAX_VALUE);
-                }
-
-                point
-            };
-
-            let point1 = clamp_point((0.0, theta0));
-            let point2 = clamp_point((MAX_VALUE, theta0 + theta1 * MAX_VALUE));
-
+        // the line is `theta0 + theta1 * x - y = 0`
+        if let Some(line) = line_inside_rect([data.theta0, data.theta1, -1.0], 0.0, view.ranges[0], view.ranges[1]) {
             plot_ctx.draw_series(
                 LineSeries::new(
-                    vec![point1, point2],
+                    line.iter().copied(),
                     RED.filled(),
                 )
             )?;
         }
 
+        *self.coord_spec.borrow_mut() = Some(plot_ctx.as_coord_spec().clone());
+
         Ok(())
     }
 }
\ No newline at end of file
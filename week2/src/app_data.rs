@@ -1,20 +1,38 @@
 use druid::*;
-use crate::constants::*;
+use helpers::dataset::parse_number;
+use helpers::learning_settings::LearningSettingsData;
+use helpers::training_control::TrainingControlData;
 use std::error::Error;
+use std::sync::Arc;
 
 #[derive(Clone, Data, Lens, Debug)]
 pub struct AppData {
     pub new_point_x: String,
     pub new_point_y: String,
     pub new_point_z: String,
+    pub learning_settings: LearningSettingsData,
+    pub training_control: TrainingControlData,
 }
 
 impl AppData {
     pub fn parse_new_point(&self) -> Result<[f64; 3], Box<dyn Error>> {
-        let new_x = self.new_point_x.parse::<f64>()?.clamp(0.0, MAX_VALUE);
-        let new_z = self.new_point_z.parse::<f64>()?.clamp(0.0, MAX_VALUE);
-        let new_y = self.new_point_y.parse::<f64>()?.clamp(0.0, MAX_VALUE);
+        let new_x = parse_number(&self.new_point_x)?;
+        let new_z = parse_number(&self.new_point_z)?;
+        let new_y = parse_number(&self.new_point_y)?;
 
         Ok([new_x, new_z, new_y])
     }
-}
\ No newline at end of file
+}
+
+// AppDataNFeatures is the state of the UI for the models with more than 2 features,
+// two of the features are plotted and the rest are fixed at the values chosen with the sliders
+#[derive(Clone, Data, Lens, Debug)]
+pub struct AppDataNFeatures {
+    // the indexes of the features on the x and z axes
+    pub x_feature: usize,
+    pub z_feature: usize,
+    // the values of all the features, only the ones that aren't plotted are used
+    pub fixed_values: Arc<Vec<f64>>,
+    pub learning_settings: LearningSettingsData,
+    pub training_control: TrainingControlData,
+}
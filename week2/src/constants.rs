@@ -1,18 +1,42 @@
+use helpers::learning_settings::LearningSettings;
+
 pub const MAX_VALUE: f64 = 10.0;
 pub const DEFAULT_NUMBER_OF_POINTS: usize = 100;
 
-// by default we use gradient descent
-pub const USE_NORMAL_EQUATION: bool = false;
-pub const LEARNING_RATE: f64 = 0.01;
-pub const GRADIENT_UPDATE_PERIOD: usize = 10;
-pub const GRADIENT_STEPS_PER_UPDATE: usize = 10;
-pub const DEFAULT_THETA_VALUE: f64 = 0.0;
+// the settings the learning starts with, they're changed in the UI. By default we use gradient descent
+pub const DEFAULT_LEARNING_SETTINGS: LearningSettings = LearningSettings {
+    learning_rate: 0.01,
+    steps_per_update: 10,
+    update_period: 10,
+    default_theta: 0.0,
+    use_normal_equation: false,
+};
+
+// online learning mode(`--online`): every example is used for one step of stochastic gradient descent and discarded
+pub const ONLINE_LEARNING_RATE: f64 = 0.005;
+// a new example comes from the stream every period(ms)
+pub const ONLINE_STREAM_PERIOD: u64 = 50;
+// the slopes of the streamed examples go back and forth with this period(in examples), so the model has to adapt
+pub const ONLINE_STREAM_DRIFT_PERIOD: f64 = 400.0;
+pub const ONLINE_STREAM_NOISE: f64 = 0.5;
+// the last examples are drawn, but they aren't used for training anymore
+pub const ONLINE_SHOWN_EXAMPLES: usize = 50;
 
 pub const REFRESH_PERIOD: u64 = 10;
 pub const WINDOW_WIDTH: f64 = 800.0;
 pub const WINDOW_HEIGHT: f64 = 500.0;
+// the window of the models with more than 2 features has room for choosing the plotted features
+pub const N_FEATURES_WINDOW_HEIGHT: f64 = 700.0;
+// the models with up to this many features can be loaded, 2 features are shown by the chart with the new points
+pub const MAX_NUMBER_OF_FEATURES: usize = 5;
 pub const PLOT_DEFAULT_PITCH: f64 = 0.45;
 pub const PLOT_DEFAULT_YAW: f64 = -0.90;
 pub const FONT: (&str, i32) = ("sans-serif", 20);
+pub const SMALL_FONT: (&str, i32) = ("sans-serif", 15);
+pub const LEARNING_RATE_TEXT_MARGIN: i32 = 5;
 pub const DEFAULT_SCALE: f64 = 0.7;
 pub const POINT_SIZE: i32 = 2;
+// the prediction surface is drawn as a grid of SURFACE_RESOLUTION x SURFACE_RESOLUTION polygons
+pub const SURFACE_RESOLUTION: usize = 10;
+// the surface is sampled on a finer grid to pick a point on it by right click
+pub const PICK_RESOLUTION: usize = 100;
@@ -1,32 +1,147 @@
-use crate::constants::*;
-use crate::demo_data::DemoData;
+use helpers::demo_data::RegressionDemoDataNFeatures;
+use helpers::learning_rate::AdaptiveLearningRate;
+use helpers::training_control::TrainingControl;
 use nalgebra::{Dynamic, Matrix, VecStorage};
 use std::sync::{Arc, Mutex};
 use std::thread;
 use std::time::Duration;
 
-pub fn learning_thread(data: Arc<Mutex<DemoData>>) {
+// learning_thread trains the model on the examples with any number of features
+pub fn learning_thread<const N_FEATURES: usize>(
+    data: Arc<Mutex<RegressionDemoDataNFeatures<N_FEATURES>>>,
+    mut control: TrainingControl,
+) where [(); N_FEATURES + 1]: {
+    // a restored session continues with the learning rate it was saved with
+    let (mut learning_rate, mut settings) = {
+        let data = data.lock().unwrap();
+        (AdaptiveLearningRate::new(data.learning_rate), data.settings)
+    };
+
     loop {
-        if USE_NORMAL_EQUATION {
+        let reset_requested = control.receive();
+        {
             let mut data = data.lock().unwrap();
-            data.theta = normal_equation_solve(&data.xs, &data.ys);
+            // the learning rate and theta start over when their initial values are changed in the UI
+            if reset_requested || data.settings.learning_rate != settings.learning_rate {
+                learning_rate = AdaptiveLearningRate::new(data.settings.learning_rate);
+                data.learning_rate = learning_rate.value();
+            }
+            if reset_requested || data.settings.default_theta != settings.default_theta {
+                data.reset_theta();
+            }
+            settings = data.settings;
+        }
+
+        if settings.use_normal_equation {
+            let mut data = data.lock().unwrap();
+            // the normal equation needs at least one point, it's solved in one iteration
+            if !data.xs.is_empty() && control.take_iterations(1) > 0 {
+                data.theta = normal_equation_solve(&data.xs, &data.ys);
+                data.iteration += 1;
+                data.record_theta();
+            }
         } else {
-            for _ in 0..GRADIENT_STEPS_PER_UPDATE {
+            for _ in 0..control.take_iterations(settings.steps_per_update) {
                 let mut data = data.lock().unwrap();
-                data.theta = helpers::math::gradient_descent_step(
-                    data.theta, LEARNING_RATE, &data.xs, &data.ys, |x: [f64; 2]| {
-                        let mut result = 0.0;
+                // gradient descent needs at least one point too, all of them can be deleted in the UI
+                if data.xs.is_empty() {
+                    break;
+                }
+                gradient_descent_iteration(&mut data, &mut learning_rate);
+            }
+        }
 
-                        for (theta_i, theta_val) in data.theta.iter().enumerate() {
-                            result += theta_val * if theta_i > 0 { x[theta_i - 1] } else { 1.0 };
-                        }
+        thread::sleep(control.period(Duration::from_millis(settings.update_period)));
+    }
+}
 
-                        result
-                    });
-            }
+// gradient_descent_iteration makes one step on the normalized features like after featureNormalize in ex1_multi,
+// otherwise the features of different scales like the sizes and the bedrooms in ex1data2 need a learning rate
+// so small that the smaller feature barely moves. Theta is kept in the units of the original features,
+// the points can be added in the UI, so the means and the deviations are found again every step
+fn gradient_descent_iteration<const N_FEATURES: usize>(
+    data: &mut RegressionDemoDataNFeatures<N_FEATURES>,
+    learning_rate: &mut AdaptiveLearningRate,
+) where [(); N_FEATURES + 1]: {
+    let normalization = FeatureNormalization::new(&data.xs);
+    let xs = normalization.normalize_examples(&data.xs);
+    let ys = &data.ys;
+
+    let new_theta = learning_rate.step(
+        normalization.normalize_theta(data.theta),
+        |theta| helpers::math::linear_regression_cost(theta, &xs, ys),
+        |theta, rate| helpers::math::gradient_descent_step(
+            theta, rate, &xs, ys, |x: [f64; N_FEATURES]| {
+                helpers::math::linear_regression_predict(theta, x)
+            }),
+    );
+    data.theta = normalization.denormalize_theta(new_theta);
+    data.learning_rate = learning_rate.value();
+    data.iteration += 1;
+    data.record_theta();
+}
+
+// FeatureNormalization remembers the mean and the standard deviation of every feature
+struct FeatureNormalization<const N_FEATURES: usize> {
+    mu: [f64; N_FEATURES],
+    sigma: [f64; N_FEATURES],
+}
+
+impl<const N_FEATURES: usize> FeatureNormalization<N_FEATURES> where [(); N_FEATURES + 1]: {
+    fn new(xs: &[[f64; N_FEATURES]]) -> FeatureNormalization<N_FEATURES> {
+        let n = xs.len() as f64;
+        let mut mu = [0.0; N_FEATURES];
+        let mut sigma = [0.0; N_FEATURES];
+
+        for feature_i in 0..N_FEATURES {
+            mu[feature_i] = xs.iter().map(|x| x[feature_i]).sum::<f64>() / n;
+
+            let variance = xs
+                .iter()
+                .map(|x| (x[feature_i] - mu[feature_i]).powi(2))
+                .sum::<f64>() / (n - 1.0).max(1.0);
+
+            // a constant feature(or the only point) is just shifted
+            sigma[feature_i] = if variance > 0.0 { variance.sqrt() } else { 1.0 };
+        }
+
+        FeatureNormalization { mu, sigma }
+    }
+
+    fn normalize_examples(&self, xs: &[[f64; N_FEATURES]]) -> Vec<[f64; N_FEATURES]> {
+        xs.iter()
+            .map(|x| {
+                let mut normalized = *x;
+                for (i, feature) in normalized.iter_mut().enumerate() {
+                    *feature = (*feature - self.mu[i]) / self.sigma[i];
+                }
+                normalized
+            })
+            .collect()
+    }
+
+    // normalize_theta returns theta predicting the same values from the normalized features
+    fn normalize_theta(&self, theta: [f64; N_FEATURES + 1]) -> [f64; N_FEATURES + 1] {
+        let mut normalized = theta;
+
+        for i in 0..N_FEATURES {
+            normalized[0] += theta[i + 1] * self.mu[i];
+            normalized[i + 1] = theta[i + 1] * self.sigma[i];
         }
 
-        thread::sleep(Duration::from_millis(GRADIENT_UPDATE_PERIOD as u64));
+        normalized
+    }
+
+    // denormalize_theta is the reverse of normalize_theta
+    fn denormalize_theta(&self, normalized: [f64; N_FEATURES + 1]) -> [f64; N_FEATURES + 1] {
+        let mut theta = normalized;
+
+        for i in 0..N_FEATURES {
+            theta[i + 1] = normalized[i + 1] / self.sigma[i];
+            theta[0] -= theta[i + 1] * self.mu[i];
+        }
+
+        theta
     }
 }
 
@@ -68,3 +183,56 @@ fn normal_equation_solve<const N_FEATURES: usize>(
 
     result_array
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::learning::{gradient_descent_iteration, normal_equation_solve, FeatureNormalization};
+    use helpers::demo_data::RegressionDemoDataNFeatures;
+    use helpers::learning_rate::AdaptiveLearningRate;
+
+    #[test]
+    fn test_feature_normalization() {
+        let xs = [[2104.0, 3.0], [1600.0, 3.0], [2400.0, 4.0]];
+        let normalization = FeatureNormalization::new(&xs);
+        let theta = [1.0, 2.0, -3.0];
+
+        let normalized = normalization.normalize_theta(theta);
+        let denormalized = normalization.denormalize_theta(normalized);
+        for i in 0..theta.len() {
+            assert!((denormalized[i] - theta[i]).abs() < 1e-9);
+        }
+
+        // the predictions stay the same
+        for (x, normalized_x) in xs.iter().zip(normalization.normalize_examples(&xs)) {
+            let prediction = helpers::math::linear_regression_predict(theta, *x);
+            let normalized_prediction = helpers::math::linear_regression_predict(normalized, normalized_x);
+            assert!((prediction - normalized_prediction).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_gradient_descent_on_unscaled_features() {
+        // the first examples of ex1data2
+        let xs = vec![[2104.0, 3.0], [1600.0, 3.0], [2400.0, 3.0], [1416.0, 2.0], [3000.0, 4.0], [1985.0, 4.0]];
+        let ys = vec![399900.0, 329900.0, 369000.0, 232000.0, 539900.0, 299900.0];
+        let mut data = RegressionDemoDataNFeatures {
+            xs,
+            ys,
+            theta: [0.0; 3],
+            learning_rate: 0.01,
+            settings: crate::constants::DEFAULT_LEARNING_SETTINGS,
+            iteration: 0,
+            trajectory: Vec::new(),
+            online_examples: None,
+        };
+        let mut learning_rate = AdaptiveLearningRate::new(data.learning_rate);
+
+        for _ in 0..5000 {
+            gradient_descent_iteration(&mut data, &mut learning_rate);
+        }
+
+        let expected = normal_equation_solve(&data.xs, &data.ys);
+        let predict = |theta| helpers::math::linear_regression_predict(theta, [1650.0, 3.0]);
+        assert!((predict(data.theta) - predict(expected)).abs() < 1.0);
+    }
+}
@@ -5,29 +5,116 @@ mod app_data;
 mod constants;
 mod demo_data;
 mod learning;
+mod settings_controller;
 mod visualizer_2_features;
+mod visualizer_n_features;
 
-use crate::app_data::AppData;
+use crate::app_data::{AppData, AppDataNFeatures};
 use crate::constants::*;
 use crate::demo_data::DemoData;
 use crate::learning::learning_thread;
+use crate::settings_controller::SettingsController;
 use druid::widget::*;
 use druid::*;
+use helpers::math::linear_regression_predict;
+use helpers::online_learning::{example_stream_thread, online_learning_thread, Example};
+use helpers::dataset::{Dataset, LabelColumn};
+use helpers::demo_data::RegressionDemoDataNFeatures;
+use helpers::learning_settings::{build_learning_settings_widget, LearningSettingsData};
 use rand;
 use rand::Rng;
+use std::error::Error;
+use std::path::PathBuf;
+use std::process;
+use std::sync::mpsc::{self, Sender};
 use std::sync::{Arc, Mutex};
 use std::thread;
 use visualizer_2_features::Visualizer2Features;
-use helpers::canvas::Canvas;
+use visualizer_n_features::{feature_means, feature_ranges, VisualizerNFeatures};
+use helpers::canvas::{Canvas, Drawer};
+use helpers::export::{ExportArguments, EXPORT_USAGE};
+use helpers::session::{load_session, save_session, Camera, ChartSession};
+use helpers::training_control::{build_training_control_widget, control_channel, ControlCommand, TrainingControlData};
 use std::time::Duration;
 
+const USAGE: &str = "usage: linear-regression-demo [--online] [--data <ex1data2.txt>] \
+[--label-column <last|number|name>] [--save <points.txt>] [--session <session.json>] \
+[--save-session <session.json>]";
+
+struct Arguments {
+    online: bool,
+    // the comma separated file with the initial points like ex1data2.txt
+    data: Option<PathBuf>,
+    label_column: LabelColumn,
+    // the points are saved to the file when the window is closed
+    save: Option<PathBuf>,
+    // the session with the points, theta, the learning rate and the camera to start from
+    session: Option<PathBuf>,
+    // the session is saved to the file when the window is closed
+    save_session: Option<PathBuf>,
+}
+
 fn main() {
+    let (export, args) = match ExportArguments::parse(std::env::args().skip(1).collect()) {
+        Ok(arguments) => arguments,
+        Err(error) => {
+            eprintln!("{}\n{}\n{}", error, USAGE, EXPORT_USAGE);
+            process::exit(2);
+        }
+    };
+    let arguments = match parse_arguments(args) {
+        Ok(arguments) => arguments,
+        Err(error) => {
+            eprintln!("{}\n{}\n{}", error, USAGE, EXPORT_USAGE);
+            process::exit(2);
+        }
+    };
+
+    if let Err(error) = run(&arguments, export) {
+        eprintln!("error: {}", error);
+        process::exit(1);
+    }
+}
+
+// run opens the chart for the number of features in the dataset or in the session,
+// the generated points have 2 features
+fn run(arguments: &Arguments, export: Option<ExportArguments>) -> Result<(), Box<dyn Error>> {
+    let dataset = match &arguments.data {
+        Some(path) => Some(Dataset::load(path, &arguments.label_column)?),
+        None => None,
+    };
+    let session = match &arguments.session {
+        Some(path) => Some(load_session::<ChartSession>(path)?),
+        None => None,
+    };
+    let number_of_features = match (&dataset, &session) {
+        (Some(dataset), _) => dataset.number_of_features(),
+        (None, Some(session)) => session.theta.len().saturating_sub(1),
+        (None, None) => 2,
+    };
+
+    // the number of features is a part of the type of the data, so every supported one has its own arm
+    match number_of_features {
+        2 => run_2_features(arguments, export, dataset, session),
+        3 => run_n_features::<3>(arguments, export, dataset, session),
+        4 => run_n_features::<4>(arguments, export, dataset, session),
+        5 => run_n_features::<5>(arguments, export, dataset, session),
+        _ => Err(format!("expected 2 to {} features, got {}", MAX_NUMBER_OF_FEATURES, number_of_features).into()),
+    }
+}
+
+fn run_2_features(
+    arguments: &Arguments,
+    export: Option<ExportArguments>,
+    dataset: Option<Dataset>,
+    session: Option<ChartSession>,
+) -> Result<(), Box<dyn Error>> {
     let mut xs = Vec::new();
     let mut ys = Vec::new();
 
     let mut rng = rand::thread_rng();
     // generate a ray with a small random shifts for each point
-    for i in 0..DEFAULT_NUMBER_OF_POINTS {
+    for i in 0..if arguments.online { 0 } else { DEFAULT_NUMBER_OF_POINTS } {
         let i = i as f64 / DEFAULT_NUMBER_OF_POINTS as f64 * MAX_VALUE;
         let mut r = || rng.gen_range(-MAX_VALUE / 10.0..MAX_VALUE / 10.0);
 
@@ -35,27 +122,148 @@ fn main() {
         ys.push(i + r());
     }
 
-    let data = Arc::new(Mutex::new(DemoData {
-        xs,
-        ys,
-        theta: [
-            DEFAULT_THETA_VALUE,
-            DEFAULT_THETA_VALUE,
-            DEFAULT_THETA_VALUE,
-        ],
-    }));
-
+    let mut demo_data: DemoData = new_demo_data(xs, ys);
+    let mut camera = Camera {
+        pitch: PLOT_DEFAULT_PITCH,
+        yaw: PLOT_DEFAULT_YAW,
+    };
+    restore_examples(arguments, session.as_ref(), dataset.as_ref(), &mut demo_data, &mut camera)?;
     let app_data = AppData {
         new_point_x: String::new(),
         new_point_z: String::new(),
         new_point_y: String::new(),
+        learning_settings: LearningSettingsData::new(&demo_data.settings),
+        training_control: TrainingControlData::default(),
+    };
+
+    let data = Arc::new(Mutex::new(demo_data));
+    let camera = Arc::new(Mutex::new(camera));
+
+    let thread_data = data.clone();
+    // the training is only controlled from the UI when it isn't online
+    let mut control_senders = Vec::new();
+    if arguments.online {
+        let (sender, receiver) = mpsc::channel();
+        data.lock().unwrap().online_examples = Some(sender.clone());
+
+        thread::spawn(move || online_learning_thread(
+            thread_data, receiver, ONLINE_LEARNING_RATE, ONLINE_SHOWN_EXAMPLES, linear_regression_predict,
+        ));
+        thread::spawn(move || {
+            example_stream_thread(sender, Duration::from_millis(ONLINE_STREAM_PERIOD), generate_example)
+        });
+    } else {
+        let (sender, control) = control_channel();
+        control_senders.push(sender);
+
+        thread::spawn(move || learning_thread(thread_data, control));
+    }
+
+    if let Some(export) = export {
+        let drawers: Vec<Box<dyn Drawer<AppData>>> = vec![Box::new(Visualizer2Features::new(data, camera))];
+        return export.run(&drawers, &app_data);
+    }
+
+    launch(get_ui_builder(data.clone(), camera.clone(), control_senders), app_data, WINDOW_HEIGHT);
+
+    let data = data.lock().unwrap();
+    let camera = *camera.lock().unwrap();
+    save_results(arguments, &data, camera)
+}
+
+// run_n_features shows the models with more than 2 features, they can only be loaded from a dataset or a session
+fn run_n_features<const N_FEATURES: usize>(
+    arguments: &Arguments,
+    export: Option<ExportArguments>,
+    dataset: Option<Dataset>,
+    session: Option<ChartSession>,
+) -> Result<(), Box<dyn Error>> where [(); N_FEATURES + 1]: {
+    if arguments.online {
+        return Err("--online only works with 2 features".into());
+    }
+
+    let mut demo_data = new_demo_data::<N_FEATURES>(Vec::new(), Vec::new());
+    let mut camera = Camera {
+        pitch: PLOT_DEFAULT_PITCH,
+        yaw: PLOT_DEFAULT_YAW,
+    };
+    restore_examples(arguments, session.as_ref(), dataset.as_ref(), &mut demo_data, &mut camera)?;
+    // the sessions don't keep the names of the features
+    let feature_names = match dataset.and_then(|dataset| dataset.feature_names) {
+        Some(names) => names,
+        None => (1..=N_FEATURES).map(|i| format!("x{}", i)).collect(),
     };
+    let app_data = AppDataNFeatures {
+        x_feature: 0,
+        z_feature: 1,
+        fixed_values: Arc::new(feature_means(&demo_data.xs)),
+        learning_settings: LearningSettingsData::new(&demo_data.settings),
+        training_control: TrainingControlData::default(),
+    };
+
+    let data = Arc::new(Mutex::new(demo_data));
+    let camera = Arc::new(Mutex::new(camera));
 
     let thread_data = data.clone();
-    thread::spawn(move || learning_thread(thread_data));
+    let (sender, control) = control_channel();
+    thread::spawn(move || learning_thread(thread_data, control));
+
+    if let Some(export) = export {
+        let drawers: Vec<Box<dyn Drawer<AppDataNFeatures>>> = vec![
+            Box::new(VisualizerNFeatures::new(data, camera, feature_names)),
+        ];
+        return export.run(&drawers, &app_data);
+    }
+
+    launch(
+        get_n_features_ui_builder(data.clone(), camera.clone(), vec![sender], feature_names),
+        app_data,
+        N_FEATURES_WINDOW_HEIGHT,
+    );
+
+    let data = data.lock().unwrap();
+    let camera = *camera.lock().unwrap();
+    save_results(arguments, &data, camera)
+}
+
+fn new_demo_data<const N_FEATURES: usize>(
+    xs: Vec<[f64; N_FEATURES]>,
+    ys: Vec<f64>,
+) -> RegressionDemoDataNFeatures<N_FEATURES> where [(); N_FEATURES + 1]: {
+    RegressionDemoDataNFeatures {
+        xs,
+        ys,
+        theta: [DEFAULT_LEARNING_SETTINGS.default_theta; N_FEATURES + 1],
+        learning_rate: DEFAULT_LEARNING_SETTINGS.learning_rate,
+        settings: DEFAULT_LEARNING_SETTINGS,
+        iteration: 0,
+        trajectory: vec![[DEFAULT_LEARNING_SETTINGS.default_theta; N_FEATURES + 1]],
+        online_examples: None,
+    }
+}
 
-    let window = WindowDesc::new(get_ui_builder(data))
-        .window_size(Size::new(WINDOW_WIDTH, WINDOW_HEIGHT))
+// restore_examples starts from the session if there's one, the examples from the dataset replace its examples
+fn restore_examples<const N_FEATURES: usize>(
+    arguments: &Arguments,
+    session: Option<&ChartSession>,
+    dataset: Option<&Dataset>,
+    demo_data: &mut RegressionDemoDataNFeatures<N_FEATURES>,
+    camera: &mut Camera,
+) -> Result<(), Box<dyn Error>> where [(); N_FEATURES + 1]: {
+    if let (Some(path), Some(session)) = (&arguments.session, session) {
+        session.restore(demo_data).map_err(|error| format!("{}: {}", path.display(), error))?;
+        *camera = session.camera.unwrap_or(*camera);
+    }
+    if let Some(dataset) = dataset {
+        demo_data.set_examples(dataset)?;
+    }
+
+    Ok(())
+}
+
+fn launch<T: Data, W: Widget<T> + 'static>(ui_builder: impl Fn() -> W + 'static, app_data: T, window_height: f64) {
+    let window = WindowDesc::new(ui_builder)
+        .window_size(Size::new(WINDOW_WIDTH, window_height))
         .title(
             LocalizedString::new("Linear Regression Demo")
                 .with_placeholder("linear-regression-demo"),
@@ -67,14 +275,85 @@ fn main() {
         .expect("launch failed");
 }
 
-fn get_ui_builder(data: Arc<Mutex<DemoData>>) -> impl Fn() -> Flex<AppData> {
+// save_results saves the examples and the session when the window is closed
+fn save_results<const N_FEATURES: usize>(
+    arguments: &Arguments,
+    data: &RegressionDemoDataNFeatures<N_FEATURES>,
+    camera: Camera,
+) -> Result<(), Box<dyn Error>> where [(); N_FEATURES + 1]: {
+    if let Some(path) = &arguments.save {
+        data.save_examples(path)?;
+    }
+    if let Some(path) = &arguments.save_session {
+        save_session(path, &ChartSession::new(data, Some(camera)))?;
+    }
+
+    Ok(())
+}
+
+fn parse_arguments(args: Vec<String>) -> Result<Arguments, Box<dyn Error>> {
+    let mut online = false;
+    let mut data = None;
+    let mut label_column = LabelColumn::default();
+    let mut save = None;
+    let mut session = None;
+    let mut save_session = None;
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("{} requires a value", arg));
+
+        match arg.as_str() {
+            // in the online learning mode the examples are streamed to the learning thread one by one
+            "--online" => online = true,
+            "--data" => data = Some(PathBuf::from(value()?)),
+            "--label-column" => label_column = value()?.parse()?,
+            "--save" => save = Some(PathBuf::from(value()?)),
+            "--session" => session = Some(PathBuf::from(value()?)),
+            "--save-session" => save_session = Some(PathBuf::from(value()?)),
+            _ => return Err(format!("unexpected argument {}", arg).into()),
+        }
+    }
+
+    Ok(Arguments {
+        online,
+        data,
+        label_column,
+        save,
+        session,
+        save_session,
+    })
+}
+
+// generate_example generates a point near the plane going through the center of the chart,
+// the slopes of the plane slowly go back and forth
+fn generate_example(i: usize) -> Example<2> {
+    let mut rng = rand::thread_rng();
+    let phase = 2.0 * std::f64::consts::PI * i as f64 / ONLINE_STREAM_DRIFT_PERIOD;
+    let center = MAX_VALUE / 2.0;
+
+    let x = [rng.gen_range(0.0..MAX_VALUE), rng.gen_range(0.0..MAX_VALUE)];
+    let y = center
+        + phase.sin() * (x[0] - center)
+        + phase.cos() * (x[1] - center) / 2.0
+        + rng.gen_range(-ONLINE_STREAM_NOISE..ONLINE_STREAM_NOISE);
+
+    (x, y.clamp(0.0, MAX_VALUE))
+}
+
+fn get_ui_builder(
+    data: Arc<Mutex<DemoData>>,
+    camera: Arc<Mutex<Camera>>,
+    control_senders: Vec<Sender<ControlCommand>>,
+) -> impl Fn() -> Flex<AppData> {
     move || {
         let data_copy = data.clone();
-        Flex::<AppData>::column()
-            .with_child(Canvas::new(
-                Duration::from_millis(REFRESH_PERIOD),
-                Box::new(Visualizer2Features::new(data.clone())),
-            ))
+        let visualizer = Visualizer2Features::new(data.clone(), camera.clone());
+        let view = visualizer.view();
+        let reset_camera = camera.clone();
+
+        let mut column = Flex::<AppData>::column()
+            .with_child(Canvas::new(Duration::from_millis(REFRESH_PERIOD), Box::new(visualizer)))
             .with_child(
                 Flex::row()
                     .with_child(
@@ -98,10 +377,110 @@ fn get_ui_builder(data: Arc<Mutex<DemoData>>) -> impl Fn() -> Flex<AppData> {
                                 Ok(new_point) => {
                                     data_copy.lock().unwrap().add_point(new_point);
                                 }
-                                Err(_) => println!("invalid point"),
+                                Err(error) => println!("invalid point: {}", error),
                             }
                         },
+                    ))
+                    .with_child(Button::new("Reset View").on_click(
+                        move |_ctx: &mut EventCtx, _app_data: &mut AppData, _env: &Env| {
+                            view.lock().unwrap().reset();
+                            *reset_camera.lock().unwrap() = Camera {
+                                pitch: PLOT_DEFAULT_PITCH,
+                                yaw: PLOT_DEFAULT_YAW,
+                            };
+                        },
                     )),
             )
+            .with_child(
+                build_learning_settings_widget(true)
+                    .controller(SettingsController::new(data.clone()))
+                    .lens(AppData::learning_settings),
+            );
+        if !control_senders.is_empty() {
+            column.add_child(build_training_control_widget(control_senders.clone()).lens(AppData::training_control));
+        }
+
+        column
+    }
+}
+
+fn get_n_features_ui_builder<const N_FEATURES: usize>(
+    data: Arc<Mutex<RegressionDemoDataNFeatures<N_FEATURES>>>,
+    camera: Arc<Mutex<Camera>>,
+    control_senders: Vec<Sender<ControlCommand>>,
+    feature_names: Vec<String>,
+) -> impl Fn() -> Flex<AppDataNFeatures> where [(); N_FEATURES + 1]: {
+    move || {
+        let visualizer = VisualizerNFeatures::new(data.clone(), camera.clone(), feature_names.clone());
+        let view = visualizer.view();
+        let reset_camera = camera.clone();
+        let (means, ranges) = {
+            let data = data.lock().unwrap();
+            (feature_means(&data.xs), feature_ranges(&data.xs))
+        };
+
+        let feature_choice = |label: &str| {
+            Flex::column()
+                .with_child(Label::new(label))
+                .with_child(RadioGroup::new(
+                    feature_names.iter().enumerate().map(|(i, name)| (name.clone(), i)).collect::<Vec<_>>(),
+                ))
+        };
+
+        // the features that aren't plotted are fixed at the values of the sliders
+        let mut fixed_values = Flex::column();
+        for (i, (min, max)) in ranges.into_iter().enumerate() {
+            let name = feature_names[i].clone();
+            let value = lens::Map::new(
+                move |app_data: &AppDataNFeatures| app_data.fixed_values[i],
+                move |app_data: &mut AppDataNFeatures, value: f64| {
+                    // the values are copied only when they change, otherwise every event would repaint the chart
+                    if app_data.fixed_values[i] != value {
+                        Arc::make_mut(&mut app_data.fixed_values)[i] = value;
+                    }
+                },
+            );
+
+            fixed_values.add_child(
+                Flex::row()
+                    .with_child(Label::new(move |app_data: &AppDataNFeatures, _env: &Env| {
+                        format!("{}: {:.2}", name, app_data.fixed_values[i])
+                    }))
+                    .with_child(Slider::new().with_range(min, max).lens(value)),
+            );
+        }
+        fixed_values.add_child(
+            Flex::row()
+                .with_child(Button::new("Use Means").on_click(
+                    move |_ctx: &mut EventCtx, app_data: &mut AppDataNFeatures, _env: &Env| {
+                        app_data.fixed_values = Arc::new(means.clone());
+                    },
+                ))
+                .with_child(Button::new("Reset View").on_click(
+                    move |_ctx: &mut EventCtx, _app_data: &mut AppDataNFeatures, _env: &Env| {
+                        view.lock().unwrap().reset();
+                        *reset_camera.lock().unwrap() = Camera {
+                            pitch: PLOT_DEFAULT_PITCH,
+                            yaw: PLOT_DEFAULT_YAW,
+                        };
+                    },
+                )),
+        );
+
+        Flex::<AppDataNFeatures>::column()
+            .with_child(Canvas::new(Duration::from_millis(REFRESH_PERIOD), Box::new(visualizer)))
+            .with_child(
+                Flex::row()
+                    .cross_axis_alignment(CrossAxisAlignment::Start)
+                    .with_child(feature_choice("x axis").lens(AppDataNFeatures::x_feature))
+                    .with_child(feature_choice("z axis").lens(AppDataNFeatures::z_feature))
+                    .with_child(fixed_values),
+            )
+            .with_child(
+                build_learning_settings_widget(true)
+                    .controller(SettingsController::new(data.clone()))
+                    .lens(AppDataNFeatures::learning_settings),
+            )
+            .with_child(build_training_control_widget(control_senders.clone()).lens(AppDataNFeatures::training_control))
     }
 }
@@ -0,0 +1,36 @@
+use druid::widget::prelude::*;
+use druid::widget::Controller;
+use druid::Data;
+use std::sync::{Arc, Mutex};
+use helpers::demo_data::RegressionDemoDataNFeatures;
+use helpers::learning_settings::LearningSettingsData;
+
+// SettingsController passes the settings chosen in the UI to the learning thread
+pub struct SettingsController<const N_FEATURES: usize> where [(); N_FEATURES + 1]: {
+    data: Arc<Mutex<RegressionDemoDataNFeatures<N_FEATURES>>>,
+}
+
+impl<const N_FEATURES: usize> SettingsController<N_FEATURES> where [(); N_FEATURES + 1]: {
+    pub fn new(data: Arc<Mutex<RegressionDemoDataNFeatures<N_FEATURES>>>) -> SettingsController<N_FEATURES> {
+        SettingsController { data }
+    }
+}
+
+impl<const N_FEATURES: usize, W: Widget<LearningSettingsData>> Controller<LearningSettingsData, W>
+for SettingsController<N_FEATURES> where [(); N_FEATURES + 1]: {
+    fn update(
+        &mut self,
+        child: &mut W,
+        ctx: &mut UpdateCtx,
+        old_data: &LearningSettingsData,
+        data: &LearningSettingsData,
+        env: &Env,
+    ) {
+        if !old_data.same(data) {
+            let mut demo_data = self.data.lock().unwrap();
+            demo_data.settings = data.settings(&demo_data.settings);
+        }
+
+        child.update(ctx, old_data, data, env)
+    }
+}
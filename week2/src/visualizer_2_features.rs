@@ -1,31 +1,49 @@
 use druid::widget::prelude::*;
 use druid::{MouseEvent, Vec2};
+use std::cell::RefCell;
 use std::sync::{Arc, Mutex};
+use plotters::coord::cartesian::Cartesian3d;
+use plotters::coord::types::RangedCoordf64;
 use plotters::prelude::*;
 use crate::demo_data::DemoData;
 use crate::constants::*;
 use std::error::Error;
 use crate::app_data::AppData;
 use helpers::canvas::Drawer;
+use helpers::dataset::parse_number;
+use helpers::session::Camera;
+use helpers::view::{zoom_factor, View};
 
 const WIDGET_WIDTH: f64 = WINDOW_WIDTH;
 const WIDGET_HEIGHT: f64 = WIDGET_WIDTH / 2.0;
 
-// Visualizer2Features visualizes linear regression with 2 features
+// Visualizer2Features visualizes linear regression with 2 features,
+// right click picks the new point on the prediction surface and dragging with the right button changes its height.
+// The axes fit the data and the wheel zooms the chart
 pub struct Visualizer2Features {
     data: Arc<Mutex<DemoData>>,
-    pitch: f64,
-    yaw: f64,
+    // it's shared to be saved in the session
+    camera: Arc<Mutex<Camera>>,
+    // the projection of the last drawn chart to find the point of the surface under the mouse
+    coord_spec: RefCell<Option<Cartesian3d<RangedCoordf64, RangedCoordf64, RangedCoordf64>>>,
+    // the ranges of x, y and z, y is the vertical axis
+    view: Arc<Mutex<View<3>>>,
 }
 
 impl Visualizer2Features {
-    pub fn new(data: Arc<Mutex<DemoData>>) -> Visualizer2Features {
+    pub fn new(data: Arc<Mutex<DemoData>>, camera: Arc<Mutex<Camera>>) -> Visualizer2Features {
         Visualizer2Features {
             data,
-            pitch: PLOT_DEFAULT_PITCH,
-            yaw: PLOT_DEFAULT_YAW,
+            camera,
+            coord_spec: RefCell::new(None),
+            view: Arc::new(Mutex::new(View::new([(0.0, MAX_VALUE); 3]))),
         }
     }
+
+    // view is shared with the button resetting it
+    pub fn view(&self) -> Arc<Mutex<View<3>>> {
+        self.view.clone()
+    }
 }
 
 impl Drawer<AppData> for Visualizer2Features {
@@ -37,27 +55,45 @@ impl Drawer<AppData> for Visualizer2Features {
 
         root.fill(&WHITE)?;
 
+        let data = self.data.lock().unwrap();
+
+        root.draw(&Text::new(
+            if data.settings.use_normal_equation {
+                format!("normal equation, iteration: {}", data.iteration)
+            } else {
+                format!("learning rate: {:.5}, iteration: {}", data.learning_rate, data.iteration)
+            },
+            (LEARNING_RATE_TEXT_MARGIN, LEARNING_RATE_TEXT_MARGIN),
+            SMALL_FONT.into_font(),
+        ))?;
+
+        let mut view = self.view.lock().unwrap();
+        view.fit(data.xs.iter().zip(&data.ys).map(|(x, y)| [x[0], *y, x[1]]));
+
         let mut chart = ChartBuilder::on(&root)
-            .caption("Left click and drag to move camera", FONT)
-            .build_cartesian_3d(
-                0.0..MAX_VALUE, 0.0..MAX_VALUE, 0.0..MAX_VALUE,
-            )?;
+            .caption("Left click and drag to move camera, right click to pick a point", FONT)
+            .build_cartesian_3d(view.range(0), view.range(1), view.range(2))?;
         chart.with_projection(|mut p| {
-            p.pitch = self.pitch;
-            p.yaw = self.yaw;
+            let camera = self.camera.lock().unwrap();
+            p.pitch = camera.pitch;
+            p.yaw = camera.yaw;
             p.scale = DEFAULT_SCALE;
             p.into_matrix() // build the projection matrix
         });
         chart.configure_axes().draw()?;
 
-        let data = self.data.lock().unwrap();
         let theta = &data.theta;
-        let xs = &data.xs;
-        let ys = &data.ys;
+        // plotting library doesn't support values outside of drawing region
+        let (xs, ys): (Vec<[f64; 2]>, Vec<f64>) = data.xs
+            .iter()
+            .zip(&data.ys)
+            .filter(|(x, y)| view.contains([x[0], **y, x[1]]))
+            .map(|(x, y)| (*x, *y))
+            .unzip();
 
         // plot the points
         helpers::plotters::three_d::plot_points(
-            &mut chart, *theta, xs, ys, POINT_SIZE, |_, _| GREEN,
+            &mut chart, *theta, &xs, &ys, POINT_SIZE, |_, _| GREEN,
         )?;
 
         // plot the new point (if there's any)
@@ -70,7 +106,9 @@ impl Drawer<AppData> for Visualizer2Features {
         // plot a surface representing our prediction function
         helpers::plotters::three_d::plot_surface(&mut chart, |x: f64, z: f64| -> f64 {
             theta[0] + theta[1] * x + theta[2] * z
-        }, MAX_VALUE as usize, MAX_VALUE, BLUE)?;
+        }, SURFACE_RESOLUTION, view.ranges[0], view.ranges[2], BLUE)?;
+
+        *self.coord_spec.borrow_mut() = Some(chart.as_coord_spec().clone());
 
         Ok(())
     }
@@ -79,11 +117,51 @@ impl Drawer<AppData> for Visualizer2Features {
         Size::new(WIDGET_WIDTH, WIDGET_HEIGHT)
     }
 
-    fn handle_mouse_move(&mut self, ctx: &EventCtx, event: &MouseEvent, diff: Vec2) {
+    fn handle_mouse_move(&mut self, ctx: &EventCtx, event: &MouseEvent, diff: Vec2, app_data: &mut AppData) {
         let size = ctx.size();
         if event.buttons.has_left() {
-            self.yaw += diff.x / size.width;
-            self.pitch -= diff.y / size.height;
+            let mut camera = self.camera.lock().unwrap();
+            camera.yaw += diff.x / size.width;
+            camera.pitch -= diff.y / size.height;
+        } else if event.buttons.has_right() {
+            // moving the mouse up raises the new point
+            if let Ok(y) = parse_number(&app_data.new_point_y) {
+                let (min_y, max_y) = self.view.lock().unwrap().ranges[1];
+                app_data.new_point_y = format!("{:.2}", y + diff.y / size.height * (max_y - min_y));
+            }
+        }
+    }
+
+    fn handle_wheel(&mut self, _ctx: &EventCtx, event: &MouseEvent, _app_data: &mut AppData) {
+        let mut view = self.view.lock().unwrap();
+        let center = view.center();
+
+        view.zoom(center, zoom_factor(event));
+    }
+
+    fn handle_mouse_down(&mut self, _ctx: &EventCtx, event: &MouseEvent, app_data: &mut AppData) {
+        if !event.button.is_right() {
+            return;
+        }
+
+        let theta = self.data.lock().unwrap().theta;
+        let view = *self.view.lock().unwrap();
+        let point = match self.coord_spec.borrow().as_ref() {
+            Some(coord_spec) => helpers::plotters::three_d::pick_surface_point(
+                coord_spec,
+                |x, z| theta[0] + theta[1] * x + theta[2] * z,
+                PICK_RESOLUTION,
+                view.ranges[0],
+                view.ranges[2],
+                (event.pos.x as i32, event.pos.y as i32),
+            ),
+            None => None,
+        };
+
+        if let Some([x, z, y]) = point {
+            app_data.new_point_x = format!("{:.2}", x);
+            app_data.new_point_z = format!("{:.2}", z);
+            app_data.new_point_y = format!("{:.2}", y);
         }
     }
 }
@@ -0,0 +1,223 @@
+use druid::widget::prelude::*;
+use druid::{MouseEvent, Vec2};
+use std::sync::{Arc, Mutex};
+use plotters::prelude::*;
+use crate::app_data::AppDataNFeatures;
+use crate::constants::*;
+use std::error::Error;
+use helpers::canvas::Drawer;
+use helpers::demo_data::RegressionDemoDataNFeatures;
+use helpers::session::Camera;
+use helpers::view::{zoom_factor, View};
+
+const WIDGET_WIDTH: f64 = WINDOW_WIDTH;
+const WIDGET_HEIGHT: f64 = WIDGET_WIDTH / 2.0;
+
+// VisualizerNFeatures visualizes linear regression with any number of features,
+// the examples are projected on the two features chosen in the UI and the prediction surface goes over them
+// with the other features fixed. Dragging the chart moves the camera and the wheel zooms it
+pub struct VisualizerNFeatures<const N_FEATURES: usize> where [(); N_FEATURES + 1]: {
+    data: Arc<Mutex<RegressionDemoDataNFeatures<N_FEATURES>>>,
+    // it's shared to be saved in the session
+    camera: Arc<Mutex<Camera>>,
+    feature_names: Vec<String>,
+    // the ranges of x, y and z, y is the vertical axis
+    view: Arc<Mutex<View<3>>>,
+    // the features the view was fitted to, the zoom of the other features doesn't fit the new ones
+    plotted_features: Mutex<(usize, usize)>,
+}
+
+impl<const N_FEATURES: usize> VisualizerNFeatures<N_FEATURES> where [(); N_FEATURES + 1]: {
+    pub fn new(
+        data: Arc<Mutex<RegressionDemoDataNFeatures<N_FEATURES>>>,
+        camera: Arc<Mutex<Camera>>,
+        feature_names: Vec<String>,
+    ) -> VisualizerNFeatures<N_FEATURES> {
+        VisualizerNFeatures {
+            data,
+            camera,
+            feature_names,
+            view: Arc::new(Mutex::new(View::new([(0.0, MAX_VALUE); 3]))),
+            plotted_features: Mutex::new((0, 1)),
+        }
+    }
+
+    // view is shared with the button resetting it
+    pub fn view(&self) -> Arc<Mutex<View<3>>> {
+        self.view.clone()
+    }
+}
+
+impl<const N_FEATURES: usize> Drawer<AppDataNFeatures> for VisualizerNFeatures<N_FEATURES>
+    where [(); N_FEATURES + 1]: {
+    fn draw_demo_data(&self, buf: &mut [u8], width: usize, height: usize, app_data: &AppDataNFeatures)
+                      -> Result<(), Box<dyn Error>> {
+        let root = BitMapBackend::with_buffer(
+            buf, (width as u32, height as u32),
+        ).into_drawing_area();
+
+        root.fill(&WHITE)?;
+
+        let data = self.data.lock().unwrap();
+
+        root.draw(&Text::new(
+            if data.settings.use_normal_equation {
+                format!("normal equation, iteration: {}", data.iteration)
+            } else {
+                format!("learning rate: {:.5}, iteration: {}", data.learning_rate, data.iteration)
+            },
+            (LEARNING_RATE_TEXT_MARGIN, LEARNING_RATE_TEXT_MARGIN),
+            SMALL_FONT.into_font(),
+        ))?;
+
+        let (x_feature, z_feature) = (app_data.x_feature, app_data.z_feature);
+        if x_feature == z_feature || x_feature >= N_FEATURES || z_feature >= N_FEATURES {
+            root.draw(&Text::new(
+                "pick two different features to plot",
+                (width as i32 / 3, height as i32 / 2),
+                FONT.into_font(),
+            ))?;
+            return Ok(());
+        }
+
+        let points: Vec<[f64; 3]> = data.xs
+            .iter()
+            .zip(&data.ys)
+            .map(|(x, y)| [x[x_feature], *y, x[z_feature]])
+            .collect();
+        let mut view = self.view.lock().unwrap();
+        let mut plotted_features = self.plotted_features.lock().unwrap();
+        if *plotted_features != (x_feature, z_feature) {
+            *plotted_features = (x_feature, z_feature);
+            view.reset();
+        }
+        view.fit(points.iter().copied());
+
+        let mut chart = ChartBuilder::on(&root)
+            .caption(
+                format!(
+                    "x: {}, z: {}, drag to move camera",
+                    self.feature_names[x_feature],
+                    self.feature_names[z_feature],
+                ),
+                FONT,
+            )
+            .build_cartesian_3d(view.range(0), view.range(1), view.range(2))?;
+        chart.with_projection(|mut p| {
+            let camera = self.camera.lock().unwrap();
+            p.pitch = camera.pitch;
+            p.yaw = camera.yaw;
+            p.scale = DEFAULT_SCALE;
+            p.into_matrix() // build the projection matrix
+        });
+        chart.configure_axes().draw()?;
+
+        // plotting library doesn't support values outside of drawing region
+        chart.draw_series(
+            points
+                .into_iter()
+                .filter(|point| view.contains(*point))
+                .map(|[x, y, z]| Circle::new((x, y, z), POINT_SIZE, GREEN.filled())),
+        )?;
+
+        // plot a surface representing our prediction function,
+        // the features that aren't plotted keep the values from the sliders
+        let theta = &data.theta;
+        helpers::plotters::three_d::plot_surface(&mut chart, |x: f64, z: f64| -> f64 {
+            let mut features = [0.0; N_FEATURES];
+            for (feature, value) in features.iter_mut().zip(app_data.fixed_values.iter()) {
+                *feature = *value;
+            }
+            features[x_feature] = x;
+            features[z_feature] = z;
+
+            helpers::math::linear_regression_predict(*theta, features)
+        }, SURFACE_RESOLUTION, view.ranges[0], view.ranges[2], BLUE)?;
+
+        Ok(())
+    }
+
+    fn get_size(&self) -> Size {
+        Size::new(WIDGET_WIDTH, WIDGET_HEIGHT)
+    }
+
+    fn handle_mouse_move(
+        &mut self, ctx: &EventCtx, event: &MouseEvent, diff: Vec2, _app_data: &mut AppDataNFeatures,
+    ) {
+        if !event.buttons.has_left() {
+            return;
+        }
+
+        let size = ctx.size();
+        let mut camera = self.camera.lock().unwrap();
+        camera.yaw += diff.x / size.width;
+        camera.pitch -= diff.y / size.height;
+    }
+
+    fn handle_wheel(&mut self, _ctx: &EventCtx, event: &MouseEvent, _app_data: &mut AppDataNFeatures) {
+        let mut view = self.view.lock().unwrap();
+        let center = view.center();
+
+        view.zoom(center, zoom_factor(event));
+    }
+}
+
+// feature_means returns the mean of every feature, the features that aren't plotted start at them
+pub fn feature_means<const N_FEATURES: usize>(xs: &[[f64; N_FEATURES]]) -> Vec<f64> {
+    let mut means = vec![0.0; N_FEATURES];
+    if xs.is_empty() {
+        return means;
+    }
+
+    for x in xs {
+        for (mean, value) in means.iter_mut().zip(x) {
+            *mean += value;
+        }
+    }
+    for mean in &mut means {
+        *mean /= xs.len() as f64;
+    }
+
+    means
+}
+
+// feature_ranges returns the smallest and the largest value of every feature for the sliders,
+// a feature with the same value everywhere gets a range of 1 around it
+pub fn feature_ranges<const N_FEATURES: usize>(xs: &[[f64; N_FEATURES]]) -> Vec<(f64, f64)> {
+    let mut ranges = vec![(f64::INFINITY, f64::NEG_INFINITY); N_FEATURES];
+    for x in xs {
+        for ((min, max), value) in ranges.iter_mut().zip(x) {
+            *min = min.min(*value);
+            *max = max.max(*value);
+        }
+    }
+
+    ranges
+        .into_iter()
+        .map(|(min, max)| if min < max {
+            (min, max)
+        } else if min == max {
+            (min - 0.5, max + 0.5)
+        } else {
+            (0.0, MAX_VALUE)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::constants::MAX_VALUE;
+    use crate::visualizer_n_features::{feature_means, feature_ranges};
+
+    #[test]
+    fn test_feature_means_and_ranges() {
+        let xs = [[1.0, 5.0, -2.0], [3.0, 5.0, 0.0], [2.0, 5.0, 8.0]];
+        assert_eq!(feature_means(&xs), vec![2.0, 5.0, 2.0]);
+        // the second feature is constant
+        assert_eq!(feature_ranges(&xs), vec![(1.0, 3.0), (4.5, 5.5), (-2.0, 8.0)]);
+
+        let xs: [[f64; 2]; 0] = [];
+        assert_eq!(feature_means(&xs), vec![0.0, 0.0]);
+        assert_eq!(feature_ranges(&xs), vec![(0.0, MAX_VALUE), (0.0, MAX_VALUE)]);
+    }
+}
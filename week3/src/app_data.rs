@@ -1,5 +1,7 @@
 use druid::*;
-use crate::constants::*;
+use helpers::dataset::parse_number;
+use helpers::learning_settings::LearningSettingsData;
+use helpers::training_control::TrainingControlData;
 use std::error::Error;
 
 #[derive(Clone, Data, Lens, Debug)]
@@ -9,20 +11,32 @@ pub struct AppData {
     pub two_features_new_point_x: String,
     pub two_features_new_point_z: String,
     pub two_features_new_point_y: String,
+    pub learning_settings: LearningSettingsData,
+    pub training_control: TrainingControlData,
 }
 
 impl AppData {
     pub fn parse_one_feature_new_point(&self) -> Result<[f64; 2], Box<dyn Error>> {
-        let new_x = self.one_feature_new_point_x.parse::<f64>()?.clamp(0.0, MAX_VALUE);
-        let new_y = self.one_feature_new_point_y.parse::<f64>()?.clamp(0.0, MAX_Y_VALUE);
+        let new_x = parse_number(&self.one_feature_new_point_x)?;
+        let new_y = parse_label(&self.one_feature_new_point_y)?;
 
         Ok([new_x, new_y])
     }
     pub fn parse_two_features_new_point(&self) -> Result<[f64; 3], Box<dyn Error>> {
-        let new_x = self.two_features_new_point_x.parse::<f64>()?.clamp(0.0, MAX_VALUE);
-        let new_z = self.two_features_new_point_z.parse::<f64>()?.clamp(0.0, MAX_VALUE);
-        let new_y = self.two_features_new_point_y.parse::<f64>()?.clamp(0.0, MAX_Y_VALUE);
+        let new_x = parse_number(&self.two_features_new_point_x)?;
+        let new_z = parse_number(&self.two_features_new_point_z)?;
+        let new_y = parse_label(&self.two_features_new_point_y)?;
 
         Ok([new_x, new_z, new_y])
     }
+}
+
+// parse_label accepts only the classes of logistic regression like the check of the labels from --data
+fn parse_label(field: &str) -> Result<f64, Box<dyn Error>> {
+    let label = parse_number(field)?;
+    if label != 0.0 && label != 1.0 {
+        return Err(format!("the label is {}, expected 0 or 1", label).into());
+    }
+
+    Ok(label)
 }
\ No newline at end of file
@@ -1,13 +1,31 @@
-use plotters::prelude::{GREEN, RGBColor};
+use helpers::learning_settings::LearningSettings;
+use plotters::prelude::{BLACK, GREEN, MAGENTA, RGBColor};
 
 pub const MAX_VALUE: f64 = 10.0;
 // since it's logistic regression, Y should be between 0 and 1
 pub const MAX_Y_VALUE: f64 = 1.0;
 
-pub const LEARNING_RATE: f64 = 0.01;
-pub const GRADIENT_UPDATE_PERIOD: usize = 10;
-pub const GRADIENT_STEPS_PER_UPDATE: usize = 10;
-pub const DEFAULT_THETA_VALUE: f64 = 0.0;
+// the settings the learning starts with, they're changed in the UI for both charts at once
+pub const DEFAULT_LEARNING_SETTINGS: LearningSettings = LearningSettings {
+    learning_rate: 0.01,
+    steps_per_update: 10,
+    update_period: 10,
+    default_theta: 0.0,
+    use_normal_equation: false,
+};
+
+// online learning mode(`--online`): every example is used for one step of stochastic gradient descent and discarded
+pub const ONLINE_LEARNING_RATE: f64 = 0.05;
+// a new example comes from the stream every period(ms)
+pub const ONLINE_STREAM_PERIOD: u64 = 50;
+// the decision boundary of the streamed examples moves back and forth with this period(in examples)
+pub const ONLINE_STREAM_DRIFT_PERIOD: f64 = 400.0;
+// the last examples are drawn, but they aren't used for training anymore
+pub const ONLINE_SHOWN_EXAMPLES: usize = 50;
+
+// linear SVM is trained on the same data as logistic regression with 2 features
+pub const SHOW_SVM: bool = true;
+pub const SVM_C: f64 = 1.0;
 
 pub const REFRESH_PERIOD: u64 = 10;
 pub const WINDOW_WIDTH: f64 = 800.0;
@@ -15,11 +33,25 @@ pub const WINDOW_HEIGHT: f64 = 800.0;
 pub const PLOT_DEFAULT_PITCH: f64 = 0.45;
 pub const PLOT_DEFAULT_YAW: f64 = -0.90;
 pub const POINT_SIZE: i32 = 2;
+// the points closer to the mouse than this(px) can be dragged, deleted or relabeled
+pub const POINT_PICK_DISTANCE: i32 = 6;
+pub const ONE_FEATURE_VISUALIZER_WIDTH: f64 = WINDOW_WIDTH * 2.0 / 3.0;
 pub const ONE_FEATURE_VISUALIZER_HEIGHT: f64 = WINDOW_WIDTH / 3.0;
+// the cost surface of the model with 1 feature is next to its chart
+pub const COST_SURFACE_WIDTH: f64 = WINDOW_WIDTH - ONE_FEATURE_VISUALIZER_WIDTH;
+// the range of theta0 and theta1 shown while there's no trajectory
+pub const COST_DEFAULT_RANGE: (f64, f64) = (-10.0, 10.0);
+// the labels on the axes of the cost surface, there's not much space for them
+pub const COST_SURFACE_LABELS: usize = 4;
 pub const NEW_POINT_COLOR: RGBColor = GREEN;
 pub const FONT: (&str, i32) = ("sans-serif", 20);
+pub const SMALL_FONT: (&str, i32) = ("sans-serif", 15);
 pub const CHART_MARGIN: i32 = 5;
 pub const LEGEND_SIZE: i32 = 30;
-pub const SIGMOID_PLOTTING_PRECISION: f64 = 10.0;
+pub const SIGMOID_RESOLUTION: usize = 100;
 pub const DEFAULT_SCALE: f64 = 0.7;
-pub const SURFACE_PRECISION: f64 = 2.0;
+// the prediction surface is drawn as a grid of SURFACE_RESOLUTION x SURFACE_RESOLUTION polygons
+pub const SURFACE_RESOLUTION: usize = 20;
+pub const LOGISTIC_REGRESSION_BOUNDARY_COLOR: RGBColor = BLACK;
+pub const SVM_COLOR: RGBColor = MAGENTA;
+pub const SMALL_LEGEND_FONT: (&str, i32) = ("sans-serif", 12);
@@ -0,0 +1,120 @@
+use druid::widget::prelude::*;
+use druid::{MouseEvent, Vec2};
+use std::error::Error;
+use std::sync::{Arc, Mutex};
+use plotters::prelude::*;
+use crate::app_data::AppData;
+use crate::constants::*;
+use crate::demo_data::DemoData1Feature;
+use helpers::canvas::Drawer;
+use helpers::plotters::three_d::CostSurface;
+use helpers::session::Camera;
+use helpers::view::{zoom_factor, View};
+
+// CostSurfaceVisualizer shows the cross entropy cost of logistic regression with 1 feature over theta0 and theta1
+// with the path gradient descent took on it, dragging the chart rotates it and the wheel zooms it
+pub struct CostSurfaceVisualizer {
+    data: Arc<Mutex<DemoData1Feature>>,
+    camera: Arc<Mutex<Camera>>,
+    // the ranges of theta0 and theta1, the cost axis fits the surface
+    view: Arc<Mutex<View<2>>>,
+}
+
+impl CostSurfaceVisualizer {
+    pub fn new(data: Arc<Mutex<DemoData1Feature>>) -> CostSurfaceVisualizer {
+        CostSurfaceVisualizer {
+            data,
+            camera: Arc::new(Mutex::new(Camera {
+                pitch: PLOT_DEFAULT_PITCH,
+                yaw: PLOT_DEFAULT_YAW,
+            })),
+            view: Arc::new(Mutex::new(View::new([COST_DEFAULT_RANGE; 2]))),
+        }
+    }
+
+    // the view and the camera are shared with the button resetting them
+    pub fn view(&self) -> Arc<Mutex<View<2>>> {
+        self.view.clone()
+    }
+
+    pub fn camera(&self) -> Arc<Mutex<Camera>> {
+        self.camera.clone()
+    }
+}
+
+impl Drawer<AppData> for CostSurfaceVisualizer {
+    fn draw_demo_data(&self, buf: &mut [u8], width: usize, height: usize, _app_data: &AppData)
+                      -> Result<(), Box<dyn Error>> {
+        let root = BitMapBackend::with_buffer(
+            buf, (width as u32, height as u32),
+        ).into_drawing_area();
+
+        root.fill(&WHITE)?;
+
+        // the cost is sampled without holding the lock, so the learning thread doesn't wait for the drawing
+        let (xs, ys, trajectory) = {
+            let data = self.data.lock().unwrap();
+            (data.xs.clone(), data.ys.clone(), data.trajectory.clone())
+        };
+        let current_theta = match trajectory.last() {
+            Some(theta) if !xs.is_empty() => *theta,
+            _ => return Ok(()),
+        };
+
+        let mut view = self.view.lock().unwrap();
+        // there's no closed form solution for logistic regression,
+        // so the trajectory is mirrored around the current theta to keep it in the middle of the chart
+        let surface = CostSurface::sample(
+            |theta0, theta1| helpers::math::logistic_regression_cost([theta0, theta1], &xs, &ys),
+            &trajectory,
+            Some(current_theta),
+            &mut view,
+            SURFACE_RESOLUTION,
+        );
+
+        let mut chart = ChartBuilder::on(&root)
+            .caption("cost surface", FONT.into_font())
+            .margin(CHART_MARGIN)
+            .build_cartesian_3d(view.range(0), surface.cost_range(), view.range(1))?;
+        chart.with_projection(|mut p| {
+            let camera = self.camera.lock().unwrap();
+            p.pitch = camera.pitch;
+            p.yaw = camera.yaw;
+            p.scale = DEFAULT_SCALE;
+            p.into_matrix()
+        });
+        chart
+            .configure_axes()
+            .x_labels(COST_SURFACE_LABELS)
+            .y_labels(COST_SURFACE_LABELS)
+            .z_labels(COST_SURFACE_LABELS)
+            .label_style(SMALL_LEGEND_FONT)
+            .draw()?;
+
+        surface.draw(&mut chart, BLUE, RED)?;
+
+        Ok(())
+    }
+
+    fn get_size(&self) -> Size {
+        Size::new(COST_SURFACE_WIDTH, ONE_FEATURE_VISUALIZER_HEIGHT)
+    }
+
+    fn handle_mouse_move(&mut self, ctx: &EventCtx, event: &MouseEvent, diff: Vec2, _app_data: &mut AppData) {
+        if !event.buttons.has_left() {
+            return;
+        }
+
+        let size = ctx.size();
+        let mut camera = self.camera.lock().unwrap();
+        camera.yaw += diff.x / size.width;
+        camera.pitch -= diff.y / size.height;
+    }
+
+    fn handle_wheel(&mut self, _ctx: &EventCtx, event: &MouseEvent, _app_data: &mut AppData) {
+        let mut view = self.view.lock().unwrap();
+        let center = view.center();
+
+        view.zoom(center, zoom_factor(event));
+    }
+}
@@ -1,26 +1,128 @@
-use crate::constants::*;
-use crate::demo_data::DemoDataNFeatures;
+use crate::demo_data::{DemoData2Features, DemoDataNFeatures};
+use helpers::svm::LinearSvm;
+use helpers::learning_rate::AdaptiveLearningRate;
+use helpers::training_control::TrainingControl;
 use std::sync::{Arc, Mutex};
 use std::thread;
 use std::time::Duration;
 
-pub fn learning_thread<const N_FEATURES: usize>(data: Arc<Mutex<DemoDataNFeatures<N_FEATURES>>>)
+pub fn learning_thread<const N_FEATURES: usize>(
+    data: Arc<Mutex<DemoDataNFeatures<N_FEATURES>>>,
+    mut control: TrainingControl,
+)
     where [(); N_FEATURES + 1]:
 {
+    // a restored session continues with the learning rate it was saved with
+    let (mut learning_rate, mut settings) = {
+        let data = data.lock().unwrap();
+        (AdaptiveLearningRate::new(data.learning_rate), data.settings)
+    };
+
     loop {
-        for _ in 0..GRADIENT_STEPS_PER_UPDATE {
+        let reset_requested = control.receive();
+        {
             let mut data = data.lock().unwrap();
-            data.theta = helpers::math::gradient_descent_step(
-                data.theta,
-                LEARNING_RATE,
-                &data.xs,
-                &data.ys,
-                |x: [f64; N_FEATURES]| {
-                    helpers::math::logistic_regression_predict(data.theta, x)
-                },
-            );
+            // the learning rate and theta start over when their initial values are changed in the UI
+            if reset_requested || data.settings.learning_rate != settings.learning_rate {
+                learning_rate = AdaptiveLearningRate::new(data.settings.learning_rate);
+                data.learning_rate = learning_rate.value();
+            }
+            if reset_requested || data.settings.default_theta != settings.default_theta {
+                data.reset_theta();
+            }
+            settings = data.settings;
+        }
+
+        for _ in 0..control.take_iterations(settings.steps_per_update) {
+            gradient_descent_iteration(&mut data.lock().unwrap(), &mut learning_rate);
         }
 
-        thread::sleep(Duration::from_millis(GRADIENT_UPDATE_PERIOD as u64));
+        thread::sleep(control.period(Duration::from_millis(settings.update_period)));
+    }
+}
+
+// gradient_descent_iteration makes one step of gradient descent,
+// nothing happens while there are no examples, e.g. all of them were deleted
+fn gradient_descent_iteration<const N_FEATURES: usize>(
+    data: &mut DemoDataNFeatures<N_FEATURES>,
+    learning_rate: &mut AdaptiveLearningRate,
+)
+    where [(); N_FEATURES + 1]:
+{
+    if data.xs.is_empty() {
+        return;
+    }
+
+    let xs = &data.xs;
+    let ys = &data.ys;
+    let new_theta = learning_rate.step(
+        data.theta,
+        |theta| helpers::math::logistic_regression_cost(theta, xs, ys),
+        |theta, rate| helpers::math::gradient_descent_step(
+            theta,
+            rate,
+            xs,
+            ys,
+            |x: [f64; N_FEATURES]| {
+                helpers::math::logistic_regression_predict(theta, x)
+            },
+        ),
+    );
+    data.theta = new_theta;
+    data.learning_rate = learning_rate.value();
+    data.iteration += 1;
+    data.record_theta();
+}
+
+pub fn svm_learning_thread(data: Arc<Mutex<DemoData2Features>>, svm: Arc<Mutex<LinearSvm<2>>>) {
+    loop {
+        let update_period = {
+            let data = data.lock().unwrap();
+            svm.lock().unwrap().train_step(&data.xs, &data.ys);
+            data.settings.update_period
+        };
+
+        thread::sleep(Duration::from_millis(update_period));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::demo_data::DemoData1Feature;
+    use crate::learning::gradient_descent_iteration;
+    use helpers::learning_rate::AdaptiveLearningRate;
+    use helpers::learning_settings::LearningSettings;
+
+    #[test]
+    fn test_iteration_without_examples() {
+        let settings = LearningSettings {
+            learning_rate: 0.1,
+            steps_per_update: 1,
+            update_period: 1,
+            default_theta: 0.0,
+            use_normal_equation: false,
+        };
+        let mut data = DemoData1Feature {
+            xs: vec![[1.0], [2.0]],
+            ys: vec![0.0, 1.0],
+            theta: [0.0; 2],
+            learning_rate: settings.learning_rate,
+            settings,
+            iteration: 0,
+            trajectory: vec![[0.0; 2]],
+            online_examples: None,
+        };
+        let mut learning_rate = AdaptiveLearningRate::new(settings.learning_rate);
+
+        data.remove_example(1);
+        data.remove_example(0);
+        gradient_descent_iteration(&mut data, &mut learning_rate);
+        assert_eq!(data.theta, [0.0; 2]);
+        assert_eq!(data.iteration, 0);
+
+        data.add_point([1.0, 1.0]);
+        gradient_descent_iteration(&mut data, &mut learning_rate);
+        assert_eq!(data.iteration, 1);
+        assert!(data.theta[1] > 0.0);
     }
 }
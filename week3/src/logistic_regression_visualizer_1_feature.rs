@@ -1,19 +1,46 @@
 use druid::widget::prelude::*;
+use druid::{MouseButton, MouseEvent, Vec2};
+use std::cell::RefCell;
 use std::sync::{Arc, Mutex};
+use plotters::coord::types::RangedCoordf64;
+use plotters::coord::ReverseCoordTranslate;
 use plotters::prelude::*;
 use crate::demo_data::DemoData1Feature;
 use crate::constants::*;
 use std::error::Error;
 use crate::app_data::AppData;
 use helpers::canvas::Drawer;
+use helpers::plotters::two_d::{closest_point, split_into_visible_segments};
+use helpers::view::{is_pan, pan_shift, zoom_factor, View};
 
+// Visualizer1Feature shows logistic regression with 1 feature,
+// left click adds a point of class 1 and right click adds a point of class 0. The existing points are
+// dragged along the x axis, ctrl-click deletes a point and shift-click changes its class.
+// The wheel zooms the chart and dragging it with the middle button or with alt pans it
 pub struct Visualizer1Feature {
     data: Arc<Mutex<DemoData1Feature>>,
+    // the coordinate system of the last drawn chart to convert clicks into points
+    coord_spec: RefCell<Option<Cartesian2d<RangedCoordf64, RangedCoordf64>>>,
+    // the index of the point being dragged
+    dragged_point: Option<usize>,
+    view: Arc<Mutex<View<2>>>,
+    is_panning: bool,
 }
 
 impl Visualizer1Feature {
     pub fn new(data: Arc<Mutex<DemoData1Feature>>) -> Visualizer1Feature {
-        Visualizer1Feature { data }
+        Visualizer1Feature {
+            data,
+            coord_spec: RefCell::new(None),
+            dragged_point: None,
+            view: Arc::new(Mutex::new(View::new([(0.0, MAX_VALUE), (0.0, MAX_Y_VALUE)]))),
+            is_panning: false,
+        }
+    }
+
+    // view is shared with the button resetting it
+    pub fn view(&self) -> Arc<Mutex<View<2>>> {
+        self.view.clone()
     }
 }
 
@@ -26,18 +53,31 @@ impl Drawer<AppData> for Visualizer1Feature {
 
         root.fill(&WHITE)?;
 
+        let data = self.data.lock().unwrap();
+
+        root.draw(&Text::new(
+            format!("learning rate: {:.5}, iteration: {}", data.learning_rate, data.iteration),
+            (CHART_MARGIN, CHART_MARGIN),
+            SMALL_FONT.into_font(),
+        ))?;
+
+        let mut view = self.view.lock().unwrap();
+        // the y axis always shows the whole sigmoid
+        view.fit(data.xs.iter().flat_map(|x| [[x[0], 0.0], [x[0], MAX_Y_VALUE]]));
+        let (min_x, max_x) = view.ranges[0];
+        let (min_y, max_y) = view.ranges[1];
+
         let mut chart = ChartBuilder::on(&root)
             .caption("1 feature", FONT.into_font())
             .margin(CHART_MARGIN)
             .x_label_area_size(LEGEND_SIZE)
             .y_label_area_size(LEGEND_SIZE)
-            .build_cartesian_2d(0f64..MAX_VALUE, 0f64..MAX_Y_VALUE)?;
+            .build_cartesian_2d(view.range(0), view.range(1))?;
 
         chart.configure_mesh().draw()?;
-        // plot the points
-        let data = self.data.lock().unwrap();
+        // plot the points, plotting library doesn't support values outside of drawing region
         chart.draw_series(
-            data.xs.iter().enumerate().map(
+            data.xs.iter().enumerate().filter(|(i, x)| view.contains([x[0], data.ys[*i]])).map(
                 |(i, x)| Circle::new(
                     (x[0], data.ys[i]),
                     POINT_SIZE,
@@ -51,40 +91,151 @@ impl Drawer<AppData> for Visualizer1Feature {
         )?;
 
         // plot the new point (if there's any)
-        if let Ok(new_point) = app_data.parse_one_feature_new_point() {
-            chart.draw_series(
-                [(new_point[0], new_point[1])]
-                    .iter()
-                    .map(|(x, y)| {
-                        Circle::new(
-                            (*x, *y), POINT_SIZE, NEW_POINT_COLOR.filled(),
-                        )
-                    }),
-            )?;
+        match app_data.parse_one_feature_new_point() {
+            Ok(new_point) if view.contains(new_point) => {
+                chart.draw_series(
+                    [(new_point[0], new_point[1])]
+                        .iter()
+                        .map(|(x, y)| {
+                            Circle::new(
+                                (*x, *y), POINT_SIZE, NEW_POINT_COLOR.filled(),
+                            )
+                        }),
+                )?;
+            }
+            _ => (),
         }
 
         // horizontal line at Y = 0.5
-        chart.draw_series(
-            LineSeries::new(
-                [(0.0, 0.5), (MAX_VALUE, 0.5)].iter().map(|x| *x),
-                BLACK.filled(),
-            ),
-        )?;
+        if min_y <= 0.5 && 0.5 <= max_y {
+            chart.draw_series(
+                LineSeries::new(
+                    [(min_x, 0.5), (max_x, 0.5)].iter().map(|x| *x),
+                    BLACK.filled(),
+                ),
+            )?;
+        }
 
         // predicting sigmoid
-        chart.draw_series(
-            LineSeries::new(
-                (0..(MAX_VALUE * SIGMOID_PLOTTING_PRECISION) as usize)
-                    .map(|x| x as f64 / SIGMOID_PLOTTING_PRECISION)
-                    .map(|x| (x, helpers::math::logistic_regression_predict(data.theta, [x]))),
-                GREEN.filled(),
-            ),
-        )?;
+        let sigmoid = (0..=SIGMOID_RESOLUTION)
+            .map(|i| min_x + (max_x - min_x) * i as f64 / SIGMOID_RESOLUTION as f64)
+            .map(|x| (x, helpers::math::logistic_regression_predict(data.theta, [x])));
+        for segment in split_into_visible_segments(sigmoid, min_y, max_y) {
+            chart.draw_series(LineSeries::new(segment, GREEN.filled()))?;
+        }
+
+        *self.coord_spec.borrow_mut() = Some(chart.as_coord_spec().clone());
 
         Ok(())
     }
 
     fn get_size(&self) -> Size {
-        Size::new(WINDOW_WIDTH, ONE_FEATURE_VISUALIZER_HEIGHT)
+        Size::new(ONE_FEATURE_VISUALIZER_WIDTH, ONE_FEATURE_VISUALIZER_HEIGHT)
+    }
+
+    fn handle_mouse_move(&mut self, _ctx: &EventCtx, event: &MouseEvent, diff: Vec2, _app_data: &mut AppData) {
+        // the canvas doesn't get the mouse up, so the panning stops when the buttons are released
+        if self.is_panning && (event.buttons.has_middle() || event.buttons.has_left()) {
+            let shift = match self.coord_spec.borrow().as_ref() {
+                Some(coord_spec) => pan_shift(coord_spec, event.pos + diff, event.pos),
+                None => None,
+            };
+            if let Some(shift) = shift {
+                self.view.lock().unwrap().pan(shift);
+            }
+            return;
+        }
+
+        let i = match self.dragged_point {
+            Some(i) if event.buttons.has_left() => i,
+            _ => return,
+        };
+        let point = match self.coord_spec.borrow().as_ref() {
+            Some(coord_spec) => coord_spec.reverse_translate((event.pos.x as i32, event.pos.y as i32)),
+            None => None,
+        };
+
+        if let Some((x, _)) = point {
+            let mut data = self.data.lock().unwrap();
+            if let Some(y) = data.ys.get(i).copied() {
+                data.move_example(i, [x], y);
+            }
+        }
+    }
+
+    fn handle_mouse_down(&mut self, _ctx: &EventCtx, event: &MouseEvent, _app_data: &mut AppData) {
+        self.dragged_point = None;
+        self.is_panning = is_pan(event);
+        if self.is_panning {
+            return;
+        }
+
+        let label = match click_label(event) {
+            Some(label) => label,
+            None => return,
+        };
+        let coord_spec = self.coord_spec.borrow();
+        let coord_spec = match coord_spec.as_ref() {
+            Some(coord_spec) => coord_spec,
+            None => return,
+        };
+        let pixel = (event.pos.x as i32, event.pos.y as i32);
+        let mut data = self.data.lock().unwrap();
+
+        let points: Vec<(f64, f64)> = data.xs.iter().zip(&data.ys).map(|(x, y)| (x[0], *y)).collect();
+        match closest_point(coord_spec, points, pixel, POINT_PICK_DISTANCE) {
+            Some(i) => match edit_action(event) {
+                EditAction::Delete => data.remove_example(i),
+                EditAction::ChangeClass => {
+                    let (x, y) = (data.xs[i], data.ys[i]);
+                    data.move_example(i, x, 1.0 - y);
+                }
+                EditAction::Drag => self.dragged_point = Some(i),
+            },
+            // the clicks outside of the chart are ignored, the height of the click doesn't matter
+            None => {
+                if let Some((x, _)) = coord_spec.reverse_translate(pixel) {
+                    data.add_point([x, label]);
+                }
+            }
+        }
+    }
+
+    fn handle_wheel(&mut self, _ctx: &EventCtx, event: &MouseEvent, _app_data: &mut AppData) {
+        let mut view = self.view.lock().unwrap();
+        // the point under the mouse stays in place
+        let center = match self.coord_spec.borrow().as_ref() {
+            Some(coord_spec) => coord_spec.reverse_translate((event.pos.x as i32, event.pos.y as i32)),
+            None => None,
+        };
+        let center = center.map(|(x, y)| [x, y]).unwrap_or_else(|| view.center());
+
+        view.zoom(center, zoom_factor(event));
+    }
+}
+
+// EditAction is what a click on an existing point does
+pub enum EditAction {
+    Drag,
+    Delete,
+    ChangeClass,
+}
+
+pub fn edit_action(event: &MouseEvent) -> EditAction {
+    if event.mods.ctrl() {
+        EditAction::Delete
+    } else if event.mods.shift() {
+        EditAction::ChangeClass
+    } else {
+        EditAction::Drag
+    }
+}
+
+// click_label returns the class of the point added by the click: 1 for the left button and 0 for the right one
+pub fn click_label(event: &MouseEvent) -> Option<f64> {
+    match event.button {
+        MouseButton::Left => Some(1.0),
+        MouseButton::Right => Some(0.0),
+        _ => None,
     }
 }
@@ -1,5 +1,6 @@
 use druid::widget::prelude::*;
 use druid::{MouseEvent, Vec2};
+use std::cell::RefCell;
 use std::sync::{Arc, Mutex};
 use plotters::prelude::*;
 use crate::demo_data::DemoData2Features;
@@ -7,26 +8,66 @@ use crate::constants::*;
 use std::error::Error;
 use crate::app_data::AppData;
 use helpers::canvas::Drawer;
+use helpers::session::Camera;
 use plotters::coord::types::RangedCoordf64;
 use plotters::coord::cartesian::Cartesian3d;
+use plotters::coord::ReverseCoordTranslate;
+use helpers::plotters::two_d::line_inside_rect;
+use helpers::svm::LinearSvm;
+use crate::logistic_regression_visualizer_1_feature::{click_label, edit_action, EditAction};
+use helpers::plotters::two_d::closest_point;
+use helpers::view::{is_pan, pan_shift, zoom_factor, View};
 
 const WIDGET_WIDTH: f64 = WINDOW_WIDTH;
 const WIDGET_HEIGHT: f64 = WIDGET_WIDTH / 2.0;
 
+// Visualizer2Features shows logistic regression with 2 features: the points on the 2d chart are added and edited
+// like in Visualizer1Feature and dragging the 3d chart rotates it. The wheel zooms the chart under the mouse,
+// the 2d chart is panned by dragging it with the middle button or with alt
 pub struct Visualizer2Features {
     data: Arc<Mutex<DemoData2Features>>,
-    pitch: f64,
-    yaw: f64,
+    svm: Arc<Mutex<LinearSvm<2>>>,
+    // it's shared to be saved in the session
+    camera: Arc<Mutex<Camera>>,
+    // the coordinate system of the last drawn 2d chart to convert clicks into points
+    coord_spec: RefCell<Option<Cartesian2d<RangedCoordf64, RangedCoordf64>>>,
+    // the camera is moved only by the drags started on the 3d chart
+    is_rotating: bool,
+    // the index of the point being dragged on the 2d chart
+    dragged_point: Option<usize>,
+    view_2d: Arc<Mutex<View<2>>>,
+    // the ranges of x1, the prediction and x2
+    view_3d: Arc<Mutex<View<3>>>,
+    is_panning: bool,
 }
 
 impl Visualizer2Features {
-    pub fn new(data: Arc<Mutex<DemoData2Features>>) -> Visualizer2Features {
+    pub fn new(
+        data: Arc<Mutex<DemoData2Features>>,
+        svm: Arc<Mutex<LinearSvm<2>>>,
+        camera: Arc<Mutex<Camera>>,
+    ) -> Visualizer2Features {
         Visualizer2Features {
             data,
-            pitch: PLOT_DEFAULT_PITCH,
-            yaw: PLOT_DEFAULT_YAW,
+            svm,
+            camera,
+            coord_spec: RefCell::new(None),
+            is_rotating: false,
+            dragged_point: None,
+            view_2d: Arc::new(Mutex::new(View::new([(0.0, MAX_VALUE); 2]))),
+            view_3d: Arc::new(Mutex::new(View::new([(0.0, MAX_VALUE), (0.0, MAX_Y_VALUE), (0.0, MAX_VALUE)]))),
+            is_panning: false,
         }
     }
+
+    // the views are shared with the button resetting them
+    pub fn view_2d(&self) -> Arc<Mutex<View<2>>> {
+        self.view_2d.clone()
+    }
+
+    pub fn view_3d(&self) -> Arc<Mutex<View<3>>> {
+        self.view_3d.clone()
+    }
 }
 
 impl Drawer<AppData> for Visualizer2Features {
@@ -39,33 +80,62 @@ impl Drawer<AppData> for Visualizer2Features {
         root.fill(&WHITE)?;
 
         let root = root.split_horizontally((WIDGET_WIDTH / 2.0) as u32);
+        let view_2d = {
+            let mut view = self.view_2d.lock().unwrap();
+            view.fit(self.data.lock().unwrap().xs.iter().copied());
+            *view
+        };
         let mut left_chart = ChartBuilder::on(&root.0)
             .caption("2 features 2d representation", FONT.into_font())
             .margin(CHART_MARGIN)
             .x_label_area_size(LEGEND_SIZE)
             .y_label_area_size(LEGEND_SIZE)
-            .build_cartesian_2d(0f64..MAX_VALUE, 0f64..MAX_VALUE)?;
+            .build_cartesian_2d(view_2d.range(0), view_2d.range(1))?;
         left_chart.configure_mesh().draw()?;
 
-        self.draw_2d(&mut left_chart, &app_data)?;
+        self.draw_2d(&mut left_chart, &view_2d, &app_data)?;
+        *self.coord_spec.borrow_mut() = Some(left_chart.as_coord_spec().clone());
+        left_chart
+            .configure_series_labels()
+            .position(SeriesLabelPosition::UpperLeft)
+            .label_font(SMALL_LEGEND_FONT)
+            .background_style(WHITE.mix(0.8))
+            .border_style(BLACK)
+            .draw()?;
 
+        let view_3d = {
+            let mut view = self.view_3d.lock().unwrap();
+            // the vertical axis always shows the whole sigmoid
+            let data = self.data.lock().unwrap();
+            view.fit(data.xs.iter().flat_map(|x| [[x[0], 0.0, x[1]], [x[0], MAX_Y_VALUE, x[1]]]));
+            *view
+        };
         let mut right_chart = ChartBuilder::on(&root.1)
             .caption("2 features 3d representation", FONT.into_font())
             .margin(CHART_MARGIN)
             .x_label_area_size(LEGEND_SIZE)
             .y_label_area_size(LEGEND_SIZE)
             .build_cartesian_3d(
-                0f64..MAX_VALUE, 0f64..MAX_Y_VALUE, 0f64..MAX_VALUE,
+                view_3d.range(0), view_3d.range(1), view_3d.range(2),
             )?;
         right_chart.with_projection(|mut p| {
-            p.pitch = self.pitch;
-            p.yaw = self.yaw;
+            let camera = self.camera.lock().unwrap();
+            p.pitch = camera.pitch;
+            p.yaw = camera.yaw;
             p.scale = DEFAULT_SCALE;
             p.into_matrix()
         });
         right_chart.configure_axes().draw()?;
 
-        self.draw_3d(&mut right_chart, &app_data)?;
+        self.draw_3d(&mut right_chart, &view_3d, &app_data)?;
+
+        // the bottom left corner of the 3d chart is always empty
+        let data = self.data.lock().unwrap();
+        root.1.draw(&Text::new(
+            format!("learning rate: {:.5}, iteration: {}", data.learning_rate, data.iteration),
+            (CHART_MARGIN, height as i32 - CHART_MARGIN - SMALL_FONT.1),
+            SMALL_FONT.into_font(),
+        ))?;
 
         Ok(())
     }
@@ -74,13 +144,100 @@ impl Drawer<AppData> for Visualizer2Features {
         Size::new(WIDGET_WIDTH, WIDGET_HEIGHT)
     }
 
-    fn handle_mouse_move(&mut self, ctx: &EventCtx, event: &MouseEvent, diff: Vec2) {
+    fn handle_mouse_move(&mut self, ctx: &EventCtx, event: &MouseEvent, diff: Vec2, _app_data: &mut AppData) {
         let size = ctx.size();
-        if event.buttons.has_left() {
-            self.yaw += diff.x / size.width;
-            self.pitch -= diff.y / size.height;
+        // the canvas doesn't get the mouse up, so the panning stops when the buttons are released
+        if self.is_panning && (event.buttons.has_middle() || event.buttons.has_left()) {
+            let shift = match self.coord_spec.borrow().as_ref() {
+                Some(coord_spec) => pan_shift(coord_spec, event.pos + diff, event.pos),
+                None => None,
+            };
+            if let Some(shift) = shift {
+                self.view_2d.lock().unwrap().pan(shift);
+            }
+            return;
+        }
+
+        if !event.buttons.has_left() {
+            return;
+        }
+
+        if self.is_rotating {
+            let mut camera = self.camera.lock().unwrap();
+            camera.yaw += diff.x / size.width;
+            camera.pitch -= diff.y / size.height;
+        } else if let Some(i) = self.dragged_point {
+            let point = match self.coord_spec.borrow().as_ref() {
+                Some(coord_spec) => coord_spec.reverse_translate((event.pos.x as i32, event.pos.y as i32)),
+                None => None,
+            };
+
+            if let Some((x, z)) = point {
+                let mut data = self.data.lock().unwrap();
+                if let Some(y) = data.ys.get(i).copied() {
+                    data.move_example(i, [x, z], y);
+                }
+            }
         }
     }
+
+    fn handle_mouse_down(&mut self, ctx: &EventCtx, event: &MouseEvent, _app_data: &mut AppData) {
+        self.is_rotating = event.pos.x >= ctx.size().width / 2.0;
+        self.dragged_point = None;
+        self.is_panning = !self.is_rotating && is_pan(event);
+        if self.is_rotating || self.is_panning {
+            return;
+        }
+
+        let label = match click_label(event) {
+            Some(label) => label,
+            None => return,
+        };
+        let coord_spec = self.coord_spec.borrow();
+        let coord_spec = match coord_spec.as_ref() {
+            Some(coord_spec) => coord_spec,
+            None => return,
+        };
+        let pixel = (event.pos.x as i32, event.pos.y as i32);
+        let mut data = self.data.lock().unwrap();
+
+        let points: Vec<(f64, f64)> = data.xs.iter().map(|x| (x[0], x[1])).collect();
+        match closest_point(coord_spec, points, pixel, POINT_PICK_DISTANCE) {
+            Some(i) => match edit_action(event) {
+                EditAction::Delete => data.remove_example(i),
+                EditAction::ChangeClass => {
+                    let (x, y) = (data.xs[i], data.ys[i]);
+                    data.move_example(i, x, 1.0 - y);
+                }
+                EditAction::Drag => self.dragged_point = Some(i),
+            },
+            None => {
+                if let Some((x, z)) = coord_spec.reverse_translate(pixel) {
+                    data.add_point([x, z, label]);
+                }
+            }
+        }
+    }
+
+    fn handle_wheel(&mut self, ctx: &EventCtx, event: &MouseEvent, _app_data: &mut AppData) {
+        let factor = zoom_factor(event);
+        if event.pos.x >= ctx.size().width / 2.0 {
+            let mut view = self.view_3d.lock().unwrap();
+            let center = view.center();
+            view.zoom(center, factor);
+            return;
+        }
+
+        let mut view = self.view_2d.lock().unwrap();
+        // the point under the mouse stays in place
+        let center = match self.coord_spec.borrow().as_ref() {
+            Some(coord_spec) => coord_spec.reverse_translate((event.pos.x as i32, event.pos.y as i32)),
+            None => None,
+        };
+        let center = center.map(|(x, z)| [x, z]).unwrap_or_else(|| view.center());
+
+        view.zoom(center, factor);
+    }
 }
 
 fn get_point_color(theta: [f64; 3], x: [f64; 2]) -> RGBColor {
@@ -95,6 +252,7 @@ impl Visualizer2Features {
     fn draw_2d(
         &self,
         chart: &mut ChartContext<BitMapBackend, Cartesian2d<RangedCoordf64, RangedCoordf64>>,
+        view: &View<2>,
         app_data: &AppData,
     ) -> Result<(), Box<dyn Error>>
     {
@@ -103,9 +261,9 @@ impl Visualizer2Features {
         let xs = &data.xs;
         let ys = &data.ys;
 
-        // plot the points
+        // plot the points, plotting library doesn't support values outside of drawing region
         chart.draw_series(
-            xs.iter().enumerate().map(
+            xs.iter().enumerate().filter(|(_, x)| view.contains(**x)).map(
                 |(i, x)| Circle::new(
                     (x[0], x[1]),
                     if ys[i] < 0.5 { POINT_SIZE } else { POINT_SIZE * 2 },
@@ -115,23 +273,75 @@ impl Visualizer2Features {
         )?;
 
         // plot the new point (if there's any)
-        if let Ok(new_point) = app_data.parse_two_features_new_point() {
-            chart.draw_series(
-                [new_point]
-                    .iter()
-                    .map(|[x, z, y]| {
-                        Circle::new(
-                            (*x, *z),
-                            if *y < 0.5 { POINT_SIZE } else { POINT_SIZE * 2 },
-                            NEW_POINT_COLOR.filled(),
-                        )
-                    }),
-            )?;
+        match app_data.parse_two_features_new_point() {
+            Ok(new_point) if view.contains([new_point[0], new_point[1]]) => {
+                chart.draw_series(
+                    [new_point]
+                        .iter()
+                        .map(|[x, z, y]| {
+                            Circle::new(
+                                (*x, *z),
+                                if *y < 0.5 { POINT_SIZE } else { POINT_SIZE * 2 },
+                                NEW_POINT_COLOR.filled(),
+                            )
+                        }),
+                )?;
+            }
+            _ => (),
+        }
+
+        // logistic regression predicts 0.5 exactly on the decision boundary `theta_transpose * x = 0`
+        if let Some(boundary) = line_inside_rect(*theta, 0.0, view.ranges[0], view.ranges[1]) {
+            chart
+                .draw_series(LineSeries::new(boundary.iter().copied(), LOGISTIC_REGRESSION_BOUNDARY_COLOR))?
+                .label("logistic regression")
+                .legend(|(x, y)| {
+                    PathElement::new(vec![(x, y), (x + LEGEND_SIZE, y)], LOGISTIC_REGRESSION_BOUNDARY_COLOR)
+                });
+        }
+
+        if SHOW_SVM {
+            self.draw_svm(chart, view, xs)?;
         }
 
         Ok(())
     }
 
+    // draw_svm draws the separating line, the margins and highlights the support vectors
+    fn draw_svm(
+        &self,
+        chart: &mut ChartContext<BitMapBackend, Cartesian2d<RangedCoordf64, RangedCoordf64>>,
+        view: &View<2>,
+        xs: &[[f64; 2]],
+    ) -> Result<(), Box<dyn Error>>
+    {
+        let svm = self.svm.lock().unwrap();
+
+        if let Some(boundary) = line_inside_rect(svm.theta, 0.0, view.ranges[0], view.ranges[1]) {
+            chart
+                .draw_series(LineSeries::new(boundary.iter().copied(), SVM_COLOR.stroke_width(2)))?
+                .label("SVM")
+                .legend(|(x, y)| {
+                    PathElement::new(vec![(x, y), (x + LEGEND_SIZE, y)], SVM_COLOR.stroke_width(2))
+                });
+        }
+
+        for level in [-1.0, 1.0].iter() {
+            if let Some(margin) = line_inside_rect(svm.theta, *level, view.ranges[0], view.ranges[1]) {
+                chart.draw_series(LineSeries::new(margin.iter().copied(), SVM_COLOR.mix(0.5)))?;
+            }
+        }
+
+        chart.draw_series(
+            svm.support_vectors()
+                .into_iter()
+                // the data could've been changed after the last training step
+                .filter(|i| *i < xs.len() && view.contains(xs[*i]))
+                .map(|i| Circle::new((xs[i][0], xs[i][1]), POINT_SIZE * 4, SVM_COLOR.stroke_width(2))),
+        )?;
+
+        Ok(())
+    }
 
     fn draw_3d(
         &self,
@@ -139,30 +349,39 @@ impl Visualizer2Features {
             BitMapBackend,
             Cartesian3d<RangedCoordf64, RangedCoordf64, RangedCoordf64>,
         >,
+        view: &View<3>,
         app_data: &AppData,
     ) -> Result<(), Box<dyn Error>>
     {
         let data = self.data.lock().unwrap();
         let theta = &data.theta;
-        let xs = &data.xs;
-        let ys = &data.ys;
+        // plotting library doesn't support values outside of drawing region
+        let (xs, ys): (Vec<[f64; 2]>, Vec<f64>) = data.xs
+            .iter()
+            .zip(&data.ys)
+            .filter(|(x, y)| view.contains([x[0], **y, x[1]]))
+            .map(|(x, y)| (*x, *y))
+            .unzip();
 
         // plot the points
         helpers::plotters::three_d::plot_points(
-            chart, *theta, xs, ys, POINT_SIZE, get_point_color,
+            chart, *theta, &xs, &ys, POINT_SIZE, get_point_color,
         )?;
 
         // plot the new point (if there's any)
-        if let Ok(new_point) = app_data.parse_two_features_new_point() {
-            helpers::plotters::three_d::plot_new_point(
-                chart, new_point, POINT_SIZE, NEW_POINT_COLOR,
-            )?;
+        match app_data.parse_two_features_new_point() {
+            Ok([x, z, y]) if view.contains([x, y, z]) => {
+                helpers::plotters::three_d::plot_new_point(
+                    chart, [x, z, y], POINT_SIZE, NEW_POINT_COLOR,
+                )?;
+            }
+            _ => (),
         }
 
         // plot a surface representing our prediction function
         helpers::plotters::three_d::plot_surface(chart, |x: f64, z: f64| -> f64 {
             helpers::math::logistic_regression_predict(*theta, [x, z])
-        }, (MAX_VALUE * SURFACE_PRECISION) as usize, MAX_VALUE, BLUE)?;
+        }, SURFACE_RESOLUTION, view.ranges[0], view.ranges[2], BLUE)?;
 
         Ok(())
     }
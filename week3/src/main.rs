@@ -3,51 +3,189 @@
 
 mod app_data;
 mod constants;
+mod cost_surface_visualizer;
 mod demo_data;
 mod learning;
 mod logistic_regression_visualizer_1_feature;
 mod logistic_regression_visualizer_2_features;
+mod settings_controller;
 
 use crate::app_data::AppData;
 use crate::constants::*;
-use crate::demo_data::{DemoData1Feature, DemoData2Features};
-use crate::learning::learning_thread;
+use crate::cost_surface_visualizer::CostSurfaceVisualizer;
+use crate::demo_data::{DemoData1Feature, DemoData2Features, DemoDataNFeatures};
+use crate::learning::{learning_thread, svm_learning_thread};
+use crate::settings_controller::SettingsController;
 use druid::widget::*;
 use druid::*;
+use helpers::math::logistic_regression_predict;
+use helpers::online_learning::{example_stream_thread, online_learning_thread, Example};
+use rand::Rng;
+use std::f64::consts::PI;
+use std::process;
+use std::sync::mpsc::{self, Sender};
 use std::sync::{Arc, Mutex};
 use std::thread;
 use logistic_regression_visualizer_1_feature::Visualizer1Feature;
-use helpers::canvas::Canvas;
+use helpers::canvas::{Canvas, Drawer};
+use helpers::export::{ExportArguments, EXPORT_USAGE};
 use std::time::Duration;
 use crate::logistic_regression_visualizer_2_features::Visualizer2Features;
+use helpers::svm::LinearSvm;
+use helpers::dataset::{Dataset, LabelColumn};
+use helpers::learning_settings::{build_learning_settings_widget, LearningSettingsData};
+use helpers::training_control::{build_training_control_widget, control_channel, ControlCommand, TrainingControlData};
+use std::error::Error;
+use std::path::{Path, PathBuf};
+use helpers::session::{load_session, save_session, Camera, ChartSession};
+use serde::{Deserialize, Serialize};
+
+const USAGE: &str = "usage: logistic-regression-demo [--online] [--data <ex2data1.txt>]... \
+[--label-column <last|number|name>] [--save-1-feature <points.txt>] [--save-2-features <points.txt>] \
+[--session <session.json>] [--save-session <session.json>]";
+// the labels is_label accepts
+const LABELS: &str = "0 or 1";
+
+struct Arguments {
+    online: bool,
+    // the comma separated files with the initial points like ex2data1.txt, the files with 1 feature
+    // go to the first chart and the files with 2 features go to the second one
+    data: Vec<PathBuf>,
+    label_column: LabelColumn,
+    // the points are saved to the files when the window is closed
+    save_1_feature: Option<PathBuf>,
+    save_2_features: Option<PathBuf>,
+    // the session with both charts to start from
+    session: Option<PathBuf>,
+    // the session is saved to the file when the window is closed
+    save_session: Option<PathBuf>,
+}
+
+// Session is the state of both charts, only the chart with 2 features has a camera
+#[derive(Serialize, Deserialize)]
+struct Session {
+    one_feature: ChartSession,
+    two_features: ChartSession,
+}
 
 fn main() {
-    let data_1_feature = Arc::new(Mutex::new(DemoData1Feature {
+    let (export, args) = match ExportArguments::parse(std::env::args().skip(1).collect()) {
+        Ok(arguments) => arguments,
+        Err(error) => {
+            eprintln!("{}\n{}\n{}", error, USAGE, EXPORT_USAGE);
+            process::exit(2);
+        }
+    };
+    let arguments = match parse_arguments(args) {
+        Ok(arguments) => arguments,
+        Err(error) => {
+            eprintln!("{}\n{}\n{}", error, USAGE, EXPORT_USAGE);
+            process::exit(2);
+        }
+    };
+
+    let mut demo_data_1_feature = DemoData1Feature {
         xs: vec![[1.0], [2.0], [3.0], [7.0], [8.0], [9.0]],
         ys: vec![0.0, 0.0, 0.0, 1.0, 1.0, 1.0],
-        theta: [DEFAULT_THETA_VALUE, DEFAULT_THETA_VALUE],
-    }));
-    let data_2_features = Arc::new(Mutex::new(DemoData2Features {
+        theta: [DEFAULT_LEARNING_SETTINGS.default_theta; 2],
+        learning_rate: DEFAULT_LEARNING_SETTINGS.learning_rate,
+        settings: DEFAULT_LEARNING_SETTINGS,
+        iteration: 0,
+        trajectory: vec![[DEFAULT_LEARNING_SETTINGS.default_theta; 2]],
+        online_examples: None,
+    };
+    let mut demo_data_2_features = DemoData2Features {
         xs: vec![[1.0, 1.0], [2.0, 1.0], [3.0, 1.0], [7.0, 7.0], [8.0, 8.0], [9.0, 9.0]],
         ys: vec![0.0, 0.0, 0.0, 1.0, 1.0, 1.0],
-        theta: [DEFAULT_THETA_VALUE, DEFAULT_THETA_VALUE, DEFAULT_THETA_VALUE],
-    }));
-
+        theta: [DEFAULT_LEARNING_SETTINGS.default_theta; 3],
+        learning_rate: DEFAULT_LEARNING_SETTINGS.learning_rate,
+        settings: DEFAULT_LEARNING_SETTINGS,
+        iteration: 0,
+        trajectory: vec![[DEFAULT_LEARNING_SETTINGS.default_theta; 3]],
+        online_examples: None,
+    };
+    let mut camera = Camera {
+        pitch: PLOT_DEFAULT_PITCH,
+        yaw: PLOT_DEFAULT_YAW,
+    };
+    if let Some(path) = &arguments.session {
+        let result = load_session::<Session>(path).and_then(|session| {
+            session.one_feature.check_labels(is_label, LABELS)
+                .and_then(|_| session.one_feature.restore(&mut demo_data_1_feature))
+                .map_err(|error| format!("{}: one_feature: {}", path.display(), error))?;
+            session.two_features.check_labels(is_label, LABELS)
+                .and_then(|_| session.two_features.restore(&mut demo_data_2_features))
+                .map_err(|error| format!("{}: two_features: {}", path.display(), error))?;
+            camera = session.two_features.camera.unwrap_or(camera);
+            Ok(())
+        });
+        if let Err(error) = result {
+            eprintln!("error: {}", error);
+            process::exit(1);
+        }
+    }
+    for path in &arguments.data {
+        let result = load_data(path, &arguments.label_column, &mut demo_data_1_feature, &mut demo_data_2_features);
+        if let Err(error) = result {
+            eprintln!("error: {}", error);
+            process::exit(1);
+        }
+    }
     let app_data = AppData {
         one_feature_new_point_x: String::new(),
         one_feature_new_point_y: String::new(),
         two_features_new_point_x: String::new(),
         two_features_new_point_z: String::new(),
         two_features_new_point_y: String::new(),
+        // the charts share the controls, so they start with the settings of the first one
+        learning_settings: LearningSettingsData::new(&demo_data_1_feature.settings),
+        training_control: TrainingControlData::default(),
     };
 
-    let thread_data_1_feature = data_1_feature.clone();
-    thread::spawn(move || learning_thread(thread_data_1_feature));
+    let data_1_feature = Arc::new(Mutex::new(demo_data_1_feature));
+    let data_2_features = Arc::new(Mutex::new(demo_data_2_features));
+    let camera = Arc::new(Mutex::new(camera));
 
-    let thread_data_2_features = data_2_features.clone();
-    thread::spawn(move || learning_thread(thread_data_2_features));
+    // the training is only controlled from the UI when it isn't online, the controls are shared by both charts
+    let mut control_senders = Vec::new();
+    if arguments.online {
+        start_online_learning(data_1_feature.clone(), generate_example_1_feature);
+        start_online_learning(data_2_features.clone(), generate_example_2_features);
+    } else {
+        let (sender, control) = control_channel();
+        control_senders.push(sender);
+        let thread_data_1_feature = data_1_feature.clone();
+        thread::spawn(move || learning_thread(thread_data_1_feature, control));
 
-    let window = WindowDesc::new(get_ui_builder(data_1_feature, data_2_features))
+        let (sender, control) = control_channel();
+        control_senders.push(sender);
+        let thread_data_2_features = data_2_features.clone();
+        thread::spawn(move || learning_thread(thread_data_2_features, control));
+    }
+
+    let svm = Arc::new(Mutex::new(LinearSvm::<2>::new(SVM_C)));
+    if SHOW_SVM {
+        let thread_data_2_features = data_2_features.clone();
+        let thread_svm = svm.clone();
+        thread::spawn(move || svm_learning_thread(thread_data_2_features, thread_svm));
+    }
+
+    if let Some(export) = export {
+        let drawers: Vec<Box<dyn Drawer<AppData>>> = vec![
+            Box::new(Visualizer1Feature::new(data_1_feature.clone())),
+            Box::new(Visualizer2Features::new(data_2_features, svm, camera)),
+            Box::new(CostSurfaceVisualizer::new(data_1_feature)),
+        ];
+        if let Err(error) = export.run(&drawers, &app_data) {
+            eprintln!("error: {}", error);
+            process::exit(1);
+        }
+        return;
+    }
+
+    let window = WindowDesc::new(get_ui_builder(
+        data_1_feature.clone(), data_2_features.clone(), svm, camera.clone(), control_senders,
+    ))
         .window_size(Size::new(WINDOW_WIDTH, WINDOW_HEIGHT))
         .title(
             LocalizedString::new("Logistic Regression Demo")
@@ -58,17 +196,163 @@ fn main() {
         .use_simple_logger()
         .launch(app_data)
         .expect("launch failed");
+
+    if let Some(path) = &arguments.save_1_feature {
+        if let Err(error) = data_1_feature.lock().unwrap().save_examples(path) {
+            eprintln!("error: {}", error);
+            process::exit(1);
+        }
+    }
+    if let Some(path) = &arguments.save_2_features {
+        if let Err(error) = data_2_features.lock().unwrap().save_examples(path) {
+            eprintln!("error: {}", error);
+            process::exit(1);
+        }
+    }
+    if let Some(path) = &arguments.save_session {
+        let session = Session {
+            one_feature: ChartSession::new(&data_1_feature.lock().unwrap(), None),
+            two_features: ChartSession::new(&data_2_features.lock().unwrap(), Some(*camera.lock().unwrap())),
+        };
+        if let Err(error) = save_session(path, &session) {
+            eprintln!("error: {}", error);
+            process::exit(1);
+        }
+    }
+}
+
+fn parse_arguments(args: Vec<String>) -> Result<Arguments, Box<dyn Error>> {
+    let mut online = false;
+    let mut data = Vec::new();
+    let mut label_column = LabelColumn::default();
+    let mut save_1_feature = None;
+    let mut save_2_features = None;
+    let mut session = None;
+    let mut save_session = None;
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("{} requires a value", arg));
+
+        match arg.as_str() {
+            // in the online learning mode the examples are streamed to the learning threads one by one
+            "--online" => online = true,
+            "--data" => data.push(PathBuf::from(value()?)),
+            "--label-column" => label_column = value()?.parse()?,
+            "--save-1-feature" => save_1_feature = Some(PathBuf::from(value()?)),
+            "--save-2-features" => save_2_features = Some(PathBuf::from(value()?)),
+            "--session" => session = Some(PathBuf::from(value()?)),
+            "--save-session" => save_session = Some(PathBuf::from(value()?)),
+            _ => return Err(format!("unexpected argument {}", arg).into()),
+        }
+    }
+
+    Ok(Arguments {
+        online,
+        data,
+        label_column,
+        save_1_feature,
+        save_2_features,
+        session,
+        save_session,
+    })
+}
+
+// is_label checks the labels of the data files and the sessions, they're the classes
+fn is_label(y: f64) -> bool {
+    y == 0.0 || y == 1.0
+}
+
+// load_data replaces the examples of the chart with the same number of features as the file has
+fn load_data(
+    path: &Path,
+    label_column: &LabelColumn,
+    data_1_feature: &mut DemoData1Feature,
+    data_2_features: &mut DemoData2Features,
+) -> Result<(), Box<dyn Error>> {
+    let dataset = Dataset::load(path, label_column)?;
+    dataset.check_labels(is_label, LABELS)?;
+
+    match dataset.number_of_features() {
+        1 => data_1_feature.set_examples(&dataset),
+        2 => data_2_features.set_examples(&dataset),
+        n => Err(format!("{}: expected 1 or 2 features, got {}", path.display(), n).into()),
+    }
+}
+
+// start_online_learning replaces the stored examples with a stream of generated ones,
+// the new points from the UI are added to the stream too
+fn start_online_learning<const N_FEATURES: usize, F: 'static + Send + FnMut(usize) -> Example<N_FEATURES>>(
+    data: Arc<Mutex<DemoDataNFeatures<N_FEATURES>>>,
+    generate_example: F,
+) where [(); N_FEATURES + 1]: {
+    let (sender, receiver) = mpsc::channel();
+    {
+        let mut data = data.lock().unwrap();
+        data.xs.clear();
+        data.ys.clear();
+        data.online_examples = Some(sender.clone());
+    }
+
+    thread::spawn(move || online_learning_thread(
+        data, receiver, ONLINE_LEARNING_RATE, ONLINE_SHOWN_EXAMPLES, logistic_regression_predict,
+    ));
+    thread::spawn(move || {
+        example_stream_thread(sender, Duration::from_millis(ONLINE_STREAM_PERIOD), generate_example)
+    });
+}
+
+// generate_example_1_feature generates examples of class 1 to the right of the threshold moving back and forth
+fn generate_example_1_feature(i: usize) -> Example<1> {
+    let threshold = MAX_VALUE / 2.0 * (1.0 + 0.6 * (2.0 * PI * i as f64 / ONLINE_STREAM_DRIFT_PERIOD).sin());
+    let x = rand::thread_rng().gen_range(0.0..MAX_VALUE);
+
+    ([x], if x > threshold { 1.0 } else { 0.0 })
+}
+
+// generate_example_2_features generates examples separated by a line rotating around the center of the chart
+fn generate_example_2_features(i: usize) -> Example<2> {
+    let angle = PI / 4.0 * (1.0 + (2.0 * PI * i as f64 / ONLINE_STREAM_DRIFT_PERIOD).sin());
+    let mut rng = rand::thread_rng();
+    let x = [rng.gen_range(0.0..MAX_VALUE), rng.gen_range(0.0..MAX_VALUE)];
+    let center = MAX_VALUE / 2.0;
+
+    let is_positive = (x[0] - center) * angle.cos() + (x[1] - center) * angle.sin() > 0.0;
+
+    (x, if is_positive { 1.0 } else { 0.0 })
 }
 
 fn get_ui_builder(
-    data_1_feature: Arc<Mutex<DemoData1Feature>>, data_2_features: Arc<Mutex<DemoData2Features>>,
-) -> impl Fn() -> Flex<AppData> {
+    data_1_feature: Arc<Mutex<DemoData1Feature>>,
+    data_2_features: Arc<Mutex<DemoData2Features>>,
+    svm: Arc<Mutex<LinearSvm<2>>>,
+    camera: Arc<Mutex<Camera>>,
+    control_senders: Vec<Sender<ControlCommand>>,
+) -> impl Fn() -> ControllerHost<Flex<AppData>, SettingsController> {
     move || {
-        Flex::<AppData>::column()
-            .with_child(Canvas::<AppData>::new(
-                Duration::from_millis(REFRESH_PERIOD),
-                Box::new(Visualizer1Feature::new(data_1_feature.clone())),
-            ))
+        let visualizer_1_feature = Visualizer1Feature::new(data_1_feature.clone());
+        let visualizer_2_features = Visualizer2Features::new(data_2_features.clone(), svm.clone(), camera.clone());
+        let cost_surface_visualizer = CostSurfaceVisualizer::new(data_1_feature.clone());
+        let (view_1_feature, view_2d, view_3d, cost_view) = (
+            visualizer_1_feature.view(),
+            visualizer_2_features.view_2d(),
+            visualizer_2_features.view_3d(),
+            cost_surface_visualizer.view(),
+        );
+        let (reset_camera, cost_camera) = (camera.clone(), cost_surface_visualizer.camera());
+
+        let mut column = Flex::<AppData>::column()
+            .with_child(
+                Flex::row()
+                    .with_child(Canvas::<AppData>::new(
+                        Duration::from_millis(REFRESH_PERIOD),
+                        Box::new(visualizer_1_feature),
+                    ))
+                    .with_child(Canvas::<AppData>::new(
+                        Duration::from_millis(REFRESH_PERIOD),
+                        Box::new(cost_surface_visualizer),
+                    )),
+            )
             .with_child(build_add_point_2_params_widget(
                 AppData::one_feature_new_point_x, "x".to_string(),
                 AppData::one_feature_new_point_y, "y".to_string(),
@@ -76,7 +360,7 @@ fn get_ui_builder(
             ))
             .with_child(Canvas::<AppData>::new(
                 Duration::from_millis(REFRESH_PERIOD),
-                Box::new(Visualizer2Features::new(data_2_features.clone())),
+                Box::new(visualizer_2_features),
             ))
             .with_child(build_add_point_3_params_widget(
                 AppData::two_features_new_point_x, "x".to_string(),
@@ -84,6 +368,26 @@ fn get_ui_builder(
                 AppData::two_features_new_point_y, "y".to_string(),
                 data_2_features.clone(),
             ))
+            .with_child(Button::new("Reset View").on_click(
+                move |_ctx: &mut EventCtx, _app_data: &mut AppData, _env: &Env| {
+                    view_1_feature.lock().unwrap().reset();
+                    view_2d.lock().unwrap().reset();
+                    view_3d.lock().unwrap().reset();
+                    cost_view.lock().unwrap().reset();
+                    for camera in [&reset_camera, &cost_camera] {
+                        *camera.lock().unwrap() = Camera {
+                            pitch: PLOT_DEFAULT_PITCH,
+                            yaw: PLOT_DEFAULT_YAW,
+                        };
+                    }
+                },
+            ))
+            .with_child(build_learning_settings_widget(false).lens(AppData::learning_settings));
+        if !control_senders.is_empty() {
+            column.add_child(build_training_control_widget(control_senders.clone()).lens(AppData::training_control));
+        }
+
+        column.controller(SettingsController::new(data_1_feature.clone(), data_2_features.clone()))
     }
 }
 
@@ -114,7 +418,7 @@ fn build_add_point_2_params_widget<
                     Ok(new_point) => {
                         data.lock().unwrap().add_point(new_point);
                     }
-                    Err(_) => println!("invalid point"),
+                    Err(error) => println!("invalid point: {}", error),
                 }
             },
         ))
@@ -155,7 +459,7 @@ fn build_add_point_3_params_widget<
                     Ok(new_point) => {
                         data.lock().unwrap().add_point(new_point);
                     }
-                    Err(_) => println!("invalid point"),
+                    Err(error) => println!("invalid point: {}", error),
                 }
             },
         ))
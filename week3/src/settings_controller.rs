@@ -0,0 +1,37 @@
+use druid::widget::prelude::*;
+use druid::widget::Controller;
+use druid::Data;
+use std::sync::{Arc, Mutex};
+use crate::app_data::AppData;
+use crate::demo_data::{DemoData1Feature, DemoData2Features};
+
+// SettingsController passes the settings chosen in the UI to the learning threads of both charts
+pub struct SettingsController {
+    data_1_feature: Arc<Mutex<DemoData1Feature>>,
+    data_2_features: Arc<Mutex<DemoData2Features>>,
+}
+
+impl SettingsController {
+    pub fn new(
+        data_1_feature: Arc<Mutex<DemoData1Feature>>,
+        data_2_features: Arc<Mutex<DemoData2Features>>,
+    ) -> SettingsController {
+        SettingsController {
+            data_1_feature,
+            data_2_features,
+        }
+    }
+}
+
+impl<W: Widget<AppData>> Controller<AppData, W> for SettingsController {
+    fn update(&mut self, child: &mut W, ctx: &mut UpdateCtx, old_data: &AppData, data: &AppData, env: &Env) {
+        if !old_data.same(data) {
+            let mut data_1_feature = self.data_1_feature.lock().unwrap();
+            data_1_feature.settings = data.learning_settings.settings(&data_1_feature.settings);
+            let mut data_2_features = self.data_2_features.lock().unwrap();
+            data_2_features.settings = data.learning_settings.settings(&data_2_features.settings);
+        }
+
+        child.update(ctx, old_data, data, env)
+    }
+}
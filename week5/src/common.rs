@@ -1,10 +1,16 @@
-use matfile::{MatFile, NumericData};
+use helpers::mat_file::get_array_from_mat_file;
+use matfile::MatFile;
 use std::error::Error;
 use std::fs::File;
 use std::path::Path;
 
 pub const INPUT_LAYER_SIZE: usize = 400;
 pub const OUTPUT_LAYER_SIZE: usize = 10;
+pub const HIDDEN_LAYER_SIZE: usize = 25;
+pub const REGULARIZATION_RATE: f64 = 1.0;
+pub const DEFAULT_NUMBER_OF_ITERATIONS: usize = 100;
+// the share of the examples the network is trained on, the rest is used to measure the accuracy
+pub const TRAINING_SET_SHARE: f64 = 0.8;
 
 pub fn load_demo_data() -> (
     Vec<Vec<f64>>,
@@ -15,7 +21,7 @@ pub fn load_demo_data() -> (
     load_demo_data_impl().unwrap()
 }
 
-fn load_demo_data_impl() -> Result<
+pub fn load_demo_data_impl() -> Result<
     (
         Vec<Vec<f64>>,
         Vec<Vec<f64>>,
@@ -53,31 +59,6 @@ fn load_expected_theta_gradients_impl() -> Result<[Vec<Vec<f64>>; 2], Box<dyn Er
     return Ok([theta1_grad, theta2_grad]);
 }
 
-fn get_array_from_mat_file<const N_COLUMNS: usize>(
-    mat_file: &MatFile,
-    array_name: &str,
-) -> Vec<[f64; N_COLUMNS]> {
-    let array = mat_file.find_by_name(array_name).unwrap().data().clone();
-
-    if let NumericData::Double { real, .. } = array {
-        let mut res: Vec<[f64; N_COLUMNS]> = Vec::new();
-
-        let n_rows = real.len() / N_COLUMNS;
-        for r in 0..n_rows {
-            let mut row = [0.0; N_COLUMNS];
-            for c in 0..N_COLUMNS {
-                // the data is written column by column(for some reason)
-                row[c] = real[c * n_rows + r];
-            }
-            res.push(row);
-        }
-
-        return res;
-    }
-
-    panic!("array has different type: {:?}", array);
-}
-
 fn convert_ys<const N: usize>(ys: &[[f64; 1]]) -> Vec<[bool; N]> {
     ys.iter()
         .map(|y| {
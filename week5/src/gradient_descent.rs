@@ -17,6 +17,21 @@ pub fn get_cost_and_gradient<
     xs: &[[f64; INPUT_LAYER_SIZE]],
     ys: &[[bool; OUTPUT_LAYER_SIZE]],
     regularization_rate: f64,
+) -> (f64, [Vec<Vec<f64>>; N_LAYERS - 1]) {
+    get_cost_and_gradient_of_inputs::<N_LAYERS, OUTPUT_LAYER_SIZE, _>(thetas, xs, ys, regularization_rate)
+}
+
+// get_cost_and_gradient_of_inputs is get_cost_and_gradient for the inputs whose size is known only at runtime,
+// like the projections of the images onto the principal components
+pub fn get_cost_and_gradient_of_inputs<
+    const N_LAYERS: usize,
+    const OUTPUT_LAYER_SIZE: usize,
+    X: AsRef<[f64]>,
+>(
+    thetas: &[Vec<Vec<f64>>; N_LAYERS - 1],
+    xs: &[X],
+    ys: &[[bool; OUTPUT_LAYER_SIZE]],
+    regularization_rate: f64,
 ) -> (f64, [Vec<Vec<f64>>; N_LAYERS - 1]) {
     // TODO: rewrite using nalgebra instead of raw arrays
 
@@ -30,7 +45,7 @@ pub fn get_cost_and_gradient<
     for (example_number, x) in xs.iter().enumerate() {
         let mut activations_sigmoid_gradient: Vec<Vec<f64>> = Vec::new();
         let mut activations: Vec<Vec<_>> = std::iter::repeat(vec![]).take(N_LAYERS).collect();
-        activations[0] = x.to_vec();
+        activations[0] = x.as_ref().to_vec();
         activations[0].insert(0, 1.0);
 
         for i in 1..activations.len() {
@@ -5,8 +5,141 @@ extern crate approx;
 
 mod common;
 mod gradient_descent;
+mod pca_comparison;
+mod photo_ocr;
+mod training;
+
+use crate::common::{load_demo_data_impl, DEFAULT_NUMBER_OF_ITERATIONS};
+use crate::photo_ocr::{detect, draw_detections, generate_scene, Intensities};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::error::Error;
+use std::path::{Path, PathBuf};
+use std::process;
+
+const DEFAULT_DETECTIONS_OUTPUT: &str = "detections.png";
+
+const USAGE: &str = "usage: week5 [--pca <retained variance, e.g. 0.99>] [--iterations <number>]
+       week5 --detect <image.png> [--output <detections.png>]
+       week5 --generate-scene <scene.png> [--seed <number>]";
+
+struct Arguments {
+    retained_variance: Option<f64>,
+    number_of_iterations: usize,
+    detect: Option<PathBuf>,
+    output: PathBuf,
+    generate_scene: Option<PathBuf>,
+    seed: Option<u64>,
+}
 
 fn main() {
+    let arguments = match parse_arguments(std::env::args().skip(1).collect()) {
+        Ok(arguments) => arguments,
+        Err(error) => {
+            eprintln!("{}\n{}", error, USAGE);
+            process::exit(2);
+        }
+    };
+
+    let result = if let Some(image) = &arguments.detect {
+        run_detection(image, &arguments.output)
+    } else if let Some(scene) = &arguments.generate_scene {
+        run_scene_generation(scene, arguments.seed)
+    } else if let Some(retained_variance) = arguments.retained_variance {
+        pca_comparison::compare(retained_variance, arguments.number_of_iterations)
+    } else {
+        run_small_network_example();
+        Ok(())
+    };
+
+    if let Err(error) = result {
+        eprintln!("error: {}", error);
+        process::exit(1);
+    }
+}
+
+fn parse_arguments(args: Vec<String>) -> Result<Arguments, Box<dyn Error>> {
+    let mut retained_variance = None;
+    let mut number_of_iterations = DEFAULT_NUMBER_OF_ITERATIONS;
+    let mut detect = None;
+    let mut output = PathBuf::from(DEFAULT_DETECTIONS_OUTPUT);
+    let mut generate_scene = None;
+    let mut seed = None;
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("{} requires a value", arg));
+
+        match arg.as_str() {
+            "--pca" => retained_variance = Some(value()?.parse::<f64>()?),
+            "--iterations" => number_of_iterations = value()?.parse()?,
+            "--detect" => detect = Some(PathBuf::from(value()?)),
+            "--output" => output = PathBuf::from(value()?),
+            "--generate-scene" => generate_scene = Some(PathBuf::from(value()?)),
+            "--seed" => seed = Some(value()?.parse()?),
+            _ => return Err(format!("unexpected argument {}", arg).into()),
+        }
+    }
+
+    if let Some(retained_variance) = retained_variance {
+        if retained_variance <= 0.0 || retained_variance > 1.0 {
+            return Err("the retained variance should be in (0, 1]".into());
+        }
+    }
+
+    Ok(Arguments {
+        retained_variance,
+        number_of_iterations,
+        detect,
+        output,
+        generate_scene,
+        seed,
+    })
+}
+
+// run_detection finds the digits in the image by the network with the weights from ex4weights.mat
+// and draws them to the output image
+fn run_detection(path: &Path, output: &Path) -> Result<(), Box<dyn Error>> {
+    let (theta1, theta2, _, _) = load_demo_data_impl().map_err(|error| format!("can't load the weights: {}", error))?;
+    let image = image::open(path)
+        .map_err(|error| format!("{}: {}", path.display(), error))?
+        .into_luma8();
+
+    let detections = detect(&Intensities::from_image(&image), &[theta1, theta2]);
+    for detection in &detections {
+        println!(
+            "{} at ({}, {}), size {}, confidence {:.3}",
+            detection.digit, detection.x, detection.y, detection.size, detection.confidence
+        );
+    }
+    let text: String = detections.iter().map(|detection| detection.digit.to_string()).collect();
+    println!("found {} digits: {}", detections.len(), text);
+
+    draw_detections(output, &image, &detections)?;
+    println!("the detections are drawn to {}", output.display());
+
+    Ok(())
+}
+
+// run_scene_generation saves an image with random digits from ex4data1.mat to try the detection on
+fn run_scene_generation(path: &Path, seed: Option<u64>) -> Result<(), Box<dyn Error>> {
+    let (_, _, xs, ys) = load_demo_data_impl().map_err(|error| format!("can't load ex4data1.mat: {}", error))?;
+    let mut rng = match seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    };
+
+    let (scene, digits) = generate_scene(&xs, &ys, &mut rng);
+    scene.save(path).map_err(|error| format!("{}: {}", path.display(), error))?;
+
+    let text: String = digits.iter().map(|digit| digit.digit.to_string()).collect();
+    println!("{} digits are placed to {}: {}", digits.len(), path.display(), text);
+
+    Ok(())
+}
+
+// run_small_network_example calculates the cost and the gradient of a tiny network by hand-picked weights
+fn run_small_network_example() {
     // let mut rng = rand::thread_rng();
     // let weights = gradient_descent::get_random_weights([2, 3, 2, 4], &mut rng);
     //
@@ -0,0 +1,115 @@
+// pca_comparison trains the digit network twice: on the raw pixels and on their projections onto
+// the principal components, to show how PCA can speed up a learning algorithm
+use crate::common::{
+    load_demo_data_impl, HIDDEN_LAYER_SIZE, INPUT_LAYER_SIZE, OUTPUT_LAYER_SIZE, REGULARIZATION_RATE,
+    TRAINING_SET_SHARE,
+};
+use crate::training::{accuracy, random_initial_thetas, train};
+use helpers::pca::Pca;
+use rand::seq::SliceRandom;
+use std::error::Error;
+use std::time::{Duration, Instant};
+
+struct TrainingResult {
+    duration: Duration,
+    training_accuracy: f64,
+    test_accuracy: f64,
+}
+
+pub fn compare(retained_variance: f64, number_of_iterations: usize) -> Result<(), Box<dyn Error>> {
+    let (_, _, xs, ys) = load_demo_data_impl().map_err(|error| format!("can't load ex4data1.mat: {}", error))?;
+
+    let mut rng = rand::thread_rng();
+    let mut indices: Vec<usize> = (0..xs.len()).collect();
+    indices.shuffle(&mut rng);
+    let training_set_size = (xs.len() as f64 * TRAINING_SET_SHARE).round() as usize;
+    let (training_indices, test_indices) = indices.split_at(training_set_size);
+
+    let training_xs: Vec<Vec<f64>> = training_indices.iter().map(|&i| xs[i].to_vec()).collect();
+    let training_ys: Vec<_> = training_indices.iter().map(|&i| ys[i]).collect();
+    let test_xs: Vec<Vec<f64>> = test_indices.iter().map(|&i| xs[i].to_vec()).collect();
+    let test_ys: Vec<_> = test_indices.iter().map(|&i| ys[i]).collect();
+
+    println!(
+        "{} training and {} test examples, {} iterations",
+        training_xs.len(),
+        test_xs.len(),
+        number_of_iterations
+    );
+
+    let baseline = train_and_evaluate(
+        INPUT_LAYER_SIZE,
+        (&training_xs, &training_ys),
+        (&test_xs, &test_ys),
+        number_of_iterations,
+    );
+    print_result(&format!("{} pixels", INPUT_LAYER_SIZE), &baseline);
+
+    // PCA is fitted on the training set only and the same projection is applied to the test set
+    let pca_start = Instant::now();
+    let pca = Pca::fit(&training_xs);
+    let k = pca.smallest_k(retained_variance);
+    let training_zs: Vec<Vec<f64>> = training_xs.iter().map(|x| pca.project(x, k)).collect();
+    let test_zs: Vec<Vec<f64>> = test_xs.iter().map(|x| pca.project(x, k)).collect();
+    let pca_duration = pca_start.elapsed();
+
+    println!(
+        "PCA: {} components retain {:.2}% of the variance, took {:.2?}",
+        k,
+        pca.retained_variance(k) * 100.0,
+        pca_duration
+    );
+
+    let compressed = train_and_evaluate(k, (&training_zs, &training_ys), (&test_zs, &test_ys), number_of_iterations);
+    print_result(&format!("{} components", k), &compressed);
+
+    println!(
+        "speedup: {:.2}x for training, {:.2}x including PCA",
+        baseline.duration.as_secs_f64() / compressed.duration.as_secs_f64(),
+        baseline.duration.as_secs_f64() / (compressed.duration + pca_duration).as_secs_f64()
+    );
+    println!(
+        "test accuracy change: {:+.2}%",
+        (compressed.test_accuracy - baseline.test_accuracy) * 100.0
+    );
+
+    Ok(())
+}
+
+fn train_and_evaluate(
+    input_layer_size: usize,
+    (training_xs, training_ys): (&[Vec<f64>], &[[bool; OUTPUT_LAYER_SIZE]]),
+    (test_xs, test_ys): (&[Vec<f64>], &[[bool; OUTPUT_LAYER_SIZE]]),
+    number_of_iterations: usize,
+) -> TrainingResult {
+    let initial_thetas = random_initial_thetas(
+        [input_layer_size, HIDDEN_LAYER_SIZE, OUTPUT_LAYER_SIZE],
+        &mut rand::thread_rng(),
+    );
+
+    let start = Instant::now();
+    let thetas = train::<3, OUTPUT_LAYER_SIZE, _>(
+        initial_thetas,
+        training_xs,
+        training_ys,
+        REGULARIZATION_RATE,
+        number_of_iterations,
+    );
+    let duration = start.elapsed();
+
+    TrainingResult {
+        duration,
+        training_accuracy: accuracy(&thetas, training_xs, training_ys),
+        test_accuracy: accuracy(&thetas, test_xs, test_ys),
+    }
+}
+
+fn print_result(name: &str, result: &TrainingResult) {
+    println!(
+        "{}: trained in {:.2?}, training accuracy {:.2}%, test accuracy {:.2}%",
+        name,
+        result.duration,
+        result.training_accuracy * 100.0,
+        result.test_accuracy * 100.0
+    );
+}
@@ -0,0 +1,344 @@
+// photo_ocr finds digits in a larger image like the photo OCR pipeline from the last week of the course:
+// a window slides over the image at several scales, every window is classified by the digit network
+// and the overlapping detections are merged into one
+use crate::common::{INPUT_LAYER_SIZE, OUTPUT_LAYER_SIZE};
+use crate::training::feedforward;
+use image::{GrayImage, Luma, RgbImage};
+use plotters::prelude::*;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::error::Error;
+use std::path::Path;
+
+// the digits of ex4data1.mat are 20x20 pixels
+pub const DIGIT_SIZE: usize = 20;
+// the window sizes are DIGIT_SIZE multiplied by these scales
+pub const WINDOW_SCALES: [f64; 7] = [1.0, 1.5, 2.0, 2.5, 3.0, 4.0, 5.0];
+// the window moves by this share of its size
+pub const WINDOW_STEP_SHARE: f64 = 0.1;
+// the network has no "not a digit" class, so the windows with a weak output are thrown away
+pub const MIN_CONFIDENCE: f64 = 0.9;
+// the windows with an almost empty or an almost full digit aren't classified at all
+pub const MIN_INK_SHARE: f64 = 0.05;
+pub const MAX_INK_SHARE: f64 = 0.5;
+// the digits of the training set take most of their box with a small margin, so the ink should span most of the window
+pub const MIN_DIGIT_FILL_SHARE: f64 = 0.6;
+// a window cutting a digit has ink right outside of it, the ring of this share of the window size is checked
+pub const CONTEXT_SHARE: f64 = 0.15;
+pub const MAX_CONTEXT_INK_SHARE: f64 = 0.02;
+// a pixel is ink if its intensity is above this share of the brightest pixel of the window
+pub const INK_THRESHOLD: f64 = 0.5;
+// the darkest windows are noise
+pub const MIN_WINDOW_CONTRAST: f64 = 0.3;
+// a detection is merged into a more confident one if their intersection covers
+// more than this share of the smaller box
+pub const MAX_OVERLAP: f64 = 0.3;
+
+pub const SCENE_WIDTH: u32 = 480;
+pub const SCENE_HEIGHT: u32 = 240;
+pub const SCENE_NUMBER_OF_DIGITS: usize = 8;
+pub const SCENE_MAX_PLACEMENT_ATTEMPTS: usize = 1000;
+pub const DETECTION_COLOR: RGBColor = RGBColor(255, 40, 40);
+pub const DETECTION_FONT: &str = "sans-serif";
+
+// Intensities is a grayscale image where 1 is ink and 0 is background like the digits of ex4data1.mat
+pub struct Intensities {
+    pub width: usize,
+    pub height: usize,
+    pub values: Vec<f64>,
+}
+
+impl Intensities {
+    // from_image converts an image to intensities, the images with a light background are inverted
+    // because the network was trained on light digits on a dark background
+    pub fn from_image(image: &GrayImage) -> Intensities {
+        let mut values: Vec<f64> = image.pixels().map(|pixel| pixel[0] as f64 / 255.0).collect();
+        let mean = values.iter().sum::<f64>() / values.len().max(1) as f64;
+        if mean > 0.5 {
+            values.iter_mut().for_each(|value| *value = 1.0 - *value);
+        }
+
+        Intensities {
+            width: image.width() as usize,
+            height: image.height() as usize,
+            values,
+        }
+    }
+
+    fn get(&self, x: usize, y: usize) -> f64 {
+        self.values[y * self.width + x]
+    }
+
+    // context_ink_share returns the share of the ink pixels in the ring of the given width around the window,
+    // the part of the ring outside of the image is skipped
+    pub fn context_ink_share(&self, x: usize, y: usize, size: usize, width: usize) -> f64 {
+        let (from_x, to_x) = (x.saturating_sub(width), (x + size + width).min(self.width));
+        let (from_y, to_y) = (y.saturating_sub(width), (y + size + width).min(self.height));
+
+        let mut number_of_pixels = 0;
+        let mut number_of_ink_pixels = 0;
+        for ring_y in from_y..to_y {
+            for ring_x in from_x..to_x {
+                if (x..x + size).contains(&ring_x) && (y..y + size).contains(&ring_y) {
+                    continue;
+                }
+                number_of_pixels += 1;
+                if self.get(ring_x, ring_y) > INK_THRESHOLD {
+                    number_of_ink_pixels += 1;
+                }
+            }
+        }
+
+        number_of_ink_pixels as f64 / number_of_pixels.max(1) as f64
+    }
+
+    // window_to_input shrinks the square window to DIGIT_SIZE x DIGIT_SIZE by averaging the pixels and
+    // unrolls it by columns like the rows of X in ex4data1.mat. The result is normalized by the brightest pixel
+    pub fn window_to_input(&self, x: usize, y: usize, size: usize) -> Option<[f64; INPUT_LAYER_SIZE]> {
+        let scale = size as f64 / DIGIT_SIZE as f64;
+        // the pixels of the window averaged into the i-th pixel of the digit, at least one
+        let bounds = |i: usize| {
+            let from = (i as f64 * scale) as usize;
+            (from, (((i + 1) as f64 * scale) as usize).max(from + 1))
+        };
+
+        let mut input = [0.0; INPUT_LAYER_SIZE];
+        for column in 0..DIGIT_SIZE {
+            let (from_x, to_x) = bounds(column);
+            for row in 0..DIGIT_SIZE {
+                let (from_y, to_y) = bounds(row);
+
+                let mut sum = 0.0;
+                for source_y in y + from_y..y + to_y {
+                    for source_x in x + from_x..x + to_x {
+                        sum += self.get(source_x, source_y);
+                    }
+                }
+                input[column * DIGIT_SIZE + row] = sum / ((to_x - from_x) * (to_y - from_y)) as f64;
+            }
+        }
+
+        let max = input.iter().cloned().fold(f64::MIN, f64::max);
+        if max < MIN_WINDOW_CONTRAST {
+            return None;
+        }
+        input.iter_mut().for_each(|value| *value /= max);
+
+        Some(input)
+    }
+}
+
+// Detection is a digit found in the square window with the top left corner at (x, y)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Detection {
+    pub x: usize,
+    pub y: usize,
+    pub size: usize,
+    pub digit: usize,
+    pub confidence: f64,
+}
+
+impl Detection {
+    // overlap returns the share of the smaller box covered by the intersection of the boxes
+    pub fn overlap(&self, other: &Detection) -> f64 {
+        let intersection_width = (self.x + self.size).min(other.x + other.size) as f64 - self.x.max(other.x) as f64;
+        let intersection_height = (self.y + self.size).min(other.y + other.size) as f64 - self.y.max(other.y) as f64;
+        if intersection_width <= 0.0 || intersection_height <= 0.0 {
+            return 0.0;
+        }
+
+        intersection_width * intersection_height / self.size.min(other.size).pow(2) as f64
+    }
+}
+
+// output_to_digit converts the index of the output neuron to the digit, "0" is labelled as 10 in ex4
+pub fn output_to_digit(output: usize) -> usize {
+    (output + 1) % 10
+}
+
+// detect slides the windows of all the scales over the image and returns the merged detections
+pub fn detect(image: &Intensities, thetas: &[Vec<Vec<f64>>]) -> Vec<Detection> {
+    let mut detections = Vec::new();
+
+    for scale in WINDOW_SCALES.iter() {
+        let size = (DIGIT_SIZE as f64 * scale).round() as usize;
+        if size > image.width || size > image.height {
+            continue;
+        }
+        let step = ((size as f64 * WINDOW_STEP_SHARE).round() as usize).max(1);
+
+        for y in (0..=image.height - size).step_by(step) {
+            for x in (0..=image.width - size).step_by(step) {
+                if let Some(detection) = classify_window(image, thetas, x, y, size) {
+                    detections.push(detection);
+                }
+            }
+        }
+    }
+
+    merge_detections(detections)
+}
+
+fn classify_window(image: &Intensities, thetas: &[Vec<Vec<f64>>], x: usize, y: usize, size: usize) -> Option<Detection> {
+    let input = image.window_to_input(x, y, size)?;
+
+    let is_ink = |value: &f64| *value > INK_THRESHOLD;
+    let ink_share = input.iter().filter(|value| is_ink(value)).count() as f64 / INPUT_LAYER_SIZE as f64;
+    if !(MIN_INK_SHARE..=MAX_INK_SHARE).contains(&ink_share) {
+        return None;
+    }
+
+    // the input is unrolled by columns, the columns and the rows with ink give the size of the digit
+    let ink_columns: Vec<usize> = (0..INPUT_LAYER_SIZE).filter(|i| is_ink(&input[*i])).map(|i| i / DIGIT_SIZE).collect();
+    let ink_rows: Vec<usize> = (0..INPUT_LAYER_SIZE).filter(|i| is_ink(&input[*i])).map(|i| i % DIGIT_SIZE).collect();
+    let span = |indices: &[usize]| indices.iter().max().unwrap_or(&0) - indices.iter().min().unwrap_or(&0) + 1;
+    if (span(&ink_columns).max(span(&ink_rows)) as f64) < DIGIT_SIZE as f64 * MIN_DIGIT_FILL_SHARE {
+        return None;
+    }
+
+    let context_width = ((size as f64 * CONTEXT_SHARE).round() as usize).max(1);
+    if image.context_ink_share(x, y, size, context_width) > MAX_CONTEXT_INK_SHARE {
+        return None;
+    }
+
+    let output = feedforward(thetas, &input);
+    assert_eq!(output.len(), OUTPUT_LAYER_SIZE);
+    let (best_output, confidence) = output
+        .iter()
+        .cloned()
+        .enumerate()
+        .max_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap())
+        .unwrap();
+    if confidence < MIN_CONFIDENCE {
+        return None;
+    }
+
+    Some(Detection {
+        x,
+        y,
+        size,
+        digit: output_to_digit(best_output),
+        confidence,
+    })
+}
+
+// merge_detections is non-maximum suppression: the detections are taken from the most confident one
+// and a detection overlapping an already taken one is merged into it
+pub fn merge_detections(mut detections: Vec<Detection>) -> Vec<Detection> {
+    detections.sort_by(|a, b| b.confidence.partial_cmp(&a.confidence).unwrap());
+
+    let mut merged: Vec<Detection> = Vec::new();
+    for detection in detections {
+        if merged.iter().all(|taken| taken.overlap(&detection) <= MAX_OVERLAP) {
+            merged.push(detection);
+        }
+    }
+
+    // from left to right like the text is read
+    merged.sort_by_key(|detection| (detection.x, detection.y));
+
+    merged
+}
+
+// draw_detections draws the boxes of the detections with the predicted digits over the image
+pub fn draw_detections(path: &Path, image: &GrayImage, detections: &[Detection]) -> Result<(), Box<dyn Error>> {
+    let (width, height) = image.dimensions();
+    let root = BitMapBackend::new(path, (width, height)).into_drawing_area();
+
+    let rgb = RgbImage::from_fn(width, height, |x, y| {
+        let value = image.get_pixel(x, y)[0];
+        image::Rgb([value, value, value])
+    });
+    // the raw buffer is used instead of DynamicImage so the versions of image crate don't have to match
+    let element: BitMapElement<_> = BitMapElement::with_owned_buffer((0, 0), (width, height), rgb.into_raw())
+        .ok_or("the buffer doesn't match the size of the image")?;
+    root.draw(&element)?;
+
+    for detection in detections {
+        let (x, y, size) = (detection.x as i32, detection.y as i32, detection.size as i32);
+        root.draw(&Rectangle::new([(x, y), (x + size, y + size)], DETECTION_COLOR.stroke_width(2)))?;
+
+        let font_size = (size / 2).max(12);
+        root.draw(&Text::new(
+            detection.digit.to_string(),
+            (x + 2, (y - font_size).max(0)),
+            (DETECTION_FONT, font_size).into_font().color(&DETECTION_COLOR),
+        ))?;
+    }
+
+    root.present()?;
+
+    Ok(())
+}
+
+// generate_scene places random digits of the training set at random scales on a dark image,
+// so the detector can be tried without a photo. It returns the image and the placed digits
+pub fn generate_scene<R: Rng>(
+    xs: &[[f64; INPUT_LAYER_SIZE]],
+    ys: &[[bool; OUTPUT_LAYER_SIZE]],
+    rng: &mut R,
+) -> (GrayImage, Vec<Detection>) {
+    let mut scene = GrayImage::new(SCENE_WIDTH, SCENE_HEIGHT);
+    let mut placed: Vec<Detection> = Vec::new();
+    let indices: Vec<usize> = (0..xs.len()).collect();
+
+    for _ in 0..SCENE_MAX_PLACEMENT_ATTEMPTS {
+        if placed.len() == SCENE_NUMBER_OF_DIGITS {
+            break;
+        }
+
+        let scale = rng.gen_range(WINDOW_SCALES[0]..WINDOW_SCALES[WINDOW_SCALES.len() - 1]);
+        let size = (DIGIT_SIZE as f64 * scale) as usize;
+        let (x, y) = (
+            rng.gen_range(0..=SCENE_WIDTH as usize - size),
+            rng.gen_range(0..=SCENE_HEIGHT as usize - size),
+        );
+        let index = match indices.choose(rng) {
+            Some(index) => *index,
+            None => break,
+        };
+        let digit = Detection {
+            x,
+            y,
+            size,
+            digit: output_to_digit(ys[index].iter().position(|y| *y).unwrap_or(0)),
+            confidence: 1.0,
+        };
+        if placed.iter().any(|other| other.overlap(&digit) > 0.0) {
+            continue;
+        }
+
+        // nearest neighbor scaling, the example is unrolled by columns
+        for scene_y in 0..size {
+            for scene_x in 0..size {
+                let (column, row) = (scene_x * DIGIT_SIZE / size, scene_y * DIGIT_SIZE / size);
+                let value = xs[index][column * DIGIT_SIZE + row].clamp(0.0, 1.0);
+                scene.put_pixel((x + scene_x) as u32, (y + scene_y) as u32, Luma([(value * 255.0) as u8]));
+            }
+        }
+        placed.push(digit);
+    }
+
+    placed.sort_by_key(|digit| (digit.x, digit.y));
+
+    (scene, placed)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::photo_ocr::{merge_detections, Detection};
+
+    #[test]
+    fn test_merge_detections() {
+        let detection = |x, size, digit, confidence| Detection { x, y: 10, size, digit, confidence };
+        let detections = vec![
+            detection(100, 20, 3, 0.95),
+            detection(0, 20, 7, 0.91),
+            // a smaller window inside the most confident one
+            detection(4, 12, 1, 0.97),
+            detection(2, 20, 7, 0.99),
+        ];
+
+        assert_eq!(merge_detections(detections), vec![detection(2, 20, 7, 0.99), detection(100, 20, 3, 0.95)]);
+    }
+}
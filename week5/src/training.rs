@@ -0,0 +1,136 @@
+use crate::gradient_descent::{get_cost_and_gradient_of_inputs, sigmoid};
+use helpers::learning_rate::{LEARNING_RATE_DECREASE_FACTOR, LEARNING_RATE_INCREASE_FACTOR};
+use rand::Rng;
+use std::convert::TryInto;
+
+pub const INITIAL_LEARNING_RATE: f64 = 1.0;
+// the initial weights are in `[-INITIAL_WEIGHTS_EPSILON, INITIAL_WEIGHTS_EPSILON]` like in ex4
+pub const INITIAL_WEIGHTS_EPSILON: f64 = 0.12;
+
+// train minimizes the cost by batch gradient descent. The learning rate is adjusted like in helpers::learning_rate:
+// it grows while the cost goes down and a step increasing the cost is thrown away and the learning rate is decreased
+pub fn train<const N_LAYERS: usize, const OUTPUT_LAYER_SIZE: usize, X: AsRef<[f64]>>(
+    initial_thetas: [Vec<Vec<f64>>; N_LAYERS - 1],
+    xs: &[X],
+    ys: &[[bool; OUTPUT_LAYER_SIZE]],
+    regularization_rate: f64,
+    number_of_iterations: usize,
+) -> [Vec<Vec<f64>>; N_LAYERS - 1] {
+    let mut thetas = initial_thetas;
+    let mut learning_rate = INITIAL_LEARNING_RATE;
+    let (mut cost, mut gradient) =
+        get_cost_and_gradient_of_inputs::<N_LAYERS, OUTPUT_LAYER_SIZE, X>(&thetas, xs, ys, regularization_rate);
+
+    for _ in 0..number_of_iterations {
+        let new_thetas: [Vec<Vec<f64>>; N_LAYERS - 1] = thetas
+            .iter()
+            .zip(gradient.iter())
+            .map(|(theta, theta_gradient)| {
+                theta
+                    .iter()
+                    .zip(theta_gradient)
+                    .map(|(row, row_gradient)| {
+                        row.iter().zip(row_gradient).map(|(weight, d)| weight - learning_rate * d).collect()
+                    })
+                    .collect()
+            })
+            .collect::<Vec<Vec<Vec<f64>>>>()
+            .try_into()
+            .unwrap();
+
+        let (new_cost, new_gradient) =
+            get_cost_and_gradient_of_inputs::<N_LAYERS, OUTPUT_LAYER_SIZE, X>(&new_thetas, xs, ys, regularization_rate);
+
+        if new_cost.is_finite() && new_cost <= cost {
+            thetas = new_thetas;
+            cost = new_cost;
+            gradient = new_gradient;
+            learning_rate *= LEARNING_RATE_INCREASE_FACTOR;
+        } else {
+            learning_rate *= LEARNING_RATE_DECREASE_FACTOR;
+        }
+    }
+
+    thetas
+}
+
+// random_initial_thetas initializes the weights randomly to break the symmetry like randInitializeWeights.m
+// from ex4, every row has an extra weight for the bias unit
+pub fn random_initial_thetas<const N_LAYERS: usize, R: Rng>(
+    layers_sizes: [usize; N_LAYERS],
+    rng: &mut R,
+) -> [Vec<Vec<f64>>; N_LAYERS - 1] {
+    layers_sizes
+        .windows(2)
+        .map(|sizes| {
+            (0..sizes[1])
+                .map(|_| {
+                    (0..sizes[0] + 1)
+                        .map(|_| rng.gen_range(-INITIAL_WEIGHTS_EPSILON..INITIAL_WEIGHTS_EPSILON))
+                        .collect()
+                })
+                .collect()
+        })
+        .collect::<Vec<Vec<Vec<f64>>>>()
+        .try_into()
+        .unwrap()
+}
+
+// feedforward propagates x through the network and returns the activations of the output neurons
+pub fn feedforward(thetas: &[Vec<Vec<f64>>], x: &[f64]) -> Vec<f64> {
+    let mut activation = x.to_vec();
+
+    for theta in thetas {
+        activation = theta
+            .iter()
+            .map(|row| sigmoid(row[0] + row[1..].iter().zip(&activation).map(|(w, a)| w * a).sum::<f64>()))
+            .collect();
+    }
+
+    activation
+}
+
+// predict returns the index of the most activated output neuron
+pub fn predict(thetas: &[Vec<Vec<f64>>], x: &[f64]) -> usize {
+    feedforward(thetas, x)
+        .iter()
+        .enumerate()
+        .max_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap())
+        .map(|(i, _)| i)
+        .unwrap()
+}
+
+// accuracy returns the share of the correctly classified examples
+pub fn accuracy<X: AsRef<[f64]>, const OUTPUT_LAYER_SIZE: usize>(
+    thetas: &[Vec<Vec<f64>>],
+    xs: &[X],
+    ys: &[[bool; OUTPUT_LAYER_SIZE]],
+) -> f64 {
+    let number_of_correct = xs
+        .iter()
+        .zip(ys)
+        .filter(|(x, y)| y[predict(thetas, x.as_ref())])
+        .count();
+
+    number_of_correct as f64 / xs.len().max(1) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::training::{accuracy, train};
+
+    #[test]
+    fn test_train_learns_xor() {
+        let xs = [[0.0, 0.0], [0.0, 1.0], [1.0, 0.0], [1.0, 1.0]];
+        let ys = [[true, false], [false, true], [false, true], [true, false]];
+        // fixed weights instead of random ones to make the test deterministic
+        let thetas = [
+            vec![vec![0.1, 0.2, -0.1], vec![-0.1, 0.1, 0.2], vec![0.05, -0.2, 0.1]],
+            vec![vec![0.1, -0.1, 0.2, 0.1], vec![-0.1, 0.2, 0.1, -0.2]],
+        ];
+
+        let thetas = train::<3, 2, _>(thetas, &xs, &ys, 0.0, 2000);
+
+        assert_eq!(accuracy(&thetas, &xs, &ys), 1.0);
+    }
+}
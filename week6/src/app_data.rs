@@ -0,0 +1,31 @@
+use druid::*;
+use crate::constants::*;
+use crate::demo_data::Settings;
+
+#[derive(Clone, Data, Lens, Debug)]
+pub struct AppData {
+    pub polynomial_degree_slider: f64,
+    pub lambda_log10: f64,
+}
+
+impl AppData {
+    pub fn polynomial_degree(&self) -> usize {
+        (self.polynomial_degree_slider.round() as usize).clamp(MIN_POLYNOMIAL_DEGREE, MAX_POLYNOMIAL_DEGREE)
+    }
+
+    // lambda returns the regularization rate, the leftmost position of the slider means 0
+    pub fn lambda(&self) -> f64 {
+        if self.lambda_log10 <= MIN_LAMBDA_LOG10 {
+            0.0
+        } else {
+            10f64.powf(self.lambda_log10)
+        }
+    }
+
+    pub fn settings(&self) -> Settings {
+        Settings {
+            polynomial_degree: self.polynomial_degree(),
+            lambda: self.lambda(),
+        }
+    }
+}
@@ -0,0 +1,40 @@
+use plotters::prelude::{BLUE, GREEN, RED, RGBColor};
+
+// the data imitates ex5: change of the water level in a reservoir(x)
+// and the amount of water flowing out of a dam(y)
+pub const MIN_X_VALUE: f64 = -60.0;
+pub const MAX_X_VALUE: f64 = 50.0;
+pub const MIN_Y_VALUE: f64 = -10.0;
+pub const MAX_Y_VALUE: f64 = 50.0;
+pub const MIN_DATA_X_VALUE: f64 = -50.0;
+pub const MAX_DATA_X_VALUE: f64 = 40.0;
+pub const NOISE_LEVEL: f64 = 2.0;
+pub const TRAINING_SET_SIZE: usize = 12;
+pub const CROSS_VALIDATION_SET_SIZE: usize = 21;
+
+pub const DEFAULT_POLYNOMIAL_DEGREE: usize = 8;
+pub const MIN_POLYNOMIAL_DEGREE: usize = 1;
+pub const MAX_POLYNOMIAL_DEGREE: usize = 12;
+// lambda is chosen on a logarithmic scale, the leftmost position of the slider means no regularization
+pub const DEFAULT_LAMBDA_LOG10: f64 = MIN_LAMBDA_LOG10;
+pub const MIN_LAMBDA_LOG10: f64 = -4.0;
+pub const MAX_LAMBDA_LOG10: f64 = 2.0;
+// singular values below this one are considered to be zero when solving the normal equation
+pub const PSEUDO_INVERSE_EPSILON: f64 = 1e-10;
+pub const LEARNING_UPDATE_PERIOD: u64 = 50;
+
+pub const REFRESH_PERIOD: u64 = 10;
+pub const WINDOW_WIDTH: f64 = 800.0;
+pub const WINDOW_HEIGHT: f64 = 800.0;
+pub const FIT_VISUALIZER_HEIGHT: f64 = WINDOW_WIDTH / 2.0;
+pub const LEARNING_CURVE_VISUALIZER_HEIGHT: f64 = WINDOW_WIDTH / 3.0;
+pub const LEARNING_CURVE_MAX_ERROR: f64 = 100.0;
+pub const FONT: (&str, i32) = ("sans-serif", 20);
+pub const CHART_MARGIN: i32 = 5;
+pub const LEGEND_SIZE: i32 = 30;
+pub const POINT_SIZE: i32 = 3;
+pub const FIT_PLOTTING_PRECISION: f64 = 5.0;
+pub const CONTROL_WIDTH: f64 = 200.0;
+pub const TRAINING_SET_COLOR: RGBColor = RED;
+pub const CROSS_VALIDATION_SET_COLOR: RGBColor = GREEN;
+pub const FIT_COLOR: RGBColor = BLUE;
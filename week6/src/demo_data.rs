@@ -0,0 +1,35 @@
+use crate::learning::PolynomialRegression;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Settings {
+    pub polynomial_degree: usize,
+    pub lambda: f64,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct LearningCurvePoint {
+    pub number_of_examples: usize,
+    pub train_error: f64,
+    pub cv_error: f64,
+}
+
+// Fit is the result of training with some particular settings
+#[derive(Debug)]
+pub struct Fit {
+    pub settings: Settings,
+    pub model: PolynomialRegression,
+    pub train_error: f64,
+    pub cv_error: f64,
+    pub learning_curve: Vec<LearningCurvePoint>,
+}
+
+#[derive(Debug)]
+pub struct DemoData {
+    pub train_xs: Vec<f64>,
+    pub train_ys: Vec<f64>,
+    pub cv_xs: Vec<f64>,
+    pub cv_ys: Vec<f64>,
+    // settings chosen in the UI, the learning thread refits the model when they change
+    pub settings: Settings,
+    pub fit: Option<Fit>,
+}
@@ -0,0 +1,105 @@
+use druid::widget::prelude::*;
+use std::sync::{Arc, Mutex};
+use plotters::prelude::*;
+use crate::demo_data::DemoData;
+use crate::constants::*;
+use std::error::Error;
+use crate::app_data::AppData;
+use helpers::canvas::Drawer;
+use helpers::plotters::two_d::split_into_visible_segments;
+
+// FitVisualizer shows the training and cross validation sets and the fitted polynomial
+pub struct FitVisualizer {
+    data: Arc<Mutex<DemoData>>,
+}
+
+impl FitVisualizer {
+    pub fn new(data: Arc<Mutex<DemoData>>) -> FitVisualizer {
+        FitVisualizer { data }
+    }
+}
+
+impl Drawer<AppData> for FitVisualizer {
+    fn draw_demo_data(&self, buf: &mut [u8], width: usize, height: usize, _app_data: &AppData)
+                      -> Result<(), Box<dyn Error>> {
+        let root = BitMapBackend::with_buffer(
+            buf, (width as u32, height as u32),
+        ).into_drawing_area();
+
+        root.fill(&WHITE)?;
+
+        let data = self.data.lock().unwrap();
+        let caption = match &data.fit {
+            Some(fit) => format!(
+                "train error: {:.3}, cross validation error: {:.3}", fit.train_error, fit.cv_error,
+            ),
+            None => "training...".to_string(),
+        };
+
+        let mut chart = ChartBuilder::on(&root)
+            .caption(caption, FONT.into_font())
+            .margin(CHART_MARGIN)
+            .x_label_area_size(LEGEND_SIZE)
+            .y_label_area_size(LEGEND_SIZE)
+            .build_cartesian_2d(MIN_X_VALUE..MAX_X_VALUE, MIN_Y_VALUE..MAX_Y_VALUE)?;
+
+        chart.configure_mesh().draw()?;
+
+        chart
+            .draw_series(
+                data.train_xs.iter().zip(&data.train_ys).map(
+                    |(x, y)| Cross::new((*x, *y), POINT_SIZE, TRAINING_SET_COLOR.stroke_width(2)),
+                ),
+            )?
+            .label("training set")
+            .legend(|(x, y)| Cross::new((x, y), POINT_SIZE, TRAINING_SET_COLOR.stroke_width(2)));
+
+        chart
+            .draw_series(
+                data.cv_xs.iter().zip(&data.cv_ys).map(
+                    |(x, y)| Circle::new((*x, *y), POINT_SIZE, CROSS_VALIDATION_SET_COLOR.filled()),
+                ),
+            )?
+            .label("cross validation set")
+            .legend(|(x, y)| Circle::new((x, y), POINT_SIZE, CROSS_VALIDATION_SET_COLOR.filled()));
+
+        // the fitted polynomial
+        if let Some(fit) = &data.fit {
+            let number_of_points = ((MAX_X_VALUE - MIN_X_VALUE) * FIT_PLOTTING_PRECISION) as usize;
+            let segments = split_into_visible_segments(
+                (0..=number_of_points)
+                    .map(|i| MIN_X_VALUE + i as f64 / FIT_PLOTTING_PRECISION)
+                    .map(|x| (x, fit.model.predict(x))),
+                MIN_Y_VALUE,
+                MAX_Y_VALUE,
+            );
+
+            for (i, segment) in segments.into_iter().enumerate() {
+                let series = chart.draw_series(LineSeries::new(segment, FIT_COLOR.stroke_width(2)))?;
+                if i == 0 {
+                    series
+                        .label(format!(
+                            "degree {}, lambda {:.4}",
+                            fit.settings.polynomial_degree, fit.settings.lambda,
+                        ))
+                        .legend(|(x, y)| {
+                            PathElement::new(vec![(x, y), (x + LEGEND_SIZE, y)], FIT_COLOR.stroke_width(2))
+                        });
+                }
+            }
+        }
+
+        chart
+            .configure_series_labels()
+            .position(SeriesLabelPosition::UpperLeft)
+            .background_style(WHITE.mix(0.8))
+            .border_style(BLACK)
+            .draw()?;
+
+        Ok(())
+    }
+
+    fn get_size(&self) -> Size {
+        Size::new(WINDOW_WIDTH, FIT_VISUALIZER_HEIGHT)
+    }
+}
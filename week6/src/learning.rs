@@ -0,0 +1,188 @@
+use crate::constants::*;
+use crate::demo_data::{DemoData, Fit, LearningCurvePoint, Settings};
+use nalgebra::{DMatrix, DVector};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+pub fn learning_thread(data: Arc<Mutex<DemoData>>) {
+    loop {
+        {
+            let mut data = data.lock().unwrap();
+            let settings = data.settings;
+            let is_fitted = match &data.fit {
+                Some(fit) => fit.settings == settings,
+                None => false,
+            };
+
+            if !is_fitted {
+                data.fit = Some(fit(&data, settings));
+            }
+        }
+
+        thread::sleep(Duration::from_millis(LEARNING_UPDATE_PERIOD));
+    }
+}
+
+// fit trains the model on the whole training set and builds the learning curve
+// by training on the first 1, 2, ..., m examples
+fn fit(data: &DemoData, settings: Settings) -> Fit {
+    // the features are always normalized using the whole training set
+    // so that all the models from the learning curve are comparable
+    let normalization = FeatureNormalization::new(&data.train_xs, settings.polynomial_degree);
+    let model = PolynomialRegression::train(
+        &data.train_xs, &data.train_ys, settings.lambda, normalization.clone(),
+    );
+
+    let learning_curve = (1..=data.train_xs.len())
+        .map(|number_of_examples| {
+            let xs = &data.train_xs[..number_of_examples];
+            let ys = &data.train_ys[..number_of_examples];
+            let model = PolynomialRegression::train(xs, ys, settings.lambda, normalization.clone());
+
+            LearningCurvePoint {
+                number_of_examples,
+                train_error: model.error(xs, ys),
+                cv_error: model.error(&data.cv_xs, &data.cv_ys),
+            }
+        })
+        .collect();
+
+    Fit {
+        settings,
+        train_error: model.error(&data.train_xs, &data.train_ys),
+        cv_error: model.error(&data.cv_xs, &data.cv_ys),
+        model,
+        learning_curve,
+    }
+}
+
+// polynomial_features maps x into `[x, x ^ 2, ..., x ^ degree]`
+pub fn polynomial_features(x: f64, degree: usize) -> Vec<f64> {
+    (1..=degree).map(|power| x.powi(power as i32)).collect()
+}
+
+// FeatureNormalization remembers mean and standard deviation of every polynomial feature
+#[derive(Debug, Clone)]
+pub struct FeatureNormalization {
+    degree: usize,
+    mu: Vec<f64>,
+    sigma: Vec<f64>,
+}
+
+impl FeatureNormalization {
+    pub fn new(xs: &[f64], degree: usize) -> FeatureNormalization {
+        let features: Vec<Vec<f64>> = xs.iter().map(|x| polynomial_features(*x, degree)).collect();
+        let n = features.len() as f64;
+
+        let mut mu = vec![0.0; degree];
+        let mut sigma = vec![0.0; degree];
+
+        for feature_i in 0..degree {
+            mu[feature_i] = features.iter().map(|f| f[feature_i]).sum::<f64>() / n;
+
+            let variance = features
+                .iter()
+                .map(|f| (f[feature_i] - mu[feature_i]).powi(2))
+                .sum::<f64>() / (n - 1.0).max(1.0);
+
+            // a constant feature stays as it is
+            sigma[feature_i] = if variance > 0.0 { variance.sqrt() } else { 1.0 };
+        }
+
+        FeatureNormalization { degree, mu, sigma }
+    }
+
+    // design_row returns the normalized features of x with the bias unit in front
+    pub fn design_row(&self, x: f64) -> Vec<f64> {
+        let mut row = vec![1.0];
+
+        for (i, feature) in polynomial_features(x, self.degree).iter().enumerate() {
+            row.push((feature - self.mu[i]) / self.sigma[i]);
+        }
+
+        row
+    }
+}
+
+// PolynomialRegression is a regularized linear regression over normalized polynomial features
+#[derive(Debug, Clone)]
+pub struct PolynomialRegression {
+    normalization: FeatureNormalization,
+    theta: Vec<f64>,
+}
+
+impl PolynomialRegression {
+    // train solves the regularized normal equation
+    // `theta = (x_transpose * x + lambda * L)_inverse * x_transpose * y`
+    // where L is the identity matrix with zero in the top left corner(bias isn't regularized)
+    pub fn train(
+        xs: &[f64], ys: &[f64], lambda: f64, normalization: FeatureNormalization,
+    ) -> PolynomialRegression {
+        let n_columns = normalization.degree + 1;
+        let rows: Vec<f64> = xs.iter().flat_map(|x| normalization.design_row(*x)).collect();
+        let x = DMatrix::from_row_slice(xs.len(), n_columns, &rows);
+        let y = DVector::from_column_slice(ys);
+
+        let mut regularization = DMatrix::identity(n_columns, n_columns) * lambda;
+        regularization[(0, 0)] = 0.0;
+
+        let x_transpose = x.transpose();
+        let theta = (&x_transpose * &x + regularization)
+            .pseudo_inverse(PSEUDO_INVERSE_EPSILON)
+            .unwrap() * x_transpose * y;
+
+        PolynomialRegression {
+            normalization,
+            theta: theta.iter().copied().collect(),
+        }
+    }
+
+    pub fn predict(&self, x: f64) -> f64 {
+        self.normalization
+            .design_row(x)
+            .iter()
+            .zip(&self.theta)
+            .map(|(feature, theta)| feature * theta)
+            .sum()
+    }
+
+    // error calculates the unregularized squared error cost `1 / 2m * sum((h(x) - y) ^ 2)`
+    pub fn error(&self, xs: &[f64], ys: &[f64]) -> f64 {
+        let mut result = 0.0;
+
+        for (x, y) in xs.iter().zip(ys) {
+            result += (self.predict(*x) - y).powi(2);
+        }
+
+        result / (2.0 * xs.len() as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::learning::{FeatureNormalization, PolynomialRegression};
+
+    #[test]
+    fn test_polynomial_regression_fits_a_parabola() {
+        let xs: Vec<f64> = (-5..=5).map(|x| x as f64).collect();
+        let ys: Vec<f64> = xs.iter().map(|x| 3.0 * x * x - 2.0 * x + 1.0).collect();
+
+        let model = PolynomialRegression::train(&xs, &ys, 0.0, FeatureNormalization::new(&xs, 2));
+
+        assert!(model.error(&xs, &ys) < 1e-9);
+        assert!(relative_eq!(model.predict(10.0), 281.0, epsilon = 1e-6));
+    }
+
+    #[test]
+    fn test_regularization_increases_training_error() {
+        let xs: Vec<f64> = (-5..=5).map(|x| x as f64).collect();
+        let ys: Vec<f64> = xs.iter().map(|x| x * x * x).collect();
+
+        let normalization = FeatureNormalization::new(&xs, 3);
+        let model = PolynomialRegression::train(&xs, &ys, 0.0, normalization.clone());
+        let regularized_model = PolynomialRegression::train(&xs, &ys, 10.0, normalization);
+
+        assert!(regularized_model.error(&xs, &ys) > model.error(&xs, &ys));
+    }
+}
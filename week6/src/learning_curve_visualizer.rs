@@ -0,0 +1,92 @@
+use druid::widget::prelude::*;
+use std::sync::{Arc, Mutex};
+use plotters::prelude::*;
+use crate::demo_data::DemoData;
+use crate::constants::*;
+use std::error::Error;
+use crate::app_data::AppData;
+use helpers::canvas::Drawer;
+use helpers::plotters::two_d::split_into_visible_segments;
+
+// LearningCurveVisualizer shows how train and cross validation errors change
+// with the number of training examples
+pub struct LearningCurveVisualizer {
+    data: Arc<Mutex<DemoData>>,
+}
+
+impl LearningCurveVisualizer {
+    pub fn new(data: Arc<Mutex<DemoData>>) -> LearningCurveVisualizer {
+        LearningCurveVisualizer { data }
+    }
+}
+
+impl Drawer<AppData> for LearningCurveVisualizer {
+    fn draw_demo_data(&self, buf: &mut [u8], width: usize, height: usize, _app_data: &AppData)
+                      -> Result<(), Box<dyn Error>> {
+        let root = BitMapBackend::with_buffer(
+            buf, (width as u32, height as u32),
+        ).into_drawing_area();
+
+        root.fill(&WHITE)?;
+
+        let data = self.data.lock().unwrap();
+
+        let mut chart = ChartBuilder::on(&root)
+            .caption("Learning curve: error vs number of training examples", FONT.into_font())
+            .margin(CHART_MARGIN)
+            .x_label_area_size(LEGEND_SIZE)
+            .y_label_area_size(LEGEND_SIZE)
+            .build_cartesian_2d(
+                0f64..(data.train_xs.len() + 1) as f64, 0f64..LEARNING_CURVE_MAX_ERROR,
+            )?;
+
+        chart.configure_mesh().draw()?;
+
+        let fit = match &data.fit {
+            Some(fit) => fit,
+            None => return Ok(()),
+        };
+
+        let curves = [
+            (
+                "train",
+                TRAINING_SET_COLOR,
+                fit.learning_curve.iter().map(|p| (p.number_of_examples as f64, p.train_error)).collect::<Vec<_>>(),
+            ),
+            (
+                "cross validation",
+                CROSS_VALIDATION_SET_COLOR,
+                fit.learning_curve.iter().map(|p| (p.number_of_examples as f64, p.cv_error)).collect::<Vec<_>>(),
+            ),
+        ];
+
+        for (label, color, points) in curves.iter() {
+            let segments = split_into_visible_segments(points.iter().copied(), 0.0, LEARNING_CURVE_MAX_ERROR);
+
+            for (i, segment) in segments.into_iter().enumerate() {
+                let series = chart.draw_series(LineSeries::new(segment, color.stroke_width(2)))?;
+                if i == 0 {
+                    let color = *color;
+                    series
+                        .label(*label)
+                        .legend(move |(x, y)| {
+                            PathElement::new(vec![(x, y), (x + LEGEND_SIZE, y)], color.stroke_width(2))
+                        });
+                }
+            }
+        }
+
+        chart
+            .configure_series_labels()
+            .position(SeriesLabelPosition::UpperRight)
+            .background_style(WHITE.mix(0.8))
+            .border_style(BLACK)
+            .draw()?;
+
+        Ok(())
+    }
+
+    fn get_size(&self) -> Size {
+        Size::new(WINDOW_WIDTH, LEARNING_CURVE_VISUALIZER_HEIGHT)
+    }
+}
@@ -0,0 +1,152 @@
+#[macro_use]
+extern crate approx;
+
+mod app_data;
+mod constants;
+mod demo_data;
+mod fit_visualizer;
+mod learning;
+mod learning_curve_visualizer;
+mod settings_controller;
+
+use crate::app_data::AppData;
+use crate::constants::*;
+use crate::demo_data::DemoData;
+use crate::fit_visualizer::FitVisualizer;
+use crate::learning::learning_thread;
+use crate::learning_curve_visualizer::LearningCurveVisualizer;
+use crate::settings_controller::SettingsController;
+use druid::widget::*;
+use druid::*;
+use helpers::canvas::{Canvas, Drawer};
+use helpers::export::{ExportArguments, EXPORT_USAGE};
+use rand::rngs::ThreadRng;
+use rand::Rng;
+use std::process;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+const USAGE: &str = "usage: bias-variance-demo";
+
+fn main() {
+    let (export, args) = match ExportArguments::parse(std::env::args().skip(1).collect()) {
+        Ok(arguments) => arguments,
+        Err(error) => {
+            eprintln!("{}\n{}\n{}", error, USAGE, EXPORT_USAGE);
+            process::exit(2);
+        }
+    };
+    if let Some(arg) = args.first() {
+        eprintln!("unexpected argument {}\n{}\n{}", arg, USAGE, EXPORT_USAGE);
+        process::exit(2);
+    }
+
+    let mut rng = rand::thread_rng();
+    let (train_xs, train_ys) = generate_water_flow_data(TRAINING_SET_SIZE, &mut rng);
+    let (cv_xs, cv_ys) = generate_water_flow_data(CROSS_VALIDATION_SET_SIZE, &mut rng);
+
+    let app_data = AppData {
+        polynomial_degree_slider: DEFAULT_POLYNOMIAL_DEGREE as f64,
+        lambda_log10: DEFAULT_LAMBDA_LOG10,
+    };
+
+    let data = Arc::new(Mutex::new(DemoData {
+        train_xs,
+        train_ys,
+        cv_xs,
+        cv_ys,
+        settings: app_data.settings(),
+        fit: None,
+    }));
+
+    let thread_data = data.clone();
+    thread::spawn(move || learning_thread(thread_data));
+
+    if let Some(export) = export {
+        let drawers: Vec<Box<dyn Drawer<AppData>>> = vec![
+            Box::new(FitVisualizer::new(data.clone())),
+            Box::new(LearningCurveVisualizer::new(data)),
+        ];
+        if let Err(error) = export.run(&drawers, &app_data) {
+            eprintln!("error: {}", error);
+            process::exit(1);
+        }
+        return;
+    }
+
+    let window = WindowDesc::new(get_ui_builder(data))
+        .window_size(Size::new(WINDOW_WIDTH, WINDOW_HEIGHT))
+        .title(
+            LocalizedString::new("Bias Variance Demo")
+                .with_placeholder("bias-variance-demo"),
+        );
+
+    AppLauncher::with_window(window)
+        .use_simple_logger()
+        .launch(app_data)
+        .expect("launch failed");
+}
+
+// water_flow is the dependency the data is generated from, it's almost flat
+// for negative changes of the water level and almost linear for positive ones(just like in ex5)
+fn water_flow(x: f64) -> f64 {
+    2.5 + 6.0 * (1.0 + ((x - 5.0) / 6.0).exp()).ln()
+}
+
+fn generate_water_flow_data(number_of_points: usize, rng: &mut ThreadRng) -> (Vec<f64>, Vec<f64>) {
+    let mut xs = Vec::new();
+    let mut ys = Vec::new();
+
+    for _ in 0..number_of_points {
+        let x = rng.gen_range(MIN_DATA_X_VALUE..MAX_DATA_X_VALUE);
+
+        xs.push(x);
+        ys.push(water_flow(x) + rng.gen_range(-NOISE_LEVEL..NOISE_LEVEL));
+    }
+
+    (xs, ys)
+}
+
+fn get_ui_builder(
+    data: Arc<Mutex<DemoData>>,
+) -> impl Fn() -> ControllerHost<Flex<AppData>, SettingsController> {
+    move || {
+        Flex::<AppData>::column()
+            .with_child(Canvas::<AppData>::new(
+                Duration::from_millis(REFRESH_PERIOD),
+                Box::new(FitVisualizer::new(data.clone())),
+            ))
+            .with_child(Canvas::<AppData>::new(
+                Duration::from_millis(REFRESH_PERIOD),
+                Box::new(LearningCurveVisualizer::new(data.clone())),
+            ))
+            .with_child(
+                Flex::row()
+                    .with_child(
+                        Label::new(|app_data: &AppData, _env: &Env| {
+                            format!("polynomial degree: {}", app_data.polynomial_degree())
+                        }).fix_width(CONTROL_WIDTH),
+                    )
+                    .with_child(
+                        Slider::new()
+                            .with_range(MIN_POLYNOMIAL_DEGREE as f64, MAX_POLYNOMIAL_DEGREE as f64)
+                            .lens(AppData::polynomial_degree_slider),
+                    ),
+            )
+            .with_child(
+                Flex::row()
+                    .with_child(
+                        Label::new(|app_data: &AppData, _env: &Env| {
+                            format!("lambda: {:.4}", app_data.lambda())
+                        }).fix_width(CONTROL_WIDTH),
+                    )
+                    .with_child(
+                        Slider::new()
+                            .with_range(MIN_LAMBDA_LOG10, MAX_LAMBDA_LOG10)
+                            .lens(AppData::lambda_log10),
+                    ),
+            )
+            .controller(SettingsController::new(data.clone()))
+    }
+}
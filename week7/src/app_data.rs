@@ -0,0 +1,50 @@
+use druid::*;
+use helpers::svm::Kernel;
+use crate::constants::*;
+use helpers::training_control::TrainingControlData;
+use crate::demo_data::Settings;
+
+#[derive(Clone, Copy, Data, PartialEq, Debug)]
+pub enum KernelType {
+    Linear,
+    Polynomial,
+    Gaussian,
+}
+
+#[derive(Clone, Data, Lens, Debug)]
+pub struct AppData {
+    pub kernel_type: KernelType,
+    pub c_log10: f64,
+    pub sigma_log10: f64,
+    // the parameter search is running, the button starts only one at a time
+    pub is_searching: bool,
+    pub training_control: TrainingControlData,
+}
+
+impl AppData {
+    pub fn c(&self) -> f64 {
+        10f64.powf(self.c_log10)
+    }
+
+    pub fn sigma(&self) -> f64 {
+        10f64.powf(self.sigma_log10)
+    }
+
+    pub fn kernel(&self) -> Kernel {
+        match self.kernel_type {
+            KernelType::Linear => Kernel::Linear,
+            KernelType::Polynomial => Kernel::Polynomial {
+                degree: POLYNOMIAL_KERNEL_DEGREE,
+                coef0: POLYNOMIAL_KERNEL_COEF0,
+            },
+            KernelType::Gaussian => Kernel::Gaussian { sigma: self.sigma() },
+        }
+    }
+
+    pub fn settings(&self) -> Settings {
+        Settings {
+            kernel: self.kernel(),
+            c: self.c(),
+        }
+    }
+}
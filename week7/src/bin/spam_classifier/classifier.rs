@@ -0,0 +1,156 @@
+use crate::constants::*;
+use helpers::math::sigmoid;
+use helpers::svm::to_svm_label;
+use rand::seq::SliceRandom;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ClassifierType {
+    Svm,
+    LogisticRegression,
+}
+
+// LinearClassifier predicts spam when `theta_transpose * x >= 0`, both linear SVM and
+// logistic regression end up with such a model, so the weights of the words can be compared
+pub struct LinearClassifier {
+    pub theta: Vec<f64>,
+}
+
+impl LinearClassifier {
+    // train_svm trains the linear SVM by dual coordinate descent like LIBLINEAR, ys are 1 for spam and 0 otherwise.
+    // Unlike SMO with a kernel matrix it keeps only theta, so a step costs as much as the number of the words
+    // in the email and the whole training set of ex6 fits. The bias is the weight of a constant feature,
+    // so it's regularized too
+    pub fn train_svm(xs: &[Vec<f64>], ys: &[f64], c: f64) -> LinearClassifier {
+        let n = xs.first().map_or(0, |x| x.len());
+        // the features are binary and mostly zeros, so only the non zero ones are kept, 0 is the constant feature
+        let examples: Vec<Vec<(usize, f64)>> = xs
+            .iter()
+            .map(|x| {
+                std::iter::once((0, 1.0))
+                    .chain(x.iter().enumerate().filter(|(_, value)| **value != 0.0).map(|(i, value)| (i + 1, *value)))
+                    .collect()
+            })
+            .collect();
+        let ys: Vec<f64> = ys.iter().map(|y| to_svm_label(*y)).collect();
+        let squared_norms: Vec<f64> = examples.iter().map(|x| x.iter().map(|(_, value)| value * value).sum()).collect();
+
+        let mut alphas = vec![0.0; examples.len()];
+        let mut classifier = LinearClassifier { theta: vec![0.0; n + 1] };
+        let mut order: Vec<usize> = (0..examples.len()).collect();
+
+        for _ in 0..SVM_MAX_EPOCHS {
+            order.shuffle(&mut rand::thread_rng());
+            let mut max_violation: f64 = 0.0;
+
+            for i in order.iter().copied() {
+                let x = &examples[i];
+                let theta = &mut classifier.theta;
+                let gradient = ys[i] * x.iter().map(|(j, value)| theta[*j] * value).sum::<f64>() - 1.0;
+                // alpha can't leave [0, C], so the gradient pointing outside doesn't count
+                let projected_gradient = if alphas[i] == 0.0 {
+                    gradient.min(0.0)
+                } else if alphas[i] == c {
+                    gradient.max(0.0)
+                } else {
+                    gradient
+                };
+                max_violation = max_violation.max(projected_gradient.abs());
+                if projected_gradient == 0.0 {
+                    continue;
+                }
+
+                let alpha = (alphas[i] - gradient / squared_norms[i]).clamp(0.0, c);
+                for (j, value) in x {
+                    theta[*j] += (alpha - alphas[i]) * ys[i] * value;
+                }
+                alphas[i] = alpha;
+            }
+
+            if max_violation < SVM_TOLERANCE {
+                break;
+            }
+        }
+
+        classifier
+    }
+
+    // train_logistic_regression minimizes regularized logistic regression cost by gradient descent
+    pub fn train_logistic_regression(xs: &[Vec<f64>], ys: &[f64], lambda: f64) -> LinearClassifier {
+        let m = xs.len() as f64;
+        let n = xs.first().map_or(0, |x| x.len());
+        let mut classifier = LinearClassifier { theta: vec![0.0; n + 1] };
+
+        for _ in 0..LOGISTIC_REGRESSION_ITERATIONS {
+            let mut gradient = vec![0.0; n + 1];
+
+            for (x, y) in xs.iter().zip(ys) {
+                let error = sigmoid(classifier.decision_function(x)) - y;
+
+                gradient[0] += error;
+                // the features are binary and mostly zeros
+                for (i, value) in x.iter().enumerate().filter(|(_, value)| **value != 0.0) {
+                    gradient[i + 1] += error * value;
+                }
+            }
+
+            for (i, theta_value) in classifier.theta.iter_mut().enumerate() {
+                // the bias isn't regularized
+                let regularization = if i > 0 { lambda * *theta_value } else { 0.0 };
+                *theta_value -= LOGISTIC_REGRESSION_LEARNING_RATE * (gradient[i] + regularization) / m;
+            }
+        }
+
+        classifier
+    }
+
+    pub fn decision_function(&self, x: &[f64]) -> f64 {
+        self.theta[0] + self.theta[1..].iter().zip(x).map(|(theta, x)| theta * x).sum::<f64>()
+    }
+
+    pub fn predict(&self, x: &[f64]) -> bool {
+        self.decision_function(x) >= 0.0
+    }
+
+    // accuracy returns the fraction of the correctly classified examples
+    pub fn accuracy(&self, xs: &[Vec<f64>], ys: &[f64]) -> f64 {
+        let number_of_correct = xs
+            .iter()
+            .zip(ys)
+            .filter(|(x, y)| self.predict(x) == (**y > 0.5))
+            .count();
+
+        number_of_correct as f64 / xs.len().max(1) as f64
+    }
+
+    // top_features returns the indices of the features with the biggest weights,
+    // they are the most indicative of spam
+    pub fn top_features(&self, features: &[usize], number_of_features: usize) -> Vec<usize> {
+        let mut features = features.to_vec();
+        features.sort_unstable();
+        features.dedup();
+        features.sort_by(|a, b| self.theta[b + 1].total_cmp(&self.theta[a + 1]));
+        features.truncate(number_of_features);
+
+        features
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::classifier::LinearClassifier;
+
+    #[test]
+    fn test_svm() {
+        // the first word means spam and the second one doesn't
+        let xs = vec![vec![1.0, 0.0, 1.0], vec![1.0, 0.0, 0.0], vec![0.0, 1.0, 1.0], vec![0.0, 1.0, 0.0]];
+        let ys = [1.0, 1.0, 0.0, 0.0];
+
+        let classifier = LinearClassifier::train_svm(&xs, &ys, 1.0);
+
+        assert_eq!(classifier.accuracy(&xs, &ys), 1.0);
+        assert_eq!(classifier.top_features(&[2, 1, 0], 1), vec![0]);
+
+        let classifier = LinearClassifier { theta: vec![0.0, 1.0, f64::NAN, -1.0] };
+        assert_eq!(classifier.top_features(&[0, 1, 2], 3).len(), 3);
+    }
+}
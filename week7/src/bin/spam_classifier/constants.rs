@@ -0,0 +1,11 @@
+// ex6 trains the linear SVM with C = 0.1
+pub const DEFAULT_C: f64 = 0.1;
+pub const DEFAULT_NUMBER_OF_TOP_WORDS: usize = 15;
+// the share of the emails from the training directories used to measure the accuracy
+pub const TEST_SET_SHARE: f64 = 0.2;
+// the linear SVM stops when no alpha violates the optimality conditions more than the tolerance
+pub const SVM_TOLERANCE: f64 = 1e-3;
+pub const SVM_MAX_EPOCHS: usize = 1000;
+pub const LOGISTIC_REGRESSION_LAMBDA: f64 = 1.0;
+pub const LOGISTIC_REGRESSION_LEARNING_RATE: f64 = 1.0;
+pub const LOGISTIC_REGRESSION_ITERATIONS: usize = 1000;
@@ -0,0 +1,233 @@
+// spam_classifier is the spam classifier from ex6: it trains a linear SVM(or logistic regression)
+// on the emails from the spam and ham directories or on the feature vectors from spamTrain.mat
+// and classifies the given emails with it
+mod classifier;
+mod constants;
+mod porter_stemmer;
+mod preprocessing;
+mod vocabulary;
+
+use crate::classifier::{ClassifierType, LinearClassifier};
+use crate::constants::*;
+use crate::preprocessing::process_email;
+use crate::vocabulary::Vocabulary;
+use helpers::mat_file::{get_matrix_from_mat_file, load_mat_file};
+use rand::seq::SliceRandom;
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process;
+
+const USAGE: &str = "usage: spam_classifier --vocabulary <vocab.txt> (--spam <dir> --ham <dir> | \
+--train <spamTrain.mat> [--test <spamTest.mat>]) [--classifier svm|logistic] [--c <C>] \
+[--top <number of words>] <email.txt>...";
+
+// Example is the feature vector of an email and its label
+type Example = (Vec<f64>, f64);
+
+// TrainingSet is where the examples come from
+enum TrainingSet {
+    // the emails are split into the training and the test sets
+    Emails { spam_dir: PathBuf, ham_dir: PathBuf },
+    // the feature vectors are already made by processEmail.m and emailFeatures.m, like spamTrain.mat from ex6
+    MatFiles { train: PathBuf, test: Option<PathBuf> },
+}
+
+struct Arguments {
+    vocabulary: PathBuf,
+    training_set: TrainingSet,
+    classifier_type: ClassifierType,
+    c: f64,
+    number_of_top_words: usize,
+    emails: Vec<PathBuf>,
+}
+
+fn main() {
+    let arguments = match parse_arguments(std::env::args().skip(1).collect()) {
+        Ok(arguments) => arguments,
+        Err(error) => {
+            eprintln!("{}\n{}", error, USAGE);
+            process::exit(2);
+        }
+    };
+
+    if let Err(error) = run(&arguments) {
+        eprintln!("error: {}", error);
+        process::exit(1);
+    }
+}
+
+fn parse_arguments(args: Vec<String>) -> Result<Arguments, Box<dyn Error>> {
+    let mut vocabulary = None;
+    let mut spam_dir = None;
+    let mut ham_dir = None;
+    let mut train = None;
+    let mut test = None;
+    let mut classifier_type = ClassifierType::Svm;
+    let mut c = DEFAULT_C;
+    let mut number_of_top_words = DEFAULT_NUMBER_OF_TOP_WORDS;
+    let mut emails = Vec::new();
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("{} requires a value", arg));
+
+        match arg.as_str() {
+            "--vocabulary" => vocabulary = Some(PathBuf::from(value()?)),
+            "--spam" => spam_dir = Some(PathBuf::from(value()?)),
+            "--ham" => ham_dir = Some(PathBuf::from(value()?)),
+            "--train" => train = Some(PathBuf::from(value()?)),
+            "--test" => test = Some(PathBuf::from(value()?)),
+            "--classifier" => {
+                classifier_type = match value()?.as_str() {
+                    "svm" => ClassifierType::Svm,
+                    "logistic" => ClassifierType::LogisticRegression,
+                    other => return Err(format!("unknown classifier {}", other).into()),
+                }
+            }
+            "--c" => c = value()?.parse()?,
+            "--top" => number_of_top_words = value()?.parse()?,
+            _ if arg.starts_with("--") => return Err(format!("unknown option {}", arg).into()),
+            _ => emails.push(PathBuf::from(arg)),
+        }
+    }
+
+    let training_set = match (spam_dir, ham_dir, train) {
+        (Some(spam_dir), Some(ham_dir), None) if test.is_none() => TrainingSet::Emails { spam_dir, ham_dir },
+        (None, None, Some(train)) => TrainingSet::MatFiles { train, test },
+        (_, _, Some(_)) => return Err("--train can't be used with --spam and --ham".into()),
+        _ if test.is_some() => return Err("--test requires --train".into()),
+        _ => return Err("either --spam and --ham or --train is required".into()),
+    };
+
+    Ok(Arguments {
+        vocabulary: vocabulary.ok_or("--vocabulary is required")?,
+        training_set,
+        classifier_type,
+        c,
+        number_of_top_words,
+        emails,
+    })
+}
+
+fn run(arguments: &Arguments) -> Result<(), Box<dyn Error>> {
+    let vocabulary = Vocabulary::load(&arguments.vocabulary)?;
+
+    let (train_examples, test_examples) = match &arguments.training_set {
+        TrainingSet::Emails { spam_dir, ham_dir } => {
+            let mut examples = load_emails(spam_dir, 1.0, &vocabulary)?;
+            examples.extend(load_emails(ham_dir, 0.0, &vocabulary)?);
+            examples.shuffle(&mut rand::thread_rng());
+
+            let test_set_size = (examples.len() as f64 * TEST_SET_SHARE) as usize;
+            let train_examples = examples.split_off(test_set_size);
+            (train_examples, examples)
+        }
+        TrainingSet::MatFiles { train, test } => {
+            let train_examples = load_feature_vectors(train, "X", "y", &vocabulary)?;
+            let test_examples = match test {
+                Some(test) => load_feature_vectors(test, "Xtest", "ytest", &vocabulary)?,
+                None => Vec::new(),
+            };
+            (train_examples, test_examples)
+        }
+    };
+    let (train_xs, train_ys): (Vec<Vec<f64>>, Vec<f64>) = train_examples.into_iter().unzip();
+    let (test_xs, test_ys): (Vec<Vec<f64>>, Vec<f64>) = test_examples.into_iter().unzip();
+
+    println!("training on {} emails...", train_xs.len());
+    let classifier = match arguments.classifier_type {
+        ClassifierType::Svm => LinearClassifier::train_svm(&train_xs, &train_ys, arguments.c),
+        ClassifierType::LogisticRegression => {
+            LinearClassifier::train_logistic_regression(&train_xs, &train_ys, LOGISTIC_REGRESSION_LAMBDA)
+        }
+    };
+
+    println!("training accuracy: {:.2}%", classifier.accuracy(&train_xs, &train_ys) * 100.0);
+    if !test_xs.is_empty() {
+        println!("test accuracy: {:.2}%", classifier.accuracy(&test_xs, &test_ys) * 100.0);
+    }
+
+    let all_words: Vec<usize> = (0..vocabulary.len()).collect();
+    println!("top predictors of spam:");
+    print_words(&classifier, &vocabulary, &classifier.top_features(&all_words, arguments.number_of_top_words));
+
+    for email in &arguments.emails {
+        let words = process_email(&read_email(email)?);
+        let x = vocabulary.features(&words);
+        let is_spam = classifier.predict(&x);
+
+        println!(
+            "\n{}: {} (score {:.3})",
+            email.display(),
+            if is_spam { "spam" } else { "not spam" },
+            classifier.decision_function(&x),
+        );
+        println!("the most spammy words of the email:");
+        let top_features = classifier.top_features(&vocabulary.word_indices(&words), arguments.number_of_top_words);
+        print_words(&classifier, &vocabulary, &top_features);
+    }
+
+    Ok(())
+}
+
+// load_emails reads all the files in the directory and turns them into the examples with the label y
+fn load_emails(dir: &Path, y: f64, vocabulary: &Vocabulary) -> Result<Vec<Example>, Box<dyn Error>> {
+    let mut examples = Vec::new();
+
+    for entry in fs::read_dir(dir).map_err(|error| format!("{}: {}", dir.display(), error))? {
+        let path = entry?.path();
+        if path.is_file() {
+            let words = process_email(&read_email(&path)?);
+            examples.push((vocabulary.features(&words), y));
+        }
+    }
+
+    if examples.is_empty() {
+        return Err(format!("{}: there are no emails", dir.display()).into());
+    }
+
+    Ok(examples)
+}
+
+// load_feature_vectors reads the examples from a .mat file like spamTrain.mat or spamTest.mat from ex6,
+// every row of x is the feature vector of an email for the vocabulary and y is 1 for spam and 0 otherwise
+fn load_feature_vectors(
+    path: &Path, x_name: &str, y_name: &str, vocabulary: &Vocabulary,
+) -> Result<Vec<Example>, Box<dyn Error>> {
+    let mat_file = load_mat_file(path)?;
+    let error = |error: Box<dyn Error>| format!("{}: {}", path.display(), error);
+    let x = get_matrix_from_mat_file(&mat_file, x_name).map_err(error)?;
+    let y = get_matrix_from_mat_file(&mat_file, y_name).map_err(error)?;
+
+    if x.ncols() != vocabulary.len() {
+        return Err(format!(
+            "{}: the emails have {} features, but there are {} words in the vocabulary",
+            path.display(), x.ncols(), vocabulary.len(),
+        ).into());
+    }
+    if y.shape() != (x.nrows(), 1) {
+        return Err(format!("{}: {} is {:?}, expected ({}, 1)", path.display(), y_name, y.shape(), x.nrows()).into());
+    }
+    if let Some(label) = y.iter().find(|label| **label != 0.0 && **label != 1.0) {
+        return Err(format!("{}: the label is {}, expected 0 or 1", path.display(), label).into());
+    }
+    if x.nrows() == 0 {
+        return Err(format!("{}: there are no emails", path.display()).into());
+    }
+
+    Ok((0..x.nrows()).map(|i| (x.row(i).iter().copied().collect(), y[i])).collect())
+}
+
+// read_email reads the file ignoring invalid utf-8 since the emails often come in other encodings
+fn read_email(path: &Path) -> Result<String, Box<dyn Error>> {
+    let bytes = fs::read(path).map_err(|error| format!("{}: {}", path.display(), error))?;
+
+    Ok(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+fn print_words(classifier: &LinearClassifier, vocabulary: &Vocabulary, features: &[usize]) {
+    for feature in features {
+        println!("  {:<20} {:.3}", vocabulary.word(*feature), classifier.theta[feature + 1]);
+    }
+}
@@ -0,0 +1,300 @@
+// stem reduces an english word to its stem by the Porter stemming algorithm
+// (https://tartarus.org/martin/PorterStemmer/), the word is expected to be in lowercase.
+// The implementation follows the reference one, so the stems match those from ex6
+pub fn stem(word: &str) -> String {
+    if word.len() <= 2 || !word.bytes().all(|c| c.is_ascii_lowercase()) {
+        return word.to_string();
+    }
+
+    let mut stemmer = Stemmer {
+        b: word.as_bytes().to_vec(),
+        k: word.len() as isize - 1,
+        j: 0,
+    };
+
+    stemmer.step1ab();
+    if stemmer.k > 0 {
+        stemmer.step1c();
+        stemmer.step2();
+        stemmer.step3();
+        stemmer.step4();
+        stemmer.step5();
+    }
+
+    stemmer.b.truncate((stemmer.k + 1) as usize);
+
+    String::from_utf8(stemmer.b).unwrap()
+}
+
+// Stemmer keeps the word being stemmed in b[0..=k], j is the end of the stem
+// before the suffix found by the last call of ends
+struct Stemmer {
+    b: Vec<u8>,
+    k: isize,
+    j: isize,
+}
+
+impl Stemmer {
+    fn at(&self, i: isize) -> u8 {
+        self.b[i as usize]
+    }
+
+    // is_consonant tells if b[i] is a consonant, 'y' is a consonant when it follows a vowel
+    fn is_consonant(&self, i: isize) -> bool {
+        match self.at(i) {
+            b'a' | b'e' | b'i' | b'o' | b'u' => false,
+            b'y' => i == 0 || !self.is_consonant(i - 1),
+            _ => true,
+        }
+    }
+
+    // measure counts the number of vowel-consonant sequences in b[0..=j]:
+    // `<c><v>` gives 0, `<c>vc<v>` gives 1, `<c>vcvc<v>` gives 2 and so on
+    fn measure(&self) -> usize {
+        let mut n = 0;
+        let mut i = 0;
+
+        loop {
+            if i > self.j {
+                return n;
+            }
+            if !self.is_consonant(i) {
+                break;
+            }
+            i += 1;
+        }
+        i += 1;
+
+        loop {
+            loop {
+                if i > self.j {
+                    return n;
+                }
+                if self.is_consonant(i) {
+                    break;
+                }
+                i += 1;
+            }
+            i += 1;
+            n += 1;
+
+            loop {
+                if i > self.j {
+                    return n;
+                }
+                if !self.is_consonant(i) {
+                    break;
+                }
+                i += 1;
+            }
+            i += 1;
+        }
+    }
+
+    fn has_vowel_in_stem(&self) -> bool {
+        (0..=self.j).any(|i| !self.is_consonant(i))
+    }
+
+    fn is_double_consonant(&self, i: isize) -> bool {
+        i >= 1 && self.at(i) == self.at(i - 1) && self.is_consonant(i)
+    }
+
+    // is_cvc tells if b[i - 2..=i] is consonant-vowel-consonant and the last one is not w, x or y,
+    // it's used to restore an 'e' at the end of a short word: cav(e), lov(e), hop(e), crim(e)
+    fn is_cvc(&self, i: isize) -> bool {
+        if i < 2 || !self.is_consonant(i) || self.is_consonant(i - 1) || !self.is_consonant(i - 2) {
+            return false;
+        }
+
+        !matches!(self.at(i), b'w' | b'x' | b'y')
+    }
+
+    // ends tells if b[0..=k] ends with the suffix and sets j to the end of the stem
+    fn ends(&mut self, suffix: &str) -> bool {
+        let length = suffix.len() as isize;
+        if length > self.k + 1 || &self.b[(self.k - length + 1) as usize..=self.k as usize] != suffix.as_bytes() {
+            return false;
+        }
+
+        self.j = self.k - length;
+
+        true
+    }
+
+    // set_to replaces b[j + 1..=k] with the suffix
+    fn set_to(&mut self, suffix: &str) {
+        self.b.truncate((self.j + 1) as usize);
+        self.b.extend_from_slice(suffix.as_bytes());
+        self.k = self.j + suffix.len() as isize;
+    }
+
+    fn replace_if_measure_is_positive(&mut self, suffix: &str) {
+        if self.measure() > 0 {
+            self.set_to(suffix);
+        }
+    }
+
+    // replace_first_ending replaces the first matching ending with its replacement
+    // if the stem before it has positive measure
+    fn replace_first_ending(&mut self, replacements: &[(&str, &str)]) {
+        for (ending, replacement) in replacements {
+            if self.ends(ending) {
+                self.replace_if_measure_is_positive(replacement);
+                return;
+            }
+        }
+    }
+
+    // step1ab gets rid of plurals and -ed or -ing: caresses -> caress, ponies -> poni,
+    // feed -> feed, agreed -> agree, plastered -> plaster, motoring -> motor, hopping -> hop
+    fn step1ab(&mut self) {
+        if self.at(self.k) == b's' {
+            if self.ends("sses") {
+                self.k -= 2;
+            } else if self.ends("ies") {
+                self.set_to("i");
+            } else if self.at(self.k - 1) != b's' {
+                self.k -= 1;
+            }
+        }
+
+        if self.ends("eed") {
+            if self.measure() > 0 {
+                self.k -= 1;
+            }
+        } else if (self.ends("ed") || self.ends("ing")) && self.has_vowel_in_stem() {
+            self.k = self.j;
+            if self.ends("at") {
+                self.set_to("ate");
+            } else if self.ends("bl") {
+                self.set_to("ble");
+            } else if self.ends("iz") {
+                self.set_to("ize");
+            } else if self.is_double_consonant(self.k) {
+                if !matches!(self.at(self.k), b'l' | b's' | b'z') {
+                    self.k -= 1;
+                }
+            } else {
+                self.j = self.k;
+                if self.measure() == 1 && self.is_cvc(self.k) {
+                    self.set_to("e");
+                }
+            }
+        }
+    }
+
+    // step1c turns terminal y to i when there is another vowel in the stem
+    fn step1c(&mut self) {
+        if self.ends("y") && self.has_vowel_in_stem() {
+            self.b[self.k as usize] = b'i';
+        }
+    }
+
+    // step2 maps double suffixes to single ones: -ization -> -ize, -ational -> -ate and so on
+    fn step2(&mut self) {
+        let replacements: &[(&str, &str)] = match self.at(self.k - 1) {
+            b'a' => &[("ational", "ate"), ("tional", "tion")],
+            b'c' => &[("enci", "ence"), ("anci", "ance")],
+            b'e' => &[("izer", "ize")],
+            b'l' => &[("bli", "ble"), ("alli", "al"), ("entli", "ent"), ("eli", "e"), ("ousli", "ous")],
+            b'o' => &[("ization", "ize"), ("ation", "ate"), ("ator", "ate")],
+            b's' => &[("alism", "al"), ("iveness", "ive"), ("fulness", "ful"), ("ousness", "ous")],
+            b't' => &[("aliti", "al"), ("iviti", "ive"), ("biliti", "ble")],
+            b'g' => &[("logi", "log")],
+            _ => &[],
+        };
+
+        self.replace_first_ending(replacements);
+    }
+
+    // step3 deals with -ic-, -full, -ness and so on
+    fn step3(&mut self) {
+        let replacements: &[(&str, &str)] = match self.at(self.k) {
+            b'e' => &[("icate", "ic"), ("ative", ""), ("alize", "al")],
+            b'i' => &[("iciti", "ic")],
+            b'l' => &[("ical", "ic"), ("ful", "")],
+            b's' => &[("ness", "")],
+            _ => &[],
+        };
+
+        self.replace_first_ending(replacements);
+    }
+
+    // step4 takes off -ant, -ence and so on in context <c>vcvc<v>
+    fn step4(&mut self) {
+        let endings: &[&str] = match self.at(self.k - 1) {
+            b'a' => &["al"],
+            b'c' => &["ance", "ence"],
+            b'e' => &["er"],
+            b'i' => &["ic"],
+            b'l' => &["able", "ible"],
+            b'n' => &["ant", "ement", "ment", "ent"],
+            b'o' => &["ion", "ou"],
+            b's' => &["ism"],
+            b't' => &["ate", "iti"],
+            b'u' => &["ous"],
+            b'v' => &["ive"],
+            b'z' => &["ize"],
+            _ => &[],
+        };
+
+        let mut found = false;
+        for ending in endings {
+            if self.ends(ending) {
+                // -ion is removed only after s or t
+                found = *ending != "ion" || (self.j >= 0 && matches!(self.at(self.j), b's' | b't'));
+                if found {
+                    break;
+                }
+            }
+        }
+
+        if found && self.measure() > 1 {
+            self.k = self.j;
+        }
+    }
+
+    // step5 removes a final -e if measure is bigger than 1 and changes -ll to -l if measure is bigger than 1
+    fn step5(&mut self) {
+        self.j = self.k;
+
+        if self.at(self.k) == b'e' {
+            let measure = self.measure();
+            if measure > 1 || (measure == 1 && !self.is_cvc(self.k - 1)) {
+                self.k -= 1;
+            }
+        }
+
+        if self.at(self.k) == b'l' && self.is_double_consonant(self.k) && self.measure() > 1 {
+            self.k -= 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::porter_stemmer::stem;
+
+    #[test]
+    fn test_stem() {
+        let examples = [
+            ("caresses", "caress"),
+            ("ponies", "poni"),
+            ("agreed", "agre"),
+            ("hopping", "hop"),
+            ("filing", "file"),
+            ("happy", "happi"),
+            ("relational", "relat"),
+            ("generalization", "gener"),
+            ("hopefulness", "hope"),
+            ("adjustment", "adjust"),
+            ("controlling", "control"),
+            ("anyone", "anyon"),
+            ("unsubscribe", "unsubscrib"),
+        ];
+
+        for (word, expected_stem) in examples.iter() {
+            assert_eq!(stem(word), *expected_stem, "stem of {}", word);
+        }
+    }
+}
@@ -0,0 +1,58 @@
+use crate::porter_stemmer::stem;
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+// the characters the email is split by, the same as in ex6
+const DELIMITERS: &str = " @$/#.-:&*+=[]?!(){},'\">_<;%\n\r\t";
+
+// the regexes are compiled once for all the emails
+static HTML_TAG: Lazy<Regex> = Lazy::new(|| Regex::new(r"<[^<>]+>").unwrap());
+static NUMBER: Lazy<Regex> = Lazy::new(|| Regex::new(r"[0-9]+").unwrap());
+static URL: Lazy<Regex> = Lazy::new(|| Regex::new(r"(http|https)://[^\s]*").unwrap());
+static EMAIL_ADDRESS: Lazy<Regex> = Lazy::new(|| Regex::new(r"[^\s]+@[^\s]+").unwrap());
+static DOLLAR: Lazy<Regex> = Lazy::new(|| Regex::new(r"[$]+").unwrap());
+static NON_ALPHANUMERIC: Lazy<Regex> = Lazy::new(|| Regex::new(r"[^a-zA-Z0-9]").unwrap());
+
+// process_email normalizes the email the same way as processEmail.m from ex6 and returns the stemmed words:
+// the text is lowercased, html tags are stripped, numbers, urls, email addresses and dollar signs
+// are replaced with "number", "httpaddr", "emailaddr" and "dollar"
+pub fn process_email(text: &str) -> Vec<String> {
+    let text = text.to_lowercase();
+    let text = HTML_TAG.replace_all(&text, " ");
+    let text = NUMBER.replace_all(&text, "number");
+    let text = URL.replace_all(&text, "httpaddr");
+    let text = EMAIL_ADDRESS.replace_all(&text, "emailaddr");
+    let text = DOLLAR.replace_all(&text, "dollar");
+
+    text.split(|c| DELIMITERS.contains(c))
+        .map(|word| NON_ALPHANUMERIC.replace_all(word, "").to_string())
+        .map(|word| stem(&word))
+        .filter(|word| !word.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::preprocessing::process_email;
+
+    #[test]
+    fn test_process_email() {
+        // emailSample1.txt from ex6 and the words processEmail.m outputs for it
+        let email = "> Anyone knows how much it costs to host a web portal ?
+>
+Well, it depends on how many visitors you're expecting.
+This can be anywhere from less than 10 bucks a month to a couple of $100.
+You should checkout http://www.rackspace.com/ or perhaps Amazon EC2
+if youre running something big..
+
+To unsubscribe yourself from this mailing list, send an email to:
+groupname-unsubscribe@egroups.com
+";
+        let expected = "anyon know how much it cost to host a web portal well it depend on how mani visitor \
+            you re expect thi can be anywher from less than number buck a month to a coupl of dollarnumb \
+            you should checkout httpaddr or perhap amazon ecnumb if your run someth big to unsubscrib \
+            yourself from thi mail list send an email to emailaddr";
+
+        assert_eq!(process_email(email).join(" "), expected);
+    }
+}
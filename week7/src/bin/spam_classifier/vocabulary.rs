@@ -0,0 +1,67 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+// Vocabulary maps the stemmed words to the indices of the features
+pub struct Vocabulary {
+    words: Vec<String>,
+    indices: HashMap<String, usize>,
+}
+
+impl Vocabulary {
+    // load reads the vocabulary from the file with one word per line, the lines can be prefixed
+    // with 1-based index like in vocab.txt from ex6(`1	aa`)
+    pub fn load(path: &Path) -> Result<Vocabulary, Box<dyn Error>> {
+        let mut words = Vec::new();
+
+        for (line_i, line) in fs::read_to_string(path)?.lines().enumerate() {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let word = match fields.as_slice() {
+                [] => continue,
+                [word] => word,
+                [index, word] => {
+                    if index.parse::<usize>().ok() != Some(words.len() + 1) {
+                        return Err(format!("{}:{}: unexpected index {}", path.display(), line_i + 1, index).into());
+                    }
+                    word
+                }
+                _ => return Err(format!("{}:{}: expected `[index] word`", path.display(), line_i + 1).into()),
+            };
+
+            words.push(word.to_string());
+        }
+
+        if words.is_empty() {
+            return Err(format!("{}: the vocabulary is empty", path.display()).into());
+        }
+
+        let indices = words.iter().enumerate().map(|(i, word)| (word.clone(), i)).collect();
+
+        Ok(Vocabulary { words, indices })
+    }
+
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    pub fn word(&self, index: usize) -> &str {
+        &self.words[index]
+    }
+
+    // word_indices returns the indices of the words which are in the vocabulary
+    pub fn word_indices(&self, words: &[String]) -> Vec<usize> {
+        words.iter().filter_map(|word| self.indices.get(word).copied()).collect()
+    }
+
+    // features returns the binary vector where i-th element is 1 if i-th word of the vocabulary is in the email
+    pub fn features(&self, words: &[String]) -> Vec<f64> {
+        let mut features = vec![0.0; self.len()];
+
+        for index in self.word_indices(words) {
+            features[index] = 1.0;
+        }
+
+        features
+    }
+}
@@ -0,0 +1,47 @@
+use druid::Selector;
+use plotters::prelude::{BLACK, BLUE, GREEN, RED, RGBColor};
+
+// the data imitates ex6data2: the examples of the positive class lie along a wave,
+// so they can't be separated from the negative ones by a line
+pub const MIN_X_VALUE: f64 = 0.0;
+pub const MAX_X_VALUE: f64 = 1.0;
+pub const MIN_Y_VALUE: f64 = 0.0;
+pub const MAX_Y_VALUE: f64 = 1.0;
+pub const WAVE_AMPLITUDE: f64 = 0.2;
+pub const WAVE_FREQUENCY: f64 = 1.5;
+pub const WAVE_HALF_WIDTH: f64 = 0.15;
+// the probability of flipping the label of an example
+pub const LABEL_NOISE: f64 = 0.03;
+pub const TRAINING_SET_SIZE: usize = 200;
+pub const CROSS_VALIDATION_SET_SIZE: usize = 100;
+
+// C and sigma are chosen on a logarithmic scale
+pub const DEFAULT_C_LOG10: f64 = 0.0;
+pub const MIN_C_LOG10: f64 = -2.0;
+pub const MAX_C_LOG10: f64 = 1.5;
+pub const DEFAULT_SIGMA_LOG10: f64 = -1.0;
+pub const MIN_SIGMA_LOG10: f64 = -2.0;
+pub const MAX_SIGMA_LOG10: f64 = 1.5;
+pub const POLYNOMIAL_KERNEL_DEGREE: i32 = 3;
+pub const POLYNOMIAL_KERNEL_COEF0: f64 = 1.0;
+// the values of C and sigma tried by the parameter search, the same as in ex6
+pub const PARAMETER_SEARCH_VALUES: [f64; 8] = [0.01, 0.03, 0.1, 0.3, 1.0, 3.0, 10.0, 30.0];
+pub const MAX_SMO_PASSES: usize = 1000;
+// the search thread sends the found C and sigma to the UI with this command
+pub const PARAMETERS_FOUND: Selector<(f64, f64)> = Selector::new("svm-demo.parameters-found");
+pub const LEARNING_UPDATE_PERIOD: u64 = 50;
+
+pub const REFRESH_PERIOD: u64 = 10;
+pub const WINDOW_WIDTH: f64 = 800.0;
+pub const WINDOW_HEIGHT: f64 = 950.0;
+pub const VISUALIZER_HEIGHT: f64 = WINDOW_WIDTH;
+pub const FONT: (&str, i32) = ("sans-serif", 20);
+pub const CHART_MARGIN: i32 = 5;
+pub const LEGEND_SIZE: i32 = 30;
+pub const POINT_SIZE: i32 = 4;
+pub const CONTOUR_RESOLUTION: usize = 60;
+pub const CONTROL_WIDTH: f64 = 200.0;
+pub const POSITIVE_CLASS_COLOR: RGBColor = RED;
+pub const NEGATIVE_CLASS_COLOR: RGBColor = BLUE;
+pub const DECISION_BOUNDARY_COLOR: RGBColor = BLACK;
+pub const SUPPORT_VECTOR_COLOR: RGBColor = GREEN;
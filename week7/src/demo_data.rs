@@ -0,0 +1,32 @@
+use helpers::plotters::two_d::Grid;
+use helpers::svm::{Kernel, KernelSvm};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Settings {
+    pub kernel: Kernel,
+    pub c: f64,
+}
+
+// Model is the SVM being trained with some particular settings
+#[derive(Debug)]
+pub struct Model {
+    pub settings: Settings,
+    pub svm: KernelSvm,
+    pub number_of_passes: usize,
+    pub is_converged: bool,
+    pub train_error: f64,
+    pub cv_error: f64,
+    // the decision function sampled over the visible area, it's updated after every pass
+    pub decision_grid: Grid,
+}
+
+#[derive(Debug)]
+pub struct DemoData {
+    pub train_xs: Vec<[f64; 2]>,
+    pub train_ys: Vec<f64>,
+    pub cv_xs: Vec<[f64; 2]>,
+    pub cv_ys: Vec<f64>,
+    // settings chosen in the UI, the learning thread starts training a new model when they change
+    pub settings: Settings,
+    pub model: Option<Model>,
+}
@@ -0,0 +1,87 @@
+use crate::constants::*;
+use crate::demo_data::{DemoData, Model, Settings};
+use helpers::plotters::two_d::Grid;
+use helpers::svm::{select_parameters, Kernel, KernelSvm};
+use helpers::training_control::TrainingControl;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+// learning_thread makes one pass of SMO per update, so it's possible to see how the boundary converges
+pub fn learning_thread(data: Arc<Mutex<DemoData>>, mut control: TrainingControl) {
+    loop {
+        let reset_requested = control.receive();
+        {
+            let mut data = data.lock().unwrap();
+            let settings = data.settings;
+            if reset_requested {
+                data.model = None;
+            }
+
+            let model = match data.model.take() {
+                Some(model) if model.settings == settings => {
+                    if model.is_converged || control.take_iterations(1) == 0 {
+                        model
+                    } else {
+                        let mut svm = model.svm;
+                        let number_of_passes = model.number_of_passes + 1;
+                        let is_converged = svm.train_step() == 0 || number_of_passes >= MAX_SMO_PASSES;
+
+                        evaluate(&data, settings, svm, number_of_passes, is_converged)
+                    }
+                }
+                // the settings have changed, so we start from scratch
+                _ => {
+                    let svm = KernelSvm::new(settings.kernel, settings.c, &data.train_xs, &data.train_ys);
+
+                    evaluate(&data, settings, svm, 0, false)
+                }
+            };
+            data.model = Some(model);
+        }
+
+        thread::sleep(control.period(Duration::from_millis(LEARNING_UPDATE_PERIOD)));
+    }
+}
+
+fn evaluate(
+    data: &DemoData, settings: Settings, svm: KernelSvm, number_of_passes: usize, is_converged: bool,
+) -> Model {
+    let decision_grid = Grid::sample(
+        |x, y| svm.decision_function(&[x, y]),
+        (MIN_X_VALUE, MAX_X_VALUE),
+        (MIN_Y_VALUE, MAX_Y_VALUE),
+        CONTOUR_RESOLUTION,
+    );
+
+    Model {
+        settings,
+        train_error: svm.error(&data.train_xs, &data.train_ys),
+        cv_error: svm.error(&data.cv_xs, &data.cv_ys),
+        svm,
+        number_of_passes,
+        is_converged,
+        decision_grid,
+    }
+}
+
+// search_parameters tries every pair of C and sigma from ex6 with the gaussian kernel
+// and returns the pair with the smallest error on the cross validation set
+pub fn search_parameters(data: &Arc<Mutex<DemoData>>) -> (f64, f64) {
+    let (train_xs, train_ys, cv_xs, cv_ys) = {
+        let data = data.lock().unwrap();
+        (data.train_xs.clone(), data.train_ys.clone(), data.cv_xs.clone(), data.cv_ys.clone())
+    };
+
+    let candidates = PARAMETER_SEARCH_VALUES.iter().flat_map(|c| {
+        PARAMETER_SEARCH_VALUES.iter().map(move |sigma| (Kernel::Gaussian { sigma: *sigma }, *c))
+    });
+
+    let (svm, _) = select_parameters(&train_xs, &train_ys, &cv_xs, &cv_ys, candidates, MAX_SMO_PASSES)
+        .expect("there is at least one candidate");
+
+    match svm.kernel {
+        Kernel::Gaussian { sigma } => (svm.c, sigma),
+        _ => unreachable!(),
+    }
+}
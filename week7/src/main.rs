@@ -0,0 +1,189 @@
+mod app_data;
+mod constants;
+mod demo_data;
+mod learning;
+mod settings_controller;
+mod svm_visualizer;
+
+use crate::app_data::{AppData, KernelType};
+use crate::constants::*;
+use crate::demo_data::DemoData;
+use crate::learning::{learning_thread, search_parameters};
+use crate::settings_controller::SettingsController;
+use crate::svm_visualizer::SvmVisualizer;
+use druid::widget::*;
+use druid::*;
+use helpers::canvas::{Canvas, Drawer};
+use helpers::export::{ExportArguments, EXPORT_USAGE};
+use helpers::training_control::{build_training_control_widget, control_channel, ControlCommand, TrainingControlData};
+use rand::rngs::ThreadRng;
+use rand::Rng;
+use std::f64::consts::PI;
+use std::process;
+use std::sync::mpsc::Sender;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+const USAGE: &str = "usage: svm-demo";
+
+fn main() {
+    let (export, args) = match ExportArguments::parse(std::env::args().skip(1).collect()) {
+        Ok(arguments) => arguments,
+        Err(error) => {
+            eprintln!("{}\n{}\n{}", error, USAGE, EXPORT_USAGE);
+            process::exit(2);
+        }
+    };
+    if let Some(arg) = args.first() {
+        eprintln!("unexpected argument {}\n{}\n{}", arg, USAGE, EXPORT_USAGE);
+        process::exit(2);
+    }
+
+    let mut rng = rand::thread_rng();
+    let (train_xs, train_ys) = generate_wave_data(TRAINING_SET_SIZE, &mut rng);
+    let (cv_xs, cv_ys) = generate_wave_data(CROSS_VALIDATION_SET_SIZE, &mut rng);
+
+    let app_data = AppData {
+        kernel_type: KernelType::Gaussian,
+        c_log10: DEFAULT_C_LOG10,
+        sigma_log10: DEFAULT_SIGMA_LOG10,
+        is_searching: false,
+        training_control: TrainingControlData::default(),
+    };
+
+    let data = Arc::new(Mutex::new(DemoData {
+        train_xs,
+        train_ys,
+        cv_xs,
+        cv_ys,
+        settings: app_data.settings(),
+        model: None,
+    }));
+
+    let (control_sender, control) = control_channel();
+    let thread_data = data.clone();
+    thread::spawn(move || learning_thread(thread_data, control));
+
+    if let Some(export) = export {
+        let drawers: Vec<Box<dyn Drawer<AppData>>> = vec![Box::new(SvmVisualizer::new(data))];
+        if let Err(error) = export.run(&drawers, &app_data) {
+            eprintln!("error: {}", error);
+            process::exit(1);
+        }
+        return;
+    }
+
+    let window = WindowDesc::new(get_ui_builder(data, control_sender))
+        .window_size(Size::new(WINDOW_WIDTH, WINDOW_HEIGHT))
+        .title(
+            LocalizedString::new("SVM Demo")
+                .with_placeholder("svm-demo"),
+        );
+
+    AppLauncher::with_window(window)
+        .use_simple_logger()
+        .launch(app_data)
+        .expect("launch failed");
+}
+
+// is_positive tells if the point lies inside the wave
+fn is_positive(x: f64, y: f64) -> bool {
+    let wave_center = 0.5 + WAVE_AMPLITUDE * (2.0 * PI * WAVE_FREQUENCY * x).sin();
+
+    (y - wave_center).abs() < WAVE_HALF_WIDTH
+}
+
+fn generate_wave_data(number_of_points: usize, rng: &mut ThreadRng) -> (Vec<[f64; 2]>, Vec<f64>) {
+    let mut xs = Vec::new();
+    let mut ys = Vec::new();
+
+    for _ in 0..number_of_points {
+        let x = rng.gen_range(MIN_X_VALUE..MAX_X_VALUE);
+        let y = rng.gen_range(MIN_Y_VALUE..MAX_Y_VALUE);
+        let is_noise = rng.gen_bool(LABEL_NOISE);
+
+        xs.push([x, y]);
+        ys.push(if is_positive(x, y) != is_noise { 1.0 } else { 0.0 });
+    }
+
+    (xs, ys)
+}
+
+fn get_ui_builder(
+    data: Arc<Mutex<DemoData>>,
+    control_sender: Sender<ControlCommand>,
+) -> impl Fn() -> ControllerHost<Flex<AppData>, SettingsController> {
+    move || {
+        let search_data = data.clone();
+
+        Flex::<AppData>::column()
+            .with_child(Canvas::<AppData>::new(
+                Duration::from_millis(REFRESH_PERIOD),
+                Box::new(SvmVisualizer::new(data.clone())),
+            ))
+            .with_child(
+                Flex::row()
+                    .with_child(Label::new("kernel:").fix_width(CONTROL_WIDTH))
+                    .with_child(
+                        RadioGroup::new(vec![
+                            ("linear", KernelType::Linear),
+                            ("polynomial", KernelType::Polynomial),
+                            ("gaussian", KernelType::Gaussian),
+                        ])
+                            .lens(AppData::kernel_type),
+                    ),
+            )
+            .with_child(
+                Flex::row()
+                    .with_child(
+                        Label::new(|app_data: &AppData, _env: &Env| format!("C: {:.3}", app_data.c()))
+                            .fix_width(CONTROL_WIDTH),
+                    )
+                    .with_child(
+                        Slider::new()
+                            .with_range(MIN_C_LOG10, MAX_C_LOG10)
+                            .lens(AppData::c_log10),
+                    ),
+            )
+            .with_child(
+                Flex::row()
+                    .with_child(
+                        Label::new(|app_data: &AppData, _env: &Env| format!("sigma: {:.3}", app_data.sigma()))
+                            .fix_width(CONTROL_WIDTH),
+                    )
+                    .with_child(
+                        Slider::new()
+                            .with_range(MIN_SIGMA_LOG10, MAX_SIGMA_LOG10)
+                            .lens(AppData::sigma_log10),
+                    ),
+            )
+            .with_child(
+                // the search trains 64 SVMs, so it runs on its own thread and sends the result back
+                Button::new(|app_data: &AppData, _env: &Env| {
+                    if app_data.is_searching {
+                        "searching for C and sigma...".to_string()
+                    } else {
+                        "choose C and sigma on the cross validation set".to_string()
+                    }
+                }).on_click(
+                    move |ctx: &mut EventCtx, app_data: &mut AppData, _env: &Env| {
+                        if app_data.is_searching {
+                            return;
+                        }
+                        app_data.is_searching = true;
+
+                        let sink = ctx.get_external_handle();
+                        let search_data = search_data.clone();
+                        thread::spawn(move || {
+                            let parameters = search_parameters(&search_data);
+                            // the window could be closed while searching
+                            let _ = sink.submit_command(PARAMETERS_FOUND, parameters, Target::Auto);
+                        });
+                    },
+                ),
+            )
+            .with_child(build_training_control_widget(vec![control_sender.clone()]).lens(AppData::training_control))
+            .controller(SettingsController::new(data.clone()))
+    }
+}
@@ -0,0 +1,44 @@
+use druid::widget::prelude::*;
+use druid::widget::Controller;
+use druid::Data;
+use std::sync::{Arc, Mutex};
+use crate::app_data::{AppData, KernelType};
+use crate::constants::*;
+use crate::demo_data::DemoData;
+
+// SettingsController passes the settings chosen in the UI to the learning thread
+// and sets the parameters found by the parameter search
+pub struct SettingsController {
+    data: Arc<Mutex<DemoData>>,
+}
+
+impl SettingsController {
+    pub fn new(data: Arc<Mutex<DemoData>>) -> SettingsController {
+        SettingsController { data }
+    }
+}
+
+impl<W: Widget<AppData>> Controller<AppData, W> for SettingsController {
+    fn event(&mut self, child: &mut W, ctx: &mut EventCtx, event: &Event, data: &mut AppData, env: &Env) {
+        if let Event::Command(command) = event {
+            if let Some((c, sigma)) = command.get(PARAMETERS_FOUND) {
+                data.kernel_type = KernelType::Gaussian;
+                data.c_log10 = c.log10();
+                data.sigma_log10 = sigma.log10();
+                data.is_searching = false;
+                ctx.set_handled();
+                return;
+            }
+        }
+
+        child.event(ctx, event, data, env)
+    }
+
+    fn update(&mut self, child: &mut W, ctx: &mut UpdateCtx, old_data: &AppData, data: &AppData, env: &Env) {
+        if !old_data.same(data) {
+            self.data.lock().unwrap().settings = data.settings();
+        }
+
+        child.update(ctx, old_data, data, env)
+    }
+}
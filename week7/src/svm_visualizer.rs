@@ -0,0 +1,143 @@
+use druid::widget::prelude::*;
+use std::sync::{Arc, Mutex};
+use plotters::prelude::*;
+use crate::demo_data::DemoData;
+use crate::constants::*;
+use std::error::Error;
+use crate::app_data::AppData;
+use helpers::canvas::Drawer;
+
+// SvmVisualizer shows the training set, the regions of the predicted classes,
+// the decision boundary with the margins and the support vectors
+pub struct SvmVisualizer {
+    data: Arc<Mutex<DemoData>>,
+}
+
+impl SvmVisualizer {
+    pub fn new(data: Arc<Mutex<DemoData>>) -> SvmVisualizer {
+        SvmVisualizer { data }
+    }
+}
+
+impl Drawer<AppData> for SvmVisualizer {
+    fn draw_demo_data(&self, buf: &mut [u8], width: usize, height: usize, _app_data: &AppData)
+                      -> Result<(), Box<dyn Error>> {
+        let root = BitMapBackend::with_buffer(
+            buf, (width as u32, height as u32),
+        ).into_drawing_area();
+
+        root.fill(&WHITE)?;
+
+        let data = self.data.lock().unwrap();
+        let caption = match &data.model {
+            Some(model) => format!(
+                "iteration {}{}, train error: {:.3}, cross validation error: {:.3}",
+                model.number_of_passes,
+                if model.is_converged { "" } else { "..." },
+                model.train_error,
+                model.cv_error,
+            ),
+            None => "training...".to_string(),
+        };
+
+        let mut chart = ChartBuilder::on(&root)
+            .caption(caption, FONT.into_font())
+            .margin(CHART_MARGIN)
+            .x_label_area_size(LEGEND_SIZE)
+            .y_label_area_size(LEGEND_SIZE)
+            .build_cartesian_2d(MIN_X_VALUE..MAX_X_VALUE, MIN_Y_VALUE..MAX_Y_VALUE)?;
+
+        chart.configure_mesh().draw()?;
+
+        if let Some(model) = &data.model {
+            let grid = &model.decision_grid;
+
+            // the regions of the predicted classes
+            chart.draw_series(
+                (0..grid.resolution())
+                    .flat_map(|j| (0..grid.resolution()).map(move |i| (i, j)))
+                    .map(|(i, j)| {
+                        let color = if grid.value(i, j) >= 0.0 {
+                            POSITIVE_CLASS_COLOR
+                        } else {
+                            NEGATIVE_CLASS_COLOR
+                        };
+
+                        Rectangle::new([grid.point(i, j), grid.point(i + 1, j + 1)], color.mix(0.1).filled())
+                    }),
+            )?;
+
+            chart
+                .draw_series(
+                    grid.contour(0.0)
+                        .into_iter()
+                        .map(|segment| PathElement::new(segment.to_vec(), DECISION_BOUNDARY_COLOR.stroke_width(2))),
+                )?
+                .label("decision boundary")
+                .legend(|(x, y)| {
+                    PathElement::new(vec![(x, y), (x + LEGEND_SIZE, y)], DECISION_BOUNDARY_COLOR.stroke_width(2))
+                });
+
+            chart
+                .draw_series(
+                    grid.contour(-1.0)
+                        .into_iter()
+                        .chain(grid.contour(1.0))
+                        .map(|segment| PathElement::new(segment.to_vec(), DECISION_BOUNDARY_COLOR.mix(0.4))),
+                )?
+                .label("margin")
+                .legend(|(x, y)| {
+                    PathElement::new(vec![(x, y), (x + LEGEND_SIZE, y)], DECISION_BOUNDARY_COLOR.mix(0.4))
+                });
+        }
+
+        // the training set is drawn like in ex6: crosses for the positive examples and circles for the negative ones
+        chart
+            .draw_series(
+                data.train_xs
+                    .iter()
+                    .zip(&data.train_ys)
+                    .filter(|(_, y)| **y > 0.5)
+                    .map(|(x, _)| Cross::new((x[0], x[1]), POINT_SIZE, POSITIVE_CLASS_COLOR.stroke_width(2))),
+            )?
+            .label("y = 1")
+            .legend(|(x, y)| Cross::new((x, y), POINT_SIZE, POSITIVE_CLASS_COLOR.stroke_width(2)));
+
+        chart
+            .draw_series(
+                data.train_xs
+                    .iter()
+                    .zip(&data.train_ys)
+                    .filter(|(_, y)| **y < 0.5)
+                    .map(|(x, _)| Circle::new((x[0], x[1]), POINT_SIZE, NEGATIVE_CLASS_COLOR.filled())),
+            )?
+            .label("y = 0")
+            .legend(|(x, y)| Circle::new((x, y), POINT_SIZE, NEGATIVE_CLASS_COLOR.filled()));
+
+        if let Some(model) = &data.model {
+            chart
+                .draw_series(
+                    model.svm
+                        .support_vectors()
+                        .into_iter()
+                        .map(|i| &data.train_xs[i])
+                        .map(|x| Circle::new((x[0], x[1]), POINT_SIZE * 2, SUPPORT_VECTOR_COLOR.stroke_width(1))),
+                )?
+                .label("support vectors")
+                .legend(|(x, y)| Circle::new((x, y), POINT_SIZE * 2, SUPPORT_VECTOR_COLOR.stroke_width(1)));
+        }
+
+        chart
+            .configure_series_labels()
+            .position(SeriesLabelPosition::UpperLeft)
+            .background_style(WHITE.mix(0.8))
+            .border_style(BLACK)
+            .draw()?;
+
+        Ok(())
+    }
+
+    fn get_size(&self) -> Size {
+        Size::new(WINDOW_WIDTH, VISUALIZER_HEIGHT)
+    }
+}
@@ -0,0 +1,35 @@
+use druid::*;
+use helpers::k_means::Initialization;
+use crate::constants::*;
+use helpers::training_control::TrainingControlData;
+
+#[derive(Clone, Copy, Data, PartialEq, Debug)]
+pub enum InitializationType {
+    Random,
+    KMeansPlusPlus,
+}
+
+#[derive(Clone, Data, Lens, Debug)]
+pub struct AppData {
+    pub number_of_clusters_slider: f64,
+    pub initialization_type: InitializationType,
+    pub number_of_restarts_slider: f64,
+    pub training_control: TrainingControlData,
+}
+
+impl AppData {
+    pub fn number_of_clusters(&self) -> usize {
+        (self.number_of_clusters_slider.round() as usize).clamp(MIN_NUMBER_OF_CLUSTERS, MAX_NUMBER_OF_CLUSTERS)
+    }
+
+    pub fn initialization(&self) -> Initialization {
+        match self.initialization_type {
+            InitializationType::Random => Initialization::Random,
+            InitializationType::KMeansPlusPlus => Initialization::KMeansPlusPlus,
+        }
+    }
+
+    pub fn number_of_restarts(&self) -> usize {
+        (self.number_of_restarts_slider.round() as usize).clamp(MIN_NUMBER_OF_RESTARTS, MAX_NUMBER_OF_RESTARTS)
+    }
+}
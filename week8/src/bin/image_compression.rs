@@ -0,0 +1,197 @@
+// image_compression reduces the number of colors of an image to K like in ex7:
+// the colors of the pixels are clustered by k-means and every pixel gets the color of its centroid
+use helpers::k_means::{run_k_means_with_restarts, Initialization};
+use image::RgbImage;
+use plotters::prelude::*;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::error::Error;
+use std::path::{Path, PathBuf};
+use std::process;
+
+const DEFAULT_NUMBER_OF_CLUSTERS: usize = 16;
+const DEFAULT_NUMBER_OF_ITERATIONS: usize = 10;
+const DEFAULT_NUMBER_OF_RESTARTS: usize = 1;
+const COMPARISON_PANEL_SIZE: u32 = 500;
+const COMPARISON_CAPTION_FONT: (&str, i32) = ("sans-serif", 20);
+const COMPARISON_CAPTION_SIZE: u32 = 40;
+
+const USAGE: &str = "usage: image_compression <image.png|image.jpg> [--k <number of colors>] \
+[--iterations <number>] [--init random|kmeans++] [--restarts <number>] [--seed <number>] \
+[--output <compressed.png>] [--comparison <comparison.png>]";
+
+struct Arguments {
+    image: PathBuf,
+    number_of_clusters: usize,
+    number_of_iterations: usize,
+    initialization: Initialization,
+    number_of_restarts: usize,
+    seed: Option<u64>,
+    output: PathBuf,
+    comparison: PathBuf,
+}
+
+fn main() {
+    let arguments = match parse_arguments(std::env::args().skip(1).collect()) {
+        Ok(arguments) => arguments,
+        Err(error) => {
+            eprintln!("{}\n{}", error, USAGE);
+            process::exit(2);
+        }
+    };
+
+    if let Err(error) = run(&arguments) {
+        eprintln!("error: {}", error);
+        process::exit(1);
+    }
+}
+
+fn parse_arguments(args: Vec<String>) -> Result<Arguments, Box<dyn Error>> {
+    let mut image = None;
+    let mut number_of_clusters = DEFAULT_NUMBER_OF_CLUSTERS;
+    let mut number_of_iterations = DEFAULT_NUMBER_OF_ITERATIONS;
+    let mut initialization = Initialization::Random;
+    let mut number_of_restarts = DEFAULT_NUMBER_OF_RESTARTS;
+    let mut seed = None;
+    let mut output = None;
+    let mut comparison = None;
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("{} requires a value", arg));
+
+        match arg.as_str() {
+            "--k" => number_of_clusters = value()?.parse()?,
+            "--iterations" => number_of_iterations = value()?.parse()?,
+            "--init" => {
+                initialization = match value()?.as_str() {
+                    "random" => Initialization::Random,
+                    "kmeans++" => Initialization::KMeansPlusPlus,
+                    other => return Err(format!("unknown initialization {}", other).into()),
+                }
+            }
+            "--restarts" => number_of_restarts = value()?.parse()?,
+            "--seed" => seed = Some(value()?.parse()?),
+            "--output" => output = Some(PathBuf::from(value()?)),
+            "--comparison" => comparison = Some(PathBuf::from(value()?)),
+            _ if arg.starts_with("--") => return Err(format!("unknown option {}", arg).into()),
+            _ if image.is_none() => image = Some(PathBuf::from(arg)),
+            _ => return Err(format!("unexpected argument {}", arg).into()),
+        }
+    }
+
+    let image: PathBuf = image.ok_or("the image is required")?;
+    if number_of_clusters == 0 {
+        return Err("K should be positive".into());
+    }
+
+    Ok(Arguments {
+        output: output.unwrap_or_else(|| with_suffix(&image, "compressed")),
+        comparison: comparison.unwrap_or_else(|| with_suffix(&image, "comparison")),
+        image,
+        number_of_clusters,
+        number_of_iterations,
+        initialization,
+        number_of_restarts,
+        seed,
+    })
+}
+
+// with_suffix turns `dir/bird.jpg` into `dir/bird_<suffix>.png`
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let stem = path.file_stem().and_then(|stem| stem.to_str()).unwrap_or("image");
+
+    path.with_file_name(format!("{}_{}.png", stem, suffix))
+}
+
+fn run(arguments: &Arguments) -> Result<(), Box<dyn Error>> {
+    let original = image::open(&arguments.image)
+        .map_err(|error| format!("{}: {}", arguments.image.display(), error))?
+        .to_rgb8();
+
+    // the colors are scaled to 0..1 like in ex7
+    let pixels: Vec<[f64; 3]> = original
+        .pixels()
+        .map(|pixel| [pixel[0] as f64 / 255.0, pixel[1] as f64 / 255.0, pixel[2] as f64 / 255.0])
+        .collect();
+    let number_of_clusters = arguments.number_of_clusters.min(pixels.len());
+
+    println!(
+        "clustering {} pixels into {} colors...", pixels.len(), number_of_clusters,
+    );
+    let mut rng = match arguments.seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    };
+    // with several restarts the distortions of the best run are printed
+    let result = run_k_means_with_restarts(
+        &pixels,
+        number_of_clusters,
+        arguments.initialization,
+        arguments.number_of_restarts,
+        arguments.number_of_iterations,
+        &mut rng,
+    );
+
+    for (i, distortion) in result.distortions.iter().enumerate() {
+        println!("iteration {}: distortion {:.6}", i + 1, distortion);
+    }
+    if result.distortions.len() < arguments.number_of_iterations {
+        println!("converged");
+    }
+
+    let mut compressed = RgbImage::new(original.width(), original.height());
+    for (pixel, centroid_i) in compressed.pixels_mut().zip(&result.assignments) {
+        let centroid = &result.centroids[*centroid_i];
+        *pixel = image::Rgb([to_color(centroid[0]), to_color(centroid[1]), to_color(centroid[2])]);
+    }
+
+    compressed.save(&arguments.output)?;
+    println!("the compressed image is saved to {}", arguments.output.display());
+
+    draw_comparison(&arguments.comparison, original, compressed, number_of_clusters)?;
+    println!("the comparison is saved to {}", arguments.comparison.display());
+
+    Ok(())
+}
+
+fn to_color(value: f64) -> u8 {
+    (value * 255.0).round().clamp(0.0, 255.0) as u8
+}
+
+// draw_comparison draws the original and the compressed images side by side
+fn draw_comparison(
+    path: &Path,
+    original: RgbImage,
+    compressed: RgbImage,
+    number_of_clusters: usize,
+) -> Result<(), Box<dyn Error>> {
+    // both images are scaled to fit into the panel keeping the aspect ratio
+    let scale = (COMPARISON_PANEL_SIZE as f64 / original.width() as f64)
+        .min(COMPARISON_PANEL_SIZE as f64 / original.height() as f64);
+    let width = ((original.width() as f64 * scale) as u32).max(1);
+    let height = ((original.height() as f64 * scale) as u32).max(1);
+
+    let root = BitMapBackend::new(path, (width * 2, height + COMPARISON_CAPTION_SIZE)).into_drawing_area();
+    root.fill(&WHITE)?;
+
+    let (left, right) = root.split_horizontally(width);
+    let panels = [
+        (left, "original".to_string(), original),
+        (right, format!("compressed with {} colors", number_of_clusters), compressed),
+    ];
+
+    for (area, caption, image) in panels.iter() {
+        let area = area.titled(caption, COMPARISON_CAPTION_FONT.into_font())?;
+        let resized = image::imageops::resize(image, width, height, image::imageops::FilterType::Nearest);
+
+        // the raw buffer is used instead of DynamicImage so the versions of image crate don't have to match
+        let element: BitMapElement<_> = BitMapElement::with_owned_buffer((0, 0), (width, height), resized.into_raw())
+            .ok_or("the buffer doesn't match the size of the image")?;
+        area.draw(&element)?;
+    }
+
+    root.present()?;
+
+    Ok(())
+}
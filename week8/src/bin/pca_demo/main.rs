@@ -0,0 +1,80 @@
+// pca_demo shows principal component analysis of 2D data like the figures of ex7:
+// the principal axes and the projections of the points onto the first one.
+// Click the chart to add points
+mod pca_visualizer;
+
+use crate::pca_visualizer::PcaVisualizer;
+use druid::*;
+use helpers::canvas::{Canvas, Drawer};
+use helpers::export::{ExportArguments, EXPORT_USAGE};
+use rand::Rng;
+use std::process;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+pub const MIN_VALUE: f64 = 0.0;
+pub const MAX_VALUE: f64 = 8.0;
+// the initial points are scattered along this line like in ex7data1
+pub const DATA_CENTER: [f64; 2] = [4.0, 4.5];
+pub const DATA_DIRECTION: [f64; 2] = [1.0, 0.8];
+pub const DATA_SPREAD: f64 = 2.0;
+pub const NOISE_LEVEL: f64 = 0.4;
+pub const NUMBER_OF_POINTS: usize = 50;
+
+pub const REFRESH_PERIOD: u64 = 10;
+pub const WINDOW_SIZE: f64 = 800.0;
+
+const USAGE: &str = "usage: pca_demo";
+
+fn main() {
+    let (export, args) = match ExportArguments::parse(std::env::args().skip(1).collect()) {
+        Ok(arguments) => arguments,
+        Err(error) => {
+            eprintln!("{}\n{}\n{}", error, USAGE, EXPORT_USAGE);
+            process::exit(2);
+        }
+    };
+    if let Some(arg) = args.first() {
+        eprintln!("unexpected argument {}\n{}\n{}", arg, USAGE, EXPORT_USAGE);
+        process::exit(2);
+    }
+
+    let mut rng = rand::thread_rng();
+    let points: Vec<[f64; 2]> = (0..NUMBER_OF_POINTS)
+        .map(|_| {
+            let t = rng.gen_range(-DATA_SPREAD..DATA_SPREAD);
+
+            [
+                DATA_CENTER[0] + DATA_DIRECTION[0] * t + rng.gen_range(-NOISE_LEVEL..NOISE_LEVEL),
+                DATA_CENTER[1] + DATA_DIRECTION[1] * t + rng.gen_range(-NOISE_LEVEL..NOISE_LEVEL),
+            ]
+        })
+        .collect();
+    let points = Arc::new(Mutex::new(points));
+
+    if let Some(export) = export {
+        let drawers: Vec<Box<dyn Drawer<()>>> = vec![Box::new(PcaVisualizer::new(points.clone()))];
+        if let Err(error) = export.run(&drawers, &()) {
+            eprintln!("error: {}", error);
+            process::exit(1);
+        }
+        return;
+    }
+
+    let window = WindowDesc::new(move || {
+        Canvas::<()>::new(
+            Duration::from_millis(REFRESH_PERIOD),
+            Box::new(PcaVisualizer::new(points.clone())),
+        )
+    })
+        .window_size(Size::new(WINDOW_SIZE, WINDOW_SIZE))
+        .title(
+            LocalizedString::new("PCA Demo")
+                .with_placeholder("pca-demo"),
+        );
+
+    AppLauncher::with_window(window)
+        .use_simple_logger()
+        .launch(())
+        .expect("launch failed");
+}
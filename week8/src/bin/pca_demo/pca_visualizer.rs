@@ -0,0 +1,132 @@
+use druid::widget::prelude::*;
+use druid::MouseEvent;
+use std::cell::RefCell;
+use std::sync::{Arc, Mutex};
+use plotters::coord::types::RangedCoordf64;
+use plotters::coord::ReverseCoordTranslate;
+use plotters::prelude::*;
+use crate::{MAX_VALUE, MIN_VALUE, WINDOW_SIZE};
+use std::error::Error;
+use helpers::canvas::Drawer;
+use helpers::pca::Pca;
+
+const FONT: (&str, i32) = ("sans-serif", 20);
+const CHART_MARGIN: i32 = 5;
+const LEGEND_SIZE: i32 = 30;
+const POINT_SIZE: i32 = 4;
+// the principal axes are drawn with the length of `AXIS_SCALE * S(k)` like in ex7
+const AXIS_SCALE: f64 = 1.5;
+const POINT_COLOR: RGBColor = BLUE;
+const PROJECTION_COLOR: RGBColor = RED;
+const AXIS_COLOR: RGBColor = BLACK;
+
+// PcaVisualizer shows the points, the principal axes and the points recovered
+// from the projections onto the first principal component
+pub struct PcaVisualizer {
+    points: Arc<Mutex<Vec<[f64; 2]>>>,
+    // the coordinate system of the last drawn chart to convert clicks into points
+    coord_spec: RefCell<Option<Cartesian2d<RangedCoordf64, RangedCoordf64>>>,
+}
+
+impl PcaVisualizer {
+    pub fn new(points: Arc<Mutex<Vec<[f64; 2]>>>) -> PcaVisualizer {
+        PcaVisualizer {
+            points,
+            coord_spec: RefCell::new(None),
+        }
+    }
+}
+
+impl Drawer<()> for PcaVisualizer {
+    fn draw_demo_data(&self, buf: &mut [u8], width: usize, height: usize, _app_data: &())
+                      -> Result<(), Box<dyn Error>> {
+        let root = BitMapBackend::with_buffer(
+            buf, (width as u32, height as u32),
+        ).into_drawing_area();
+
+        root.fill(&WHITE)?;
+
+        let points = self.points.lock().unwrap();
+        // PCA is cheap for 2D data, so it's just refitted on every frame
+        let pca = if points.len() >= 2 { Some(Pca::fit(&points)) } else { None };
+        let caption = match &pca {
+            Some(pca) => format!(
+                "the first principal component retains {:.1}% of the variance", pca.retained_variance(1) * 100.0,
+            ),
+            None => "click to add points".to_string(),
+        };
+
+        let mut chart = ChartBuilder::on(&root)
+            .caption(caption, FONT.into_font())
+            .margin(CHART_MARGIN)
+            .x_label_area_size(LEGEND_SIZE)
+            .y_label_area_size(LEGEND_SIZE)
+            .build_cartesian_2d(MIN_VALUE..MAX_VALUE, MIN_VALUE..MAX_VALUE)?;
+
+        chart.configure_mesh().draw()?;
+
+        chart
+            .draw_series(points.iter().map(|point| {
+                Circle::new((point[0], point[1]), POINT_SIZE, POINT_COLOR.stroke_width(2))
+            }))?
+            .label("data")
+            .legend(|(x, y)| Circle::new((x, y), POINT_SIZE, POINT_COLOR.stroke_width(2)));
+
+        if let Some(pca) = &pca {
+            let recovered: Vec<Vec<f64>> = points.iter().map(|point| pca.recover(&pca.project(point, 1))).collect();
+
+            // the lines connecting the points with their projections
+            chart.draw_series(points.iter().zip(&recovered).map(|(point, recovered)| {
+                PathElement::new(vec![(point[0], point[1]), (recovered[0], recovered[1])], AXIS_COLOR.mix(0.3))
+            }))?;
+
+            chart
+                .draw_series(recovered.iter().map(|point| {
+                    Circle::new((point[0], point[1]), POINT_SIZE, PROJECTION_COLOR.stroke_width(2))
+                }))?
+                .label("projections onto the first component")
+                .legend(|(x, y)| Circle::new((x, y), POINT_SIZE, PROJECTION_COLOR.stroke_width(2)));
+
+            // the principal axes start at the mean, the directions are converted from the normalized space
+            let axes = (0..pca.dimension()).map(|k| {
+                let end = pca.denormalize(&[
+                    AXIS_SCALE * pca.s[k] * pca.u[(0, k)],
+                    AXIS_SCALE * pca.s[k] * pca.u[(1, k)],
+                ]);
+
+                PathElement::new(vec![(pca.mu[0], pca.mu[1]), (end[0], end[1])], AXIS_COLOR.stroke_width(2))
+            });
+            chart
+                .draw_series(axes)?
+                .label("principal axes")
+                .legend(|(x, y)| PathElement::new(vec![(x, y), (x + LEGEND_SIZE, y)], AXIS_COLOR.stroke_width(2)));
+        }
+
+        chart
+            .configure_series_labels()
+            .position(SeriesLabelPosition::UpperLeft)
+            .background_style(WHITE.mix(0.8))
+            .border_style(BLACK)
+            .draw()?;
+
+        *self.coord_spec.borrow_mut() = Some(chart.as_coord_spec().clone());
+
+        Ok(())
+    }
+
+    fn get_size(&self) -> Size {
+        Size::new(WINDOW_SIZE, WINDOW_SIZE)
+    }
+
+    fn handle_mouse_down(&mut self, _ctx: &EventCtx, event: &MouseEvent, _app_data: &mut ()) {
+        let point = match self.coord_spec.borrow().as_ref() {
+            Some(coord_spec) => coord_spec.reverse_translate((event.pos.x as i32, event.pos.y as i32)),
+            None => None,
+        };
+
+        // the clicks outside of the chart are ignored
+        if let Some((x, y)) = point {
+            self.points.lock().unwrap().push([x, y]);
+        }
+    }
+}
@@ -0,0 +1,37 @@
+use plotters::prelude::{BLACK, RGBColor};
+
+pub const MAX_VALUE: f64 = 10.0;
+// the initial points are generated around these centers like in ex7data2
+pub const CLUSTER_CENTERS: [[f64; 2]; 3] = [[2.0, 7.5], [5.0, 2.5], [8.0, 6.0]];
+pub const CLUSTER_RADIUS: f64 = 1.5;
+pub const POINTS_PER_CLUSTER: usize = 50;
+
+pub const DEFAULT_NUMBER_OF_CLUSTERS: usize = 3;
+pub const MIN_NUMBER_OF_CLUSTERS: usize = 1;
+pub const MAX_NUMBER_OF_CLUSTERS: usize = 10;
+pub const DEFAULT_NUMBER_OF_RESTARTS: usize = 1;
+pub const MIN_NUMBER_OF_RESTARTS: usize = 1;
+pub const MAX_NUMBER_OF_RESTARTS: usize = 20;
+// the limit of iterations for the runs that are not animated: the restarts and the elbow method sweep
+pub const MAX_ITERATIONS: usize = 100;
+pub const ELBOW_NUMBER_OF_RESTARTS: usize = 10;
+// the steps are slow on purpose to see how the centroids move
+pub const STEP_PERIOD: u64 = 500;
+
+pub const REFRESH_PERIOD: u64 = 10;
+pub const VISUALIZER_WIDTH: f64 = 800.0;
+pub const VISUALIZER_HEIGHT: f64 = VISUALIZER_WIDTH;
+pub const ELBOW_CHART_WIDTH: f64 = 400.0;
+pub const ELBOW_CHART_HEIGHT: f64 = ELBOW_CHART_WIDTH;
+pub const WINDOW_WIDTH: f64 = VISUALIZER_WIDTH + ELBOW_CHART_WIDTH;
+pub const WINDOW_HEIGHT: f64 = 950.0;
+pub const FONT: (&str, i32) = ("sans-serif", 20);
+pub const CHART_MARGIN: i32 = 5;
+pub const LEGEND_SIZE: i32 = 30;
+pub const POINT_SIZE: i32 = 3;
+pub const CENTROID_SIZE: i32 = 8;
+pub const CONTROL_WIDTH: f64 = 200.0;
+pub const UNASSIGNED_POINT_COLOR: RGBColor = RGBColor(150, 150, 150);
+pub const CENTROID_COLOR: RGBColor = BLACK;
+pub const ELBOW_LINE_COLOR: RGBColor = RGBColor(0, 0, 255);
+pub const CURRENT_NUMBER_OF_CLUSTERS_COLOR: RGBColor = RGBColor(255, 0, 0);
@@ -0,0 +1,49 @@
+use helpers::k_means::Initialization;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Step {
+    AssignPoints,
+    MoveCentroids,
+}
+
+#[derive(Debug)]
+pub struct DemoData {
+    pub points: Vec<[f64; 2]>,
+    // K chosen in the UI
+    pub number_of_clusters: usize,
+    pub initialization: Initialization,
+    // the animated run starts from the initial centroids of the best of this many runs
+    pub number_of_restarts: usize,
+    pub centroids: Vec<Vec<f64>>,
+    // the positions of the centroids after every iteration to draw their paths like in ex7
+    pub centroid_history: Vec<Vec<Vec<f64>>>,
+    // assignments[i] is the cluster of i-th point, the points added after the last assignment step don't have one
+    pub assignments: Vec<usize>,
+    pub next_step: Step,
+    pub iteration: usize,
+    pub is_converged: bool,
+    pub restart_requested: bool,
+    // elbow_distortions[i] is the lowest distortion found with K = i + 1
+    pub elbow_distortions: Vec<f64>,
+    pub elbow_is_outdated: bool,
+}
+
+impl DemoData {
+    pub fn add_point(&mut self, point: [f64; 2]) {
+        self.points.push(point);
+        self.elbow_is_outdated = true;
+        self.next_step = Step::AssignPoints;
+        self.is_converged = false;
+    }
+
+    pub fn clear_points(&mut self) {
+        self.points.clear();
+        self.elbow_is_outdated = true;
+        self.restart();
+    }
+
+    // restart makes the learning thread pick new initial centroids
+    pub fn restart(&mut self) {
+        self.restart_requested = true;
+    }
+}
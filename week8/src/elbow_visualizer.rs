@@ -0,0 +1,72 @@
+use druid::widget::prelude::*;
+use std::sync::{Arc, Mutex};
+use plotters::prelude::*;
+use crate::demo_data::DemoData;
+use crate::constants::*;
+use std::error::Error;
+use crate::app_data::AppData;
+use helpers::canvas::Drawer;
+
+// ElbowVisualizer shows the distortion vs K for the current points, the K after which the distortion
+// stops going down quickly(the "elbow") is a good choice for the number of clusters
+pub struct ElbowVisualizer {
+    data: Arc<Mutex<DemoData>>,
+}
+
+impl ElbowVisualizer {
+    pub fn new(data: Arc<Mutex<DemoData>>) -> ElbowVisualizer {
+        ElbowVisualizer { data }
+    }
+}
+
+impl Drawer<AppData> for ElbowVisualizer {
+    fn draw_demo_data(&self, buf: &mut [u8], width: usize, height: usize, app_data: &AppData)
+                      -> Result<(), Box<dyn Error>> {
+        let root = BitMapBackend::with_buffer(
+            buf, (width as u32, height as u32),
+        ).into_drawing_area();
+
+        root.fill(&WHITE)?;
+
+        let data = self.data.lock().unwrap();
+        let max_distortion = data.elbow_distortions.iter().cloned().fold(0.0, f64::max);
+        // the range can't be empty even if all the distortions are zero
+        let max_distortion = if max_distortion > 0.0 { max_distortion * 1.1 } else { 1.0 };
+
+        let mut chart = ChartBuilder::on(&root)
+            .caption("Elbow method: distortion vs K", FONT.into_font())
+            .margin(CHART_MARGIN)
+            .x_label_area_size(LEGEND_SIZE)
+            .y_label_area_size(LEGEND_SIZE)
+            .build_cartesian_2d(
+                (MIN_NUMBER_OF_CLUSTERS as f64 - 0.5)..(MAX_NUMBER_OF_CLUSTERS as f64 + 0.5),
+                0.0..max_distortion,
+            )?;
+
+        chart.configure_mesh().x_labels(MAX_NUMBER_OF_CLUSTERS).draw()?;
+
+        let points: Vec<(f64, f64)> = data
+            .elbow_distortions
+            .iter()
+            .enumerate()
+            .map(|(i, distortion)| ((i + 1) as f64, *distortion))
+            .collect();
+
+        chart.draw_series(LineSeries::new(points.iter().copied(), ELBOW_LINE_COLOR.stroke_width(2)))?;
+        chart.draw_series(points.iter().map(|point| {
+            let color = if point.0 as usize == app_data.number_of_clusters() {
+                CURRENT_NUMBER_OF_CLUSTERS_COLOR
+            } else {
+                ELBOW_LINE_COLOR
+            };
+
+            Circle::new(*point, POINT_SIZE + 2, color.filled())
+        }))?;
+
+        Ok(())
+    }
+
+    fn get_size(&self) -> Size {
+        Size::new(ELBOW_CHART_WIDTH, ELBOW_CHART_HEIGHT)
+    }
+}
@@ -0,0 +1,109 @@
+use druid::widget::prelude::*;
+use druid::MouseEvent;
+use std::cell::RefCell;
+use std::sync::{Arc, Mutex};
+use plotters::coord::types::RangedCoordf64;
+use plotters::coord::ReverseCoordTranslate;
+use plotters::prelude::*;
+use crate::demo_data::{DemoData, Step};
+use crate::constants::*;
+use std::error::Error;
+use crate::app_data::AppData;
+use helpers::canvas::Drawer;
+
+// KMeansVisualizer shows the points colored by their clusters and the paths of the centroids,
+// click the chart to add a point
+pub struct KMeansVisualizer {
+    data: Arc<Mutex<DemoData>>,
+    // the coordinate system of the last drawn chart to convert clicks into points
+    coord_spec: RefCell<Option<Cartesian2d<RangedCoordf64, RangedCoordf64>>>,
+}
+
+impl KMeansVisualizer {
+    pub fn new(data: Arc<Mutex<DemoData>>) -> KMeansVisualizer {
+        KMeansVisualizer {
+            data,
+            coord_spec: RefCell::new(None),
+        }
+    }
+}
+
+impl Drawer<AppData> for KMeansVisualizer {
+    fn draw_demo_data(&self, buf: &mut [u8], width: usize, height: usize, _app_data: &AppData)
+                      -> Result<(), Box<dyn Error>> {
+        let root = BitMapBackend::with_buffer(
+            buf, (width as u32, height as u32),
+        ).into_drawing_area();
+
+        root.fill(&WHITE)?;
+
+        let data = self.data.lock().unwrap();
+        let caption = if data.centroids.is_empty() {
+            format!("click to add at least {} points", data.number_of_clusters)
+        } else {
+            let status = match (data.is_converged, data.next_step) {
+                (true, _) => "converged",
+                (false, Step::AssignPoints) => "next: cluster assignment",
+                (false, Step::MoveCentroids) => "next: move centroids",
+            };
+            format!("iteration {}, {}", data.iteration, status)
+        };
+
+        let mut chart = ChartBuilder::on(&root)
+            .caption(caption, FONT.into_font())
+            .margin(CHART_MARGIN)
+            .x_label_area_size(LEGEND_SIZE)
+            .y_label_area_size(LEGEND_SIZE)
+            .build_cartesian_2d(0.0..MAX_VALUE, 0.0..MAX_VALUE)?;
+
+        chart.configure_mesh().draw()?;
+
+        chart.draw_series(
+            data.points.iter().enumerate().map(|(i, point)| {
+                let style = match data.assignments.get(i) {
+                    Some(cluster) => Palette99::pick(*cluster).filled(),
+                    None => UNASSIGNED_POINT_COLOR.filled(),
+                };
+
+                Circle::new((point[0], point[1]), POINT_SIZE, style)
+            }),
+        )?;
+
+        // the paths of the centroids
+        for centroid_i in 0..data.centroids.len() {
+            chart.draw_series(LineSeries::new(
+                data.centroid_history
+                    .iter()
+                    .filter_map(|centroids| centroids.get(centroid_i))
+                    .map(|centroid| (centroid[0], centroid[1])),
+                CENTROID_COLOR.stroke_width(2),
+            ))?;
+        }
+
+        chart.draw_series(
+            data.centroids.iter().map(|centroid| {
+                Cross::new((centroid[0], centroid[1]), CENTROID_SIZE, CENTROID_COLOR.stroke_width(3))
+            }),
+        )?;
+
+        *self.coord_spec.borrow_mut() = Some(chart.as_coord_spec().clone());
+
+        Ok(())
+    }
+
+    fn get_size(&self) -> Size {
+        Size::new(VISUALIZER_WIDTH, VISUALIZER_HEIGHT)
+    }
+
+    fn handle_mouse_down(&mut self, _ctx: &EventCtx, event: &MouseEvent, _app_data: &mut AppData) {
+        let point = match self.coord_spec.borrow().as_ref() {
+            Some(coord_spec) => coord_spec.reverse_translate((event.pos.x as i32, event.pos.y as i32)),
+            None => None,
+        };
+
+        // the clicks outside of the chart are ignored
+        if let Some((x, y)) = point {
+            self.data.lock().unwrap().add_point([x, y]);
+        }
+    }
+}
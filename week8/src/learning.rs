@@ -0,0 +1,86 @@
+use crate::constants::*;
+use crate::demo_data::{DemoData, Step};
+use helpers::k_means::{compute_centroids, elbow_sweep, find_closest_centroids, run_k_means_with_restarts};
+use helpers::training_control::TrainingControl;
+use rand::rngs::StdRng;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+// learning_thread makes one step of k-means(either assignment or move) per period,
+// all the random choices are made by rng, so the same seed gives the same animation
+pub fn learning_thread(data: Arc<Mutex<DemoData>>, mut rng: StdRng, mut control: TrainingControl) {
+    loop {
+        let reset_requested = control.receive();
+        {
+            let mut data = data.lock().unwrap();
+            if reset_requested {
+                data.restart();
+            }
+
+            if data.elbow_is_outdated {
+                data.elbow_distortions = elbow_sweep(
+                    &data.points,
+                    MAX_NUMBER_OF_CLUSTERS,
+                    data.initialization,
+                    ELBOW_NUMBER_OF_RESTARTS,
+                    MAX_ITERATIONS,
+                    &mut rng,
+                );
+                data.elbow_is_outdated = false;
+            }
+
+            let needs_initialization = data.restart_requested || data.centroids.len() != data.number_of_clusters;
+
+            if needs_initialization {
+                // we can't pick K centroids until there are at least K points
+                if data.points.len() >= data.number_of_clusters {
+                    // the restarts run without the animation, only the best initial centroids are animated
+                    let centroids = run_k_means_with_restarts(
+                        &data.points,
+                        data.number_of_clusters,
+                        data.initialization,
+                        data.number_of_restarts,
+                        MAX_ITERATIONS,
+                        &mut rng,
+                    ).initial_centroids;
+
+                    data.centroid_history = vec![centroids.clone()];
+                    data.centroids = centroids;
+                    data.assignments.clear();
+                    data.next_step = Step::AssignPoints;
+                    data.iteration = 0;
+                    data.is_converged = false;
+                    data.restart_requested = false;
+                } else if data.points.is_empty() {
+                    data.centroids.clear();
+                    data.centroid_history.clear();
+                    data.assignments.clear();
+                }
+            } else if !data.is_converged && control.take_iterations(1) > 0 {
+                make_step(&mut data);
+            }
+        }
+
+        thread::sleep(control.period(Duration::from_millis(STEP_PERIOD)));
+    }
+}
+
+fn make_step(data: &mut DemoData) {
+    match data.next_step {
+        Step::AssignPoints => {
+            let assignments = find_closest_centroids(&data.points, &data.centroids);
+
+            // nothing is going to change if the points stay in the same clusters
+            data.is_converged = assignments == data.assignments;
+            data.assignments = assignments;
+            data.next_step = Step::MoveCentroids;
+        }
+        Step::MoveCentroids => {
+            data.centroids = compute_centroids(&data.points, &data.assignments, &data.centroids);
+            data.centroid_history.push(data.centroids.clone());
+            data.iteration += 1;
+            data.next_step = Step::AssignPoints;
+        }
+    }
+}
@@ -0,0 +1,201 @@
+mod app_data;
+mod constants;
+mod demo_data;
+mod elbow_visualizer;
+mod k_means_visualizer;
+mod learning;
+mod settings_controller;
+
+use crate::app_data::{AppData, InitializationType};
+use crate::constants::*;
+use crate::demo_data::{DemoData, Step};
+use crate::elbow_visualizer::ElbowVisualizer;
+use crate::k_means_visualizer::KMeansVisualizer;
+use crate::learning::learning_thread;
+use crate::settings_controller::SettingsController;
+use druid::widget::*;
+use druid::*;
+use helpers::canvas::{Canvas, Drawer};
+use helpers::export::{ExportArguments, EXPORT_USAGE};
+use helpers::training_control::{build_training_control_widget, control_channel, ControlCommand, TrainingControlData};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::error::Error;
+use std::process;
+use std::sync::mpsc::Sender;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+const USAGE: &str = "usage: k-means-demo [--seed <number>]";
+
+fn main() {
+    let (export, args) = match ExportArguments::parse(std::env::args().skip(1).collect()) {
+        Ok(arguments) => arguments,
+        Err(error) => {
+            eprintln!("{}\n{}\n{}", error, USAGE, EXPORT_USAGE);
+            process::exit(2);
+        }
+    };
+    // the same seed gives the same points and the same initial centroids
+    let mut rng = match parse_seed(args) {
+        Ok(Some(seed)) => StdRng::seed_from_u64(seed),
+        Ok(None) => StdRng::from_entropy(),
+        Err(error) => {
+            eprintln!("{}\n{}\n{}", error, USAGE, EXPORT_USAGE);
+            process::exit(2);
+        }
+    };
+
+    let app_data = AppData {
+        number_of_clusters_slider: DEFAULT_NUMBER_OF_CLUSTERS as f64,
+        initialization_type: InitializationType::KMeansPlusPlus,
+        number_of_restarts_slider: DEFAULT_NUMBER_OF_RESTARTS as f64,
+        training_control: TrainingControlData::default(),
+    };
+
+    let data = Arc::new(Mutex::new(DemoData {
+        points: generate_clusters(&mut rng),
+        number_of_clusters: app_data.number_of_clusters(),
+        initialization: app_data.initialization(),
+        number_of_restarts: app_data.number_of_restarts(),
+        centroids: Vec::new(),
+        centroid_history: Vec::new(),
+        assignments: Vec::new(),
+        next_step: Step::AssignPoints,
+        iteration: 0,
+        is_converged: false,
+        restart_requested: false,
+        elbow_distortions: Vec::new(),
+        elbow_is_outdated: true,
+    }));
+
+    let (control_sender, control) = control_channel();
+    let thread_data = data.clone();
+    thread::spawn(move || learning_thread(thread_data, rng, control));
+
+    if let Some(export) = export {
+        let drawers: Vec<Box<dyn Drawer<AppData>>> = vec![
+            Box::new(KMeansVisualizer::new(data.clone())),
+            Box::new(ElbowVisualizer::new(data.clone())),
+        ];
+        if let Err(error) = export.run(&drawers, &app_data) {
+            eprintln!("error: {}", error);
+            process::exit(1);
+        }
+        return;
+    }
+
+    let window = WindowDesc::new(get_ui_builder(data, control_sender))
+        .window_size(Size::new(WINDOW_WIDTH, WINDOW_HEIGHT))
+        .title(
+            LocalizedString::new("K-Means Demo")
+                .with_placeholder("k-means-demo"),
+        );
+
+    AppLauncher::with_window(window)
+        .use_simple_logger()
+        .launch(app_data)
+        .expect("launch failed");
+}
+
+fn parse_seed(args: Vec<String>) -> Result<Option<u64>, Box<dyn Error>> {
+    let mut seed = None;
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--seed" => seed = Some(args.next().ok_or("--seed requires a value")?.parse()?),
+            _ => return Err(format!("unexpected argument {}", arg).into()),
+        }
+    }
+
+    Ok(seed)
+}
+
+// generate_clusters generates the points scattered around CLUSTER_CENTERS
+fn generate_clusters<R: Rng>(rng: &mut R) -> Vec<[f64; 2]> {
+    let mut points = Vec::new();
+
+    for center in CLUSTER_CENTERS.iter() {
+        for _ in 0..POINTS_PER_CLUSTER {
+            let angle = rng.gen_range(0.0..2.0 * std::f64::consts::PI);
+            let distance = CLUSTER_RADIUS * rng.gen::<f64>().sqrt();
+
+            points.push([center[0] + distance * angle.cos(), center[1] + distance * angle.sin()]);
+        }
+    }
+
+    points
+}
+
+fn get_ui_builder(
+    data: Arc<Mutex<DemoData>>,
+    control_sender: Sender<ControlCommand>,
+) -> impl Fn() -> ControllerHost<Flex<AppData>, SettingsController> {
+    move || {
+        let restart_data = data.clone();
+        let clear_data = data.clone();
+
+        Flex::<AppData>::column()
+            .with_child(
+                Flex::row()
+                    .with_child(Canvas::<AppData>::new(
+                        Duration::from_millis(REFRESH_PERIOD),
+                        Box::new(KMeansVisualizer::new(data.clone())),
+                    ))
+                    .with_child(Canvas::<AppData>::new(
+                        Duration::from_millis(REFRESH_PERIOD),
+                        Box::new(ElbowVisualizer::new(data.clone())),
+                    )),
+            )
+            .with_child(
+                Flex::row()
+                    .with_child(
+                        Label::new(|app_data: &AppData, _env: &Env| {
+                            format!("K: {}", app_data.number_of_clusters())
+                        }).fix_width(CONTROL_WIDTH),
+                    )
+                    .with_child(
+                        Slider::new()
+                            .with_range(MIN_NUMBER_OF_CLUSTERS as f64, MAX_NUMBER_OF_CLUSTERS as f64)
+                            .lens(AppData::number_of_clusters_slider),
+                    ),
+            )
+            .with_child(
+                Flex::row()
+                    .with_child(Label::new("initialization:").fix_width(CONTROL_WIDTH))
+                    .with_child(
+                        RadioGroup::new(vec![
+                            ("random examples", InitializationType::Random),
+                            ("k-means++", InitializationType::KMeansPlusPlus),
+                        ])
+                            .lens(AppData::initialization_type),
+                    ),
+            )
+            .with_child(
+                Flex::row()
+                    .with_child(
+                        Label::new(|app_data: &AppData, _env: &Env| {
+                            format!("restarts: {}", app_data.number_of_restarts())
+                        }).fix_width(CONTROL_WIDTH),
+                    )
+                    .with_child(
+                        Slider::new()
+                            .with_range(MIN_NUMBER_OF_RESTARTS as f64, MAX_NUMBER_OF_RESTARTS as f64)
+                            .lens(AppData::number_of_restarts_slider),
+                    ),
+            )
+            .with_child(
+                Flex::row()
+                    .with_child(Button::new("restart").on_click(
+                        move |_ctx, _app_data: &mut AppData, _env| restart_data.lock().unwrap().restart(),
+                    ))
+                    .with_child(Button::new("clear points").on_click(
+                        move |_ctx, _app_data: &mut AppData, _env| clear_data.lock().unwrap().clear_points(),
+                    )),
+            )
+            .with_child(build_training_control_widget(vec![control_sender.clone()]).lens(AppData::training_control))
+            .controller(SettingsController::new(data.clone()))
+    }
+}
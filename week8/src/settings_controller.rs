@@ -0,0 +1,37 @@
+use druid::widget::prelude::*;
+use druid::widget::Controller;
+use druid::Data;
+use std::sync::{Arc, Mutex};
+use crate::app_data::AppData;
+use crate::demo_data::DemoData;
+
+// SettingsController passes K and the initialization settings chosen in the UI to the learning thread
+pub struct SettingsController {
+    data: Arc<Mutex<DemoData>>,
+}
+
+impl SettingsController {
+    pub fn new(data: Arc<Mutex<DemoData>>) -> SettingsController {
+        SettingsController { data }
+    }
+}
+
+impl<W: Widget<AppData>> Controller<AppData, W> for SettingsController {
+    fn update(&mut self, child: &mut W, ctx: &mut UpdateCtx, old_data: &AppData, data: &AppData, env: &Env) {
+        if !old_data.same(data) {
+            let mut demo_data = self.data.lock().unwrap();
+            demo_data.number_of_clusters = data.number_of_clusters();
+
+            let initialization_changed = demo_data.initialization != data.initialization()
+                || demo_data.number_of_restarts != data.number_of_restarts();
+            if initialization_changed {
+                demo_data.initialization = data.initialization();
+                demo_data.number_of_restarts = data.number_of_restarts();
+                demo_data.elbow_is_outdated = true;
+                demo_data.restart();
+            }
+        }
+
+        child.update(ctx, old_data, data, env)
+    }
+}
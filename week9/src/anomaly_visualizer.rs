@@ -0,0 +1,119 @@
+use druid::widget::prelude::*;
+use druid::MouseEvent;
+use std::cell::RefCell;
+use std::sync::{Arc, Mutex};
+use plotters::coord::types::RangedCoordf64;
+use plotters::coord::ReverseCoordTranslate;
+use plotters::prelude::*;
+use crate::demo_data::DemoData;
+use crate::constants::*;
+use std::error::Error;
+use crate::app_data::AppData;
+use helpers::canvas::Drawer;
+
+// AnomalyVisualizer shows the training set, the contours of the fitted density and circles the examples
+// with the density below epsilon like the ex8 figures, click the chart to add an example
+pub struct AnomalyVisualizer {
+    data: Arc<Mutex<DemoData>>,
+    // the coordinate system of the last drawn chart to convert clicks into points
+    coord_spec: RefCell<Option<Cartesian2d<RangedCoordf64, RangedCoordf64>>>,
+}
+
+impl AnomalyVisualizer {
+    pub fn new(data: Arc<Mutex<DemoData>>) -> AnomalyVisualizer {
+        AnomalyVisualizer {
+            data,
+            coord_spec: RefCell::new(None),
+        }
+    }
+}
+
+impl Drawer<AppData> for AnomalyVisualizer {
+    fn draw_demo_data(&self, buf: &mut [u8], width: usize, height: usize, _app_data: &AppData)
+                      -> Result<(), Box<dyn Error>> {
+        let root = BitMapBackend::with_buffer(
+            buf, (width as u32, height as u32),
+        ).into_drawing_area();
+
+        root.fill(&WHITE)?;
+
+        let data = self.data.lock().unwrap();
+        let caption = match &data.model {
+            Some(model) => format!(
+                "epsilon: {:.3e}, F1 on cross validation: {:.3}, anomalies: {}",
+                model.epsilon,
+                model.f1,
+                data.xs.iter().filter(|x| model.is_anomaly(*x)).count(),
+            ),
+            None => "can't estimate the density".to_string(),
+        };
+
+        let mut chart = ChartBuilder::on(&root)
+            .caption(caption, FONT.into_font())
+            .margin(CHART_MARGIN)
+            .x_label_area_size(LEGEND_SIZE)
+            .y_label_area_size(LEGEND_SIZE)
+            .build_cartesian_2d(0f64..MAX_VALUE, 0f64..MAX_VALUE)?;
+
+        chart.configure_mesh().draw()?;
+
+        if let Some(model) = &data.model {
+            chart
+                .draw_series(
+                    CONTOUR_LEVELS_LOG10
+                        .iter()
+                        .flat_map(|level| model.log_density_grid.contour(*level))
+                        .map(|segment| PathElement::new(segment.to_vec(), CONTOUR_COLOR)),
+                )?
+                .label("density 1e-20, 1e-17, ..., 1e-2")
+                .legend(|(x, y)| PathElement::new(vec![(x, y), (x + LEGEND_SIZE, y)], CONTOUR_COLOR));
+        }
+
+        chart
+            .draw_series(
+                data.xs.iter().map(|x| Cross::new((x[0], x[1]), POINT_SIZE, EXAMPLE_COLOR.stroke_width(1))),
+            )?
+            .label("examples")
+            .legend(|(x, y)| Cross::new((x, y), POINT_SIZE, EXAMPLE_COLOR.stroke_width(1)));
+
+        if let Some(model) = &data.model {
+            chart
+                .draw_series(
+                    data.xs
+                        .iter()
+                        .filter(|x| model.is_anomaly(*x))
+                        .map(|x| Circle::new((x[0], x[1]), ANOMALY_CIRCLE_SIZE, ANOMALY_COLOR.stroke_width(2))),
+                )?
+                .label("anomalies")
+                .legend(|(x, y)| Circle::new((x, y), ANOMALY_CIRCLE_SIZE, ANOMALY_COLOR.stroke_width(2)));
+        }
+
+        chart
+            .configure_series_labels()
+            .position(SeriesLabelPosition::UpperLeft)
+            .label_font(SMALL_LEGEND_FONT)
+            .background_style(WHITE.mix(0.8))
+            .border_style(BLACK)
+            .draw()?;
+
+        *self.coord_spec.borrow_mut() = Some(chart.as_coord_spec().clone());
+
+        Ok(())
+    }
+
+    fn get_size(&self) -> Size {
+        Size::new(WINDOW_WIDTH, VISUALIZER_HEIGHT)
+    }
+
+    fn handle_mouse_down(&mut self, _ctx: &EventCtx, event: &MouseEvent, _app_data: &mut AppData) {
+        let point = match self.coord_spec.borrow().as_ref() {
+            Some(coord_spec) => coord_spec.reverse_translate((event.pos.x as i32, event.pos.y as i32)),
+            None => None,
+        };
+
+        // the clicks outside of the chart are ignored
+        if let Some((x, y)) = point {
+            self.data.lock().unwrap().add_point([x, y]);
+        }
+    }
+}
@@ -0,0 +1,14 @@
+use druid::*;
+
+#[derive(Clone, Copy, Data, PartialEq, Debug)]
+pub enum ModelType {
+    // every feature has its own normal distribution
+    Gaussian,
+    // one distribution with the full covariance matrix
+    MultivariateGaussian,
+}
+
+#[derive(Clone, Data, Lens, Debug)]
+pub struct AppData {
+    pub model_type: ModelType,
+}
@@ -0,0 +1,240 @@
+// movie_recommender trains collaborative filtering on the movie ratings like in ex8,
+// adds a new user with some ratings and prints the movies recommended to them
+use helpers::collaborative_filtering::{check_gradients, Ratings, Recommender};
+use helpers::dataset::parse_number;
+use helpers::mat_file::{get_matrix_from_mat_file, load_mat_file};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process;
+
+const DEFAULT_NUMBER_OF_FEATURES: usize = 10;
+const DEFAULT_REGULARIZATION_RATE: f64 = 10.0;
+const DEFAULT_NUMBER_OF_ITERATIONS: usize = 100;
+const DEFAULT_NUMBER_OF_RECOMMENDATIONS: usize = 10;
+// the ratings of the new user from ex8_cofi.m, the movie ids are 1-based like in movie_ids.txt
+const DEFAULT_RATINGS: [(usize, f64); 11] = [
+    (1, 4.0),
+    (98, 2.0),
+    (7, 3.0),
+    (12, 5.0),
+    (54, 4.0),
+    (64, 5.0),
+    (66, 3.0),
+    (69, 5.0),
+    (183, 4.0),
+    (226, 5.0),
+    (355, 5.0),
+];
+
+const USAGE: &str = "usage: movie_recommender --movies <ex8_movies.mat> --movie-list <movie_ids.txt> \
+[--ratings <file with \"movie id, rating\" lines>] [--features <number>] [--lambda <number>] \
+[--iterations <number>] [--top <number>] [--seed <number>] [--check-gradients]";
+
+struct Arguments {
+    movies: PathBuf,
+    movie_list: PathBuf,
+    ratings: Option<PathBuf>,
+    number_of_features: usize,
+    regularization_rate: f64,
+    number_of_iterations: usize,
+    number_of_recommendations: usize,
+    seed: Option<u64>,
+    check_gradients: bool,
+}
+
+fn main() {
+    let arguments = match parse_arguments(std::env::args().skip(1).collect()) {
+        Ok(arguments) => arguments,
+        Err(error) => {
+            eprintln!("{}\n{}", error, USAGE);
+            process::exit(2);
+        }
+    };
+
+    if let Err(error) = run(&arguments) {
+        eprintln!("error: {}", error);
+        process::exit(1);
+    }
+}
+
+fn parse_arguments(args: Vec<String>) -> Result<Arguments, Box<dyn Error>> {
+    let mut movies = None;
+    let mut movie_list = None;
+    let mut ratings = None;
+    let mut number_of_features = DEFAULT_NUMBER_OF_FEATURES;
+    let mut regularization_rate = DEFAULT_REGULARIZATION_RATE;
+    let mut number_of_iterations = DEFAULT_NUMBER_OF_ITERATIONS;
+    let mut number_of_recommendations = DEFAULT_NUMBER_OF_RECOMMENDATIONS;
+    let mut seed = None;
+    let mut check_gradients = false;
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("{} requires a value", arg));
+
+        match arg.as_str() {
+            "--movies" => movies = Some(PathBuf::from(value()?)),
+            "--movie-list" => movie_list = Some(PathBuf::from(value()?)),
+            "--ratings" => ratings = Some(PathBuf::from(value()?)),
+            "--features" => number_of_features = value()?.parse()?,
+            "--lambda" => regularization_rate = value()?.parse()?,
+            "--iterations" => number_of_iterations = value()?.parse()?,
+            "--top" => number_of_recommendations = value()?.parse()?,
+            "--seed" => seed = Some(value()?.parse()?),
+            "--check-gradients" => check_gradients = true,
+            _ => return Err(format!("unexpected argument {}", arg).into()),
+        }
+    }
+
+    Ok(Arguments {
+        movies: movies.ok_or("--movies is required")?,
+        movie_list: movie_list.ok_or("--movie-list is required")?,
+        ratings,
+        number_of_features,
+        regularization_rate,
+        number_of_iterations,
+        number_of_recommendations,
+        seed,
+        check_gradients,
+    })
+}
+
+fn run(arguments: &Arguments) -> Result<(), Box<dyn Error>> {
+    let mut rng = match arguments.seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    };
+
+    if arguments.check_gradients {
+        for regularization_rate in [0.0, 1.5].iter() {
+            println!(
+                "gradient check with lambda = {}: relative difference {:e}(should be less than 1e-9)",
+                regularization_rate,
+                check_gradients(*regularization_rate, &mut rng)
+            );
+        }
+    }
+
+    let movies = load_mat_file(&arguments.movies)?;
+    let mut ratings = Ratings {
+        y: get_matrix_from_mat_file(&movies, "Y")?,
+        r: get_matrix_from_mat_file(&movies, "R")?,
+    };
+    if ratings.y.shape() != ratings.r.shape() {
+        return Err(format!("Y is {:?}, but R is {:?}", ratings.y.shape(), ratings.r.shape()).into());
+    }
+
+    let movie_list = load_movie_list(&arguments.movie_list)?;
+    if movie_list.len() != ratings.number_of_movies() {
+        return Err(format!(
+            "{} has {} movies, but there are ratings of {}",
+            arguments.movie_list.display(),
+            movie_list.len(),
+            ratings.number_of_movies()
+        ).into());
+    }
+
+    let new_user_ratings = match &arguments.ratings {
+        Some(path) => load_ratings(path, movie_list.len())?,
+        None => default_ratings(movie_list.len())?,
+    };
+
+    println!("new user ratings:");
+    for (movie, rating) in &new_user_ratings {
+        println!("rated {} for {}", rating, movie_list[*movie]);
+    }
+
+    let user = ratings.add_user(&new_user_ratings);
+    println!(
+        "\ntraining on the ratings of {} movies by {} users...",
+        ratings.number_of_movies(),
+        ratings.number_of_users()
+    );
+    let recommender = Recommender::train(
+        &ratings,
+        arguments.number_of_features,
+        arguments.regularization_rate,
+        arguments.number_of_iterations,
+        &mut rng,
+    );
+    if let Some(cost) = recommender.costs.last() {
+        println!("cost after {} iterations: {:.2}", recommender.costs.len(), cost);
+    }
+
+    println!("\ntop recommendations:");
+    for (movie, prediction) in recommender.recommend(&ratings, user, arguments.number_of_recommendations) {
+        println!("predicting rating {:.1} for {}", prediction, movie_list[movie]);
+    }
+
+    Ok(())
+}
+
+// load_movie_list reads movie_ids.txt where every line is "<id> <title>", the ids go from 1 in order
+fn load_movie_list(path: &Path) -> Result<Vec<String>, Box<dyn Error>> {
+    let bytes = fs::read(path).map_err(|error| format!("{}: {}", path.display(), error))?;
+    // the titles aren't in UTF-8, e.g. "Misérables" is in latin1
+    let content = String::from_utf8_lossy(&bytes);
+
+    content
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty())
+        .enumerate()
+        .map(|(i, (number, line))| {
+            let (id, title) = line.split_once(' ').unwrap_or((line, ""));
+            if id.parse::<usize>().ok() != Some(i + 1) {
+                return Err(format!("{}:{}: expected movie id {}", path.display(), number, i + 1).into());
+            }
+
+            Ok(title.to_string())
+        })
+        .collect()
+}
+
+// default_ratings returns DEFAULT_RATINGS as (movie index, rating) pairs, the ids are from movie_ids.txt of ex8,
+// so another movie list can be too short for them
+fn default_ratings(number_of_movies: usize) -> Result<Vec<(usize, f64)>, Box<dyn Error>> {
+    DEFAULT_RATINGS
+        .iter()
+        .map(|(id, rating)| {
+            if *id == 0 || *id > number_of_movies {
+                return Err(format!(
+                    "the default ratings have the movie id {}, but there are {} movies, use --ratings",
+                    id,
+                    number_of_movies
+                ).into());
+            }
+            Ok((id - 1, *rating))
+        })
+        .collect()
+}
+
+// load_ratings reads "<movie id>, <rating>" lines, the ids are 1-based like in movie_ids.txt.
+// It returns (movie index, rating) pairs
+fn load_ratings(path: &Path, number_of_movies: usize) -> Result<Vec<(usize, f64)>, Box<dyn Error>> {
+    let content = fs::read_to_string(path).map_err(|error| format!("{}: {}", path.display(), error))?;
+    let mut ratings = Vec::new();
+
+    for (i, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let error = |message: &str| format!("{}:{}: {}", path.display(), i + 1, message);
+        let (id, rating) = line.split_once(',').ok_or_else(|| error("expected \"movie id, rating\""))?;
+        let id: usize = id.trim().parse().map_err(|_| error("the movie id should be a number"))?;
+        let rating = parse_number(rating).map_err(|_| error("the rating should be a number"))?;
+
+        if id == 0 || id > number_of_movies {
+            return Err(error(&format!("the movie id should be from 1 to {}", number_of_movies)).into());
+        }
+        ratings.push((id - 1, rating));
+    }
+
+    Ok(ratings)
+}
@@ -0,0 +1,31 @@
+use plotters::prelude::{BLACK, BLUE, RED, RGBColor};
+
+// the axes are the same as in the ex8 plots of server latency(ms) vs throughput(mb/s)
+pub const MAX_VALUE: f64 = 30.0;
+// the normal examples are generated around this center, the second feature is correlated with the first one,
+// so the multivariate gaussian fits them better than the per-feature one
+pub const DATA_CENTER: [f64; 2] = [14.0, 15.0];
+pub const DATA_STANDARD_DEVIATION: f64 = 1.3;
+pub const FEATURE_CORRELATION: f64 = 0.8;
+pub const TRAINING_SET_SIZE: usize = 300;
+pub const CROSS_VALIDATION_SET_SIZE: usize = 300;
+// the share of the anomalies in both sets, they are scattered uniformly over the whole chart
+pub const ANOMALY_SHARE: f64 = 0.03;
+// the density contours are drawn at 10^-20, 10^-17, ..., 10^-2 like in visualizeFit.m from ex8
+pub const CONTOUR_LEVELS_LOG10: [f64; 7] = [-20.0, -17.0, -14.0, -11.0, -8.0, -5.0, -2.0];
+pub const CONTOUR_RESOLUTION: usize = 80;
+
+pub const REFRESH_PERIOD: u64 = 10;
+pub const WINDOW_WIDTH: f64 = 800.0;
+pub const WINDOW_HEIGHT: f64 = 880.0;
+pub const VISUALIZER_HEIGHT: f64 = WINDOW_WIDTH;
+pub const FONT: (&str, i32) = ("sans-serif", 20);
+pub const SMALL_LEGEND_FONT: (&str, i32) = ("sans-serif", 12);
+pub const CHART_MARGIN: i32 = 5;
+pub const LEGEND_SIZE: i32 = 30;
+pub const POINT_SIZE: i32 = 3;
+pub const ANOMALY_CIRCLE_SIZE: i32 = 8;
+pub const CONTROL_WIDTH: f64 = 200.0;
+pub const EXAMPLE_COLOR: RGBColor = BLUE;
+pub const ANOMALY_COLOR: RGBColor = RED;
+pub const CONTOUR_COLOR: RGBColor = BLACK;
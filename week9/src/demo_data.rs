@@ -0,0 +1,93 @@
+use crate::app_data::ModelType;
+use crate::constants::*;
+use helpers::anomaly_detection::{select_threshold, Gaussian, MultivariateGaussian};
+use helpers::plotters::two_d::Grid;
+
+#[derive(Debug)]
+pub enum DensityModel {
+    Gaussian(Gaussian),
+    MultivariateGaussian(MultivariateGaussian),
+}
+
+impl DensityModel {
+    pub fn probability(&self, x: &[f64]) -> f64 {
+        match self {
+            DensityModel::Gaussian(gaussian) => gaussian.probability(x),
+            DensityModel::MultivariateGaussian(gaussian) => gaussian.probability(x),
+        }
+    }
+}
+
+// Model is the density fitted to the training set and the threshold selected on the cross validation set
+#[derive(Debug)]
+pub struct Model {
+    pub density: DensityModel,
+    pub epsilon: f64,
+    pub f1: f64,
+    // log10 of the density sampled over the visible area to draw the contours
+    pub log_density_grid: Grid,
+}
+
+impl Model {
+    pub fn is_anomaly(&self, x: &[f64]) -> bool {
+        self.density.probability(x) < self.epsilon
+    }
+}
+
+#[derive(Debug)]
+pub struct DemoData {
+    // the training set is unlabelled like in ex8
+    pub xs: Vec<[f64; 2]>,
+    pub cv_xs: Vec<[f64; 2]>,
+    // cv_ys[i] is true if i-th cross validation example is an anomaly
+    pub cv_ys: Vec<bool>,
+    pub model_type: ModelType,
+    // None if the density can't be estimated, e.g. the covariance matrix is singular or a feature has zero variance
+    pub model: Option<Model>,
+}
+
+impl DemoData {
+    pub fn add_point(&mut self, point: [f64; 2]) {
+        self.xs.push(point);
+        self.fit();
+    }
+
+    pub fn set_model_type(&mut self, model_type: ModelType) {
+        self.model_type = model_type;
+        self.fit();
+    }
+
+    // fit estimates the density on the training set and selects epsilon like ex8
+    pub fn fit(&mut self) {
+        if self.xs.is_empty() {
+            self.model = None;
+            return;
+        }
+
+        let density = match self.model_type {
+            ModelType::Gaussian => Gaussian::fit(&self.xs).map(DensityModel::Gaussian),
+            ModelType::MultivariateGaussian => {
+                MultivariateGaussian::fit(&self.xs).map(DensityModel::MultivariateGaussian)
+            }
+        };
+
+        self.model = density.map(|density| {
+            let cv_probabilities: Vec<f64> = self.cv_xs.iter().map(|x| density.probability(x)).collect();
+            let (epsilon, f1) = select_threshold(&self.cv_ys, &cv_probabilities);
+            // the density underflows to 0 far from the data, so it's limited to keep the logarithm finite
+            let log_density_grid = Grid::sample(
+                |x, y| density.probability(&[x, y]).max(f64::MIN_POSITIVE).log10(),
+                (0.0, MAX_VALUE),
+                (0.0, MAX_VALUE),
+                CONTOUR_RESOLUTION,
+            );
+
+            Model {
+                density,
+                epsilon,
+                f1,
+                log_density_grid,
+            }
+        });
+    }
+}
@@ -0,0 +1,125 @@
+mod anomaly_visualizer;
+mod app_data;
+mod constants;
+mod demo_data;
+mod settings_controller;
+
+use crate::anomaly_visualizer::AnomalyVisualizer;
+use crate::app_data::{AppData, ModelType};
+use crate::constants::*;
+use crate::demo_data::DemoData;
+use crate::settings_controller::SettingsController;
+use druid::widget::*;
+use druid::*;
+use helpers::canvas::{Canvas, Drawer};
+use helpers::export::{ExportArguments, EXPORT_USAGE};
+use helpers::math::standard_normal;
+use rand::rngs::ThreadRng;
+use rand::Rng;
+use std::process;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+const USAGE: &str = "usage: anomaly-detection-demo";
+
+fn main() {
+    let (export, args) = match ExportArguments::parse(std::env::args().skip(1).collect()) {
+        Ok(arguments) => arguments,
+        Err(error) => {
+            eprintln!("{}\n{}\n{}", error, USAGE, EXPORT_USAGE);
+            process::exit(2);
+        }
+    };
+    if let Some(arg) = args.first() {
+        eprintln!("unexpected argument {}\n{}\n{}", arg, USAGE, EXPORT_USAGE);
+        process::exit(2);
+    }
+
+    let mut rng = rand::thread_rng();
+    let (xs, _) = generate_server_data(TRAINING_SET_SIZE, &mut rng);
+    let (cv_xs, cv_ys) = generate_server_data(CROSS_VALIDATION_SET_SIZE, &mut rng);
+
+    let app_data = AppData {
+        model_type: ModelType::MultivariateGaussian,
+    };
+
+    let mut demo_data = DemoData {
+        xs,
+        cv_xs,
+        cv_ys,
+        model_type: app_data.model_type,
+        model: None,
+    };
+    // fitting is fast, so there's no learning thread and the model is refitted right after every change
+    demo_data.fit();
+    let data = Arc::new(Mutex::new(demo_data));
+
+    if let Some(export) = export {
+        let drawers: Vec<Box<dyn Drawer<AppData>>> = vec![Box::new(AnomalyVisualizer::new(data))];
+        if let Err(error) = export.run(&drawers, &app_data) {
+            eprintln!("error: {}", error);
+            process::exit(1);
+        }
+        return;
+    }
+
+    let window = WindowDesc::new(get_ui_builder(data))
+        .window_size(Size::new(WINDOW_WIDTH, WINDOW_HEIGHT))
+        .title(
+            LocalizedString::new("Anomaly Detection Demo")
+                .with_placeholder("anomaly-detection-demo"),
+        );
+
+    AppLauncher::with_window(window)
+        .use_simple_logger()
+        .launch(app_data)
+        .expect("launch failed");
+}
+
+// generate_server_data generates the examples similar to ex8data1: correlated normal examples around DATA_CENTER
+// and a few anomalies all over the chart, ys[i] is true for the anomalies
+fn generate_server_data(number_of_points: usize, rng: &mut ThreadRng) -> (Vec<[f64; 2]>, Vec<bool>) {
+    let mut xs = Vec::new();
+    let mut ys = Vec::new();
+
+    for _ in 0..number_of_points {
+        let is_anomaly = rng.gen_bool(ANOMALY_SHARE);
+
+        if is_anomaly {
+            xs.push([rng.gen_range(0.0..MAX_VALUE), rng.gen_range(0.0..MAX_VALUE)]);
+        } else {
+            let a = DATA_STANDARD_DEVIATION * standard_normal(rng);
+            let b = DATA_STANDARD_DEVIATION * standard_normal(rng);
+            let correlated = FEATURE_CORRELATION * a + (1.0 - FEATURE_CORRELATION.powi(2)).sqrt() * b;
+
+            xs.push([DATA_CENTER[0] + a, DATA_CENTER[1] + correlated]);
+        }
+        ys.push(is_anomaly);
+    }
+
+    (xs, ys)
+}
+
+fn get_ui_builder(
+    data: Arc<Mutex<DemoData>>,
+) -> impl Fn() -> ControllerHost<Flex<AppData>, SettingsController> {
+    move || {
+        Flex::<AppData>::column()
+            .with_child(Canvas::<AppData>::new(
+                Duration::from_millis(REFRESH_PERIOD),
+                Box::new(AnomalyVisualizer::new(data.clone())),
+            ))
+            .with_child(
+                Flex::row()
+                    .with_child(Label::new("density model:").fix_width(CONTROL_WIDTH))
+                    .with_child(
+                        RadioGroup::new(vec![
+                            ("gaussian per feature", ModelType::Gaussian),
+                            ("multivariate gaussian", ModelType::MultivariateGaussian),
+                        ])
+                            .lens(AppData::model_type),
+                    ),
+            )
+            .controller(SettingsController::new(data.clone()))
+    }
+}
@@ -0,0 +1,27 @@
+use druid::widget::prelude::*;
+use druid::widget::Controller;
+use druid::Data;
+use std::sync::{Arc, Mutex};
+use crate::app_data::AppData;
+use crate::demo_data::DemoData;
+
+// SettingsController refits the density when another model is chosen in the UI
+pub struct SettingsController {
+    data: Arc<Mutex<DemoData>>,
+}
+
+impl SettingsController {
+    pub fn new(data: Arc<Mutex<DemoData>>) -> SettingsController {
+        SettingsController { data }
+    }
+}
+
+impl<W: Widget<AppData>> Controller<AppData, W> for SettingsController {
+    fn update(&mut self, child: &mut W, ctx: &mut UpdateCtx, old_data: &AppData, data: &AppData, env: &Env) {
+        if !old_data.same(data) {
+            self.data.lock().unwrap().set_model_type(data.model_type);
+        }
+
+        child.update(ctx, old_data, data, env)
+    }
+}